@@ -4,6 +4,7 @@ use crate::request::AccountRequest;
 use crate::request::Request;
 use crate::{ROOT_URL, STATIC_URL_ERROR};
 use getset::{Getters, Setters};
+use serde_json::{Map, Value};
 
 const ACCOUNT_SEGMENT: &str = "account";
 
@@ -28,6 +29,11 @@ pub struct Account {
     /// A human-readable message giving more details about the status of the
     /// account.
     status_message: String,
+    /// Any fields returned by the API which are not otherwise captured.
+    ///
+    /// *Note:* With the `strict` feature enabled these cause an error instead.
+    #[serde(flatten, deserialize_with = "super::deserialize_extra")]
+    extra: Map<String, Value>,
 }
 
 impl Account {
@@ -1,11 +1,16 @@
 use super::{ApiLinks, ApiMeta};
 use super::{HasPagination, HasResponse, HasValue};
+use crate::error::Error;
 use crate::method::{Get, List};
 use crate::request::ActionRequest;
-use crate::request::Request;
+use crate::request::{Executable, Request};
+use crate::DigitalOcean;
 use crate::{ROOT_URL, STATIC_URL_ERROR};
 use chrono::{DateTime, Utc};
 use getset::{Getters, Setters};
+use serde_json::{Map, Value};
+use std::thread;
+use std::time::{Duration, Instant};
 use url::Url;
 
 const ACTIONS_SEGMENT: &str = "actions";
@@ -35,6 +40,12 @@ pub struct Action {
     status: String,
     /// The type of action that the event is executing (reboot, power_off,
     /// etc.).
+    ///
+    /// *Note:* Since `type` is a keyword in Rust `kind` is used instead.
+    #[serde(rename = "type")]
+    kind: String,
+    /// A time value given in ISO8601 combined date and time format that
+    /// represents when the action was initiated.
     started_at: DateTime<Utc>,
     /// A time value given in ISO8601 combined date and time format that
     /// represents when the action was completed.
@@ -50,6 +61,11 @@ pub struct Action {
     // region: Option<Region>,
     /// A slug representing the region where the action occurred.
     region_slug: Option<String>,
+    /// Any fields returned by the API which are not otherwise captured.
+    ///
+    /// *Note:* With the `strict` feature enabled these cause an error instead.
+    #[serde(flatten, deserialize_with = "super::deserialize_extra")]
+    extra: Map<String, Value>,
 }
 
 impl Action {
@@ -72,6 +88,32 @@ impl Action {
 
         Request::new(url)
     }
+    /// Poll the action every `poll_interval` until it has completed,
+    /// returning the completed action.
+    ///
+    /// Returns `Error::ActionErrored` if the action errors, or
+    /// `Error::Timeout` if it is still in progress after `timeout`.
+    pub fn wait(
+        &self,
+        client: &DigitalOcean,
+        poll_interval: Duration,
+        timeout: Duration,
+    ) -> Result<Action, Error> {
+        let start = Instant::now();
+        let mut action = self.clone();
+        loop {
+            match action.status().as_str() {
+                "completed" => return Ok(action),
+                "errored" => return Err(Error::ActionErrored(action.id)),
+                _ => (),
+            }
+            if start.elapsed() > timeout {
+                return Err(Error::Timeout(format!("action {}", action.id)));
+            }
+            thread::sleep(poll_interval);
+            action = Action::get(action.id).execute(client)?;
+        }
+    }
 }
 
 /// Response type returned from Digital Ocean.
@@ -0,0 +1,517 @@
+use self::autoscale_pool_fields::{AutoscaleConfig, DropletTemplate, Utilization};
+use super::{ApiLinks, ApiMeta};
+use super::{HasPagination, HasResponse, HasValue};
+use crate::method::{Create, Delete, Get, List, Update};
+use crate::request::AutoscalePoolRequest;
+use crate::request::Request;
+use crate::{ROOT_URL, STATIC_URL_ERROR};
+use chrono::{DateTime, Utc};
+use getset::{Getters, Setters};
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::fmt::Display;
+use url::Url;
+
+const DROPLETS_SEGMENT: &str = "droplets";
+const AUTOSCALE_SEGMENT: &str = "autoscale";
+const MEMBERS_SEGMENT: &str = "members";
+const HISTORY_SEGMENT: &str = "history";
+
+/// Droplet autoscale pools manage a group of identical Droplets, created
+/// from a Droplet template, and scale the number of Droplets in the pool
+/// based on resource utilization or a fixed target.
+///
+/// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#tag/Droplet-Autoscale-Pools)
+#[derive(Deserialize, Serialize, Debug, Clone, Getters, Setters)]
+#[get = "pub"]
+pub struct AutoscalePool {
+    /// A unique identifier for each autoscale pool instance. This is
+    /// automatically generated upon autoscale pool creation.
+    id: String,
+    /// The human-readable name set for the autoscale pool.
+    name: String,
+    /// The scaling configuration for the autoscale pool.
+    config: AutoscaleConfig,
+    /// The template used to create each Droplet in the autoscale pool.
+    droplet_template: DropletTemplate,
+    /// A time value given in ISO8601 combined date and time format that
+    /// represents when the autoscale pool was created.
+    created_at: DateTime<Utc>,
+    /// A time value given in ISO8601 combined date and time format that
+    /// represents when the autoscale pool was last updated.
+    updated_at: DateTime<Utc>,
+    /// The number of active Droplets in the autoscale pool.
+    active_resources_count: usize,
+    /// The current status of the autoscale pool. This may be "active",
+    /// "deleting" or "error".
+    status: String,
+    /// The average resource utilization of the autoscale pool.
+    current_utilization: Option<Utilization>,
+    /// Any fields returned by the API which are not otherwise captured.
+    ///
+    /// *Note:* With the `strict` feature enabled these cause an error instead.
+    #[serde(flatten, deserialize_with = "super::deserialize_extra")]
+    extra: Map<String, Value>,
+}
+
+/// A Droplet which is a member of an autoscale pool.
+///
+/// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/autoscalepools_list_members)
+#[derive(Deserialize, Serialize, Debug, Clone, Getters, Setters)]
+#[get = "pub"]
+pub struct AutoscalePoolMember {
+    /// The unique identifier of the Droplet.
+    droplet_id: usize,
+    /// A time value given in ISO8601 combined date and time format that
+    /// represents when the Droplet was created.
+    created_at: DateTime<Utc>,
+    /// A time value given in ISO8601 combined date and time format that
+    /// represents when the Droplet was last updated.
+    updated_at: DateTime<Utc>,
+    /// The health status of the Droplet.
+    health_status: String,
+    /// The power status of the Droplet. This may be "provisioning",
+    /// "active", "deleting" or "off".
+    status: String,
+    /// The resource utilization of the Droplet.
+    current_utilization: Option<Utilization>,
+    /// Any fields returned by the API which are not otherwise captured.
+    ///
+    /// *Note:* With the `strict` feature enabled these cause an error instead.
+    #[serde(flatten, deserialize_with = "super::deserialize_extra")]
+    extra: Map<String, Value>,
+}
+
+/// A scaling event which has occurred in an autoscale pool.
+///
+/// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/autoscalepools_list_history)
+#[derive(Deserialize, Serialize, Debug, Clone, Getters, Setters)]
+#[get = "pub"]
+pub struct AutoscalePoolHistoryEvent {
+    /// The unique identifier of the history event.
+    history_event_id: String,
+    /// The number of Droplets in the pool before the scaling event.
+    current_instance_count: usize,
+    /// The number of Droplets in the pool after the scaling event.
+    desired_instance_count: usize,
+    /// The reason for the scaling event. This may be "CONFIGURATION_CHANGE",
+    /// "SCALE_UP" or "SCALE_DOWN".
+    reason: String,
+    /// The status of the scaling event. This may be "in_progress", "success"
+    /// or "error".
+    status: String,
+    /// A time value given in ISO8601 combined date and time format that
+    /// represents when the scaling event was created.
+    created_at: DateTime<Utc>,
+    /// A time value given in ISO8601 combined date and time format that
+    /// represents when the scaling event was last updated.
+    updated_at: DateTime<Utc>,
+    /// Any fields returned by the API which are not otherwise captured.
+    ///
+    /// *Note:* With the `strict` feature enabled these cause an error instead.
+    #[serde(flatten, deserialize_with = "super::deserialize_extra")]
+    extra: Map<String, Value>,
+}
+
+/// Fields which exists inside autoscale pools.
+pub mod autoscale_pool_fields {
+    use serde::Serialize;
+    use std::fmt::Display;
+
+    /// This exists in the `config` field of an autoscale pool.
+    ///
+    /// A pool either holds a fixed number of Droplets (`target_number_instances`)
+    /// or scales between `min_instances` and `max_instances` to reach the
+    /// target CPU and/or memory utilization.
+    #[derive(Deserialize, Serialize, Debug, Clone, Default)]
+    pub struct AutoscaleConfig {
+        /// The minimum number of Droplets in a dynamic pool.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub min_instances: Option<usize>,
+        /// The maximum number of Droplets in a dynamic pool.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub max_instances: Option<usize>,
+        /// The target average CPU utilization of a dynamic pool, between
+        /// `0.05` and `1.00`.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub target_cpu_utilization: Option<f64>,
+        /// The target average memory utilization of a dynamic pool, between
+        /// `0.05` and `1.00`.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub target_memory_utilization: Option<f64>,
+        /// The number of minutes to wait between scaling events of a dynamic
+        /// pool.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub cooldown_minutes: Option<usize>,
+        /// The fixed number of Droplets in a static pool.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub target_number_instances: Option<usize>,
+    }
+    impl AutoscaleConfig {
+        /// A static pool which always holds `instances` Droplets.
+        pub fn fixed(instances: usize) -> Self {
+            AutoscaleConfig {
+                target_number_instances: Some(instances),
+                ..Default::default()
+            }
+        }
+        /// A dynamic pool which scales between `min` and `max` Droplets.
+        /// Be sure to set a utilization target as well.
+        pub fn dynamic(min: usize, max: usize) -> Self {
+            AutoscaleConfig {
+                min_instances: Some(min),
+                max_instances: Some(max),
+                ..Default::default()
+            }
+        }
+        pub fn target_cpu_utilization(mut self, val: f64) -> Self {
+            self.target_cpu_utilization = Some(val);
+            self
+        }
+        pub fn target_memory_utilization(mut self, val: f64) -> Self {
+            self.target_memory_utilization = Some(val);
+            self
+        }
+        pub fn cooldown_minutes(mut self, val: usize) -> Self {
+            self.cooldown_minutes = Some(val);
+            self
+        }
+    }
+
+    /// This exists in the `droplet_template` field of an autoscale pool.
+    ///
+    /// Each Droplet in a pool is created from its template. The pool names
+    /// its Droplets itself, so a template has no name.
+    #[derive(Deserialize, Serialize, Debug, Clone)]
+    pub struct DropletTemplate {
+        /// The slug identifier for the region the Droplets are created in.
+        pub region: String,
+        /// The slug identifier for the size of the Droplets.
+        pub size: String,
+        /// The image id or slug used to create the Droplets.
+        pub image: String,
+        /// The IDs or fingerprints of the SSH keys to embed in the Droplets.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        pub ssh_keys: Vec<String>,
+        /// The tags to apply to the Droplets.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        pub tags: Vec<String>,
+        /// Whether IPv6 is enabled on the Droplets.
+        #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+        pub ipv6: bool,
+        /// 'User data' used to configure the Droplets on first boot.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub user_data: Option<String>,
+        /// The VPC the Droplets are placed in.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub vpc_uuid: Option<String>,
+        /// Whether to install the DigitalOcean agent used for providing
+        /// access to the Droplet web console.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub with_droplet_agent: Option<bool>,
+        /// The project the Droplets are assigned to.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub project_id: Option<String>,
+    }
+    impl DropletTemplate {
+        /// A template for Droplets of `size`, created from `image` in
+        /// `region`.
+        pub fn new<R, Z, D>(region: R, size: Z, image: D) -> Self
+        where
+            R: Into<String>,
+            Z: Into<String>,
+            D: Display,
+        {
+            DropletTemplate {
+                region: region.into(),
+                size: size.into(),
+                image: image.to_string(),
+                ssh_keys: Vec::new(),
+                tags: Vec::new(),
+                ipv6: false,
+                user_data: None,
+                vpc_uuid: None,
+                with_droplet_agent: None,
+                project_id: None,
+            }
+        }
+        pub fn ssh_keys<D>(mut self, val: Vec<D>) -> Self
+        where
+            D: Display,
+        {
+            self.ssh_keys = val.iter().map(ToString::to_string).collect();
+            self
+        }
+        pub fn tags(mut self, val: Vec<String>) -> Self {
+            self.tags = val;
+            self
+        }
+        pub fn ipv6(mut self, val: bool) -> Self {
+            self.ipv6 = val;
+            self
+        }
+        pub fn user_data<S>(mut self, val: S) -> Self
+        where
+            S: Into<String>,
+        {
+            self.user_data = Some(val.into());
+            self
+        }
+        pub fn vpc_uuid<S>(mut self, val: S) -> Self
+        where
+            S: Into<String>,
+        {
+            self.vpc_uuid = Some(val.into());
+            self
+        }
+        pub fn with_droplet_agent(mut self, val: bool) -> Self {
+            self.with_droplet_agent = Some(val);
+            self
+        }
+        pub fn project_id<S>(mut self, val: S) -> Self
+        where
+            S: Into<String>,
+        {
+            self.project_id = Some(val.into());
+            self
+        }
+    }
+
+    /// This exists in the `current_utilization` field of an autoscale pool
+    /// and its members.
+    #[derive(Deserialize, Serialize, Debug, Clone)]
+    pub struct Utilization {
+        /// The average memory utilization, between `0.0` and `1.0`.
+        pub memory: Option<f64>,
+        /// The average CPU utilization, between `0.0` and `1.0`.
+        pub cpu: Option<f64>,
+    }
+}
+
+impl AutoscalePool {
+    /// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/autoscalepools_create)
+    pub fn create<S>(
+        name: S,
+        config: AutoscaleConfig,
+        droplet_template: DropletTemplate,
+    ) -> AutoscalePoolRequest<Create, AutoscalePool>
+    where
+        S: AsRef<str> + Serialize + Display,
+    {
+        let mut url = ROOT_URL.clone();
+        url.path_segments_mut()
+            .expect(STATIC_URL_ERROR)
+            .push(DROPLETS_SEGMENT)
+            .push(AUTOSCALE_SEGMENT);
+
+        let mut req = Request::new(url);
+        req.set_body(json!({
+            "name": name,
+            "config": config,
+            "droplet_template": droplet_template,
+        }));
+        req
+    }
+
+    /// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/autoscalepools_list)
+    pub fn list() -> AutoscalePoolRequest<List, Vec<AutoscalePool>> {
+        let mut url = ROOT_URL.clone();
+        url.path_segments_mut()
+            .expect(STATIC_URL_ERROR)
+            .push(DROPLETS_SEGMENT)
+            .push(AUTOSCALE_SEGMENT);
+
+        Request::new(url)
+    }
+
+    /// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/autoscalepools_get)
+    pub fn get<S>(id: S) -> AutoscalePoolRequest<Get, AutoscalePool>
+    where
+        S: AsRef<str> + Serialize + Display,
+    {
+        let mut url = ROOT_URL.clone();
+        url.path_segments_mut()
+            .expect(STATIC_URL_ERROR)
+            .push(DROPLETS_SEGMENT)
+            .push(AUTOSCALE_SEGMENT)
+            .push(id.as_ref());
+
+        Request::new(url)
+    }
+
+    /// **Note:** The name, config and Droplet template are all required as
+    /// the autoscale pool is replaced entirely.
+    ///
+    /// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/autoscalepools_update)
+    pub fn update<I, N>(
+        id: I,
+        name: N,
+        config: AutoscaleConfig,
+        droplet_template: DropletTemplate,
+    ) -> AutoscalePoolRequest<Update, AutoscalePool>
+    where
+        I: AsRef<str> + Serialize + Display,
+        N: AsRef<str> + Serialize + Display,
+    {
+        let mut url = ROOT_URL.clone();
+        url.path_segments_mut()
+            .expect(STATIC_URL_ERROR)
+            .push(DROPLETS_SEGMENT)
+            .push(AUTOSCALE_SEGMENT)
+            .push(id.as_ref());
+
+        let mut req = Request::new(url);
+        req.set_body(json!({
+            "name": name,
+            "config": config,
+            "droplet_template": droplet_template,
+        }));
+        req
+    }
+
+    /// **Note:** The Droplets in the pool are deleted along with it.
+    ///
+    /// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/autoscalepools_delete)
+    pub fn delete<S>(id: S) -> AutoscalePoolRequest<Delete, ()>
+    where
+        S: AsRef<str> + Serialize + Display,
+    {
+        let mut url = ROOT_URL.clone();
+        url.path_segments_mut()
+            .expect(STATIC_URL_ERROR)
+            .push(DROPLETS_SEGMENT)
+            .push(AUTOSCALE_SEGMENT)
+            .push(id.as_ref());
+
+        Request::new(url)
+    }
+}
+
+impl AutoscalePoolRequest<List, Vec<AutoscalePool>> {
+    /// Restrict the results to autoscale pools with the given name.
+    ///
+    /// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/autoscalepools_list)
+    pub fn name<S>(mut self, name: S) -> Self
+    where
+        S: AsRef<str> + Serialize + Display,
+    {
+        self.url_mut()
+            .query_pairs_mut()
+            .append_pair("name", name.as_ref());
+
+        self
+    }
+}
+
+impl AutoscalePoolRequest<Get, AutoscalePool> {
+    /// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/autoscalepools_list_members)
+    pub fn members(mut self) -> AutoscalePoolRequest<List, Vec<AutoscalePoolMember>> {
+        self.url_mut()
+            .path_segments_mut()
+            .expect(STATIC_URL_ERROR)
+            .push(MEMBERS_SEGMENT);
+
+        self.transmute()
+    }
+    /// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/autoscalepools_list_history)
+    pub fn history(mut self) -> AutoscalePoolRequest<List, Vec<AutoscalePoolHistoryEvent>> {
+        self.url_mut()
+            .path_segments_mut()
+            .expect(STATIC_URL_ERROR)
+            .push(HISTORY_SEGMENT);
+
+        self.transmute()
+    }
+}
+
+/// Response type returned from Digital Ocean.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct AutoscalePoolResponse {
+    autoscale_pool: AutoscalePool,
+}
+
+impl HasResponse for AutoscalePool {
+    type Response = AutoscalePoolResponse;
+}
+
+impl HasValue for AutoscalePoolResponse {
+    type Value = AutoscalePool;
+    fn value(self) -> AutoscalePool {
+        self.autoscale_pool
+    }
+}
+
+/// Response type returned from Digital Ocean.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct AutoscalePoolListResponse {
+    autoscale_pools: Vec<AutoscalePool>,
+    links: ApiLinks,
+    meta: ApiMeta,
+}
+
+impl HasResponse for Vec<AutoscalePool> {
+    type Response = AutoscalePoolListResponse;
+}
+
+impl HasPagination for AutoscalePoolListResponse {
+    fn next_page(&self) -> Option<Url> {
+        self.links.next()
+    }
+}
+
+impl HasValue for AutoscalePoolListResponse {
+    type Value = Vec<AutoscalePool>;
+    fn value(self) -> Vec<AutoscalePool> {
+        self.autoscale_pools
+    }
+}
+
+/// Response type returned from Digital Ocean.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct AutoscalePoolMemberListResponse {
+    droplets: Vec<AutoscalePoolMember>,
+    links: ApiLinks,
+    meta: ApiMeta,
+}
+
+impl HasResponse for Vec<AutoscalePoolMember> {
+    type Response = AutoscalePoolMemberListResponse;
+}
+
+impl HasPagination for AutoscalePoolMemberListResponse {
+    fn next_page(&self) -> Option<Url> {
+        self.links.next()
+    }
+}
+
+impl HasValue for AutoscalePoolMemberListResponse {
+    type Value = Vec<AutoscalePoolMember>;
+    fn value(self) -> Vec<AutoscalePoolMember> {
+        self.droplets
+    }
+}
+
+/// Response type returned from Digital Ocean.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct AutoscalePoolHistoryListResponse {
+    history: Vec<AutoscalePoolHistoryEvent>,
+    links: ApiLinks,
+    meta: ApiMeta,
+}
+
+impl HasResponse for Vec<AutoscalePoolHistoryEvent> {
+    type Response = AutoscalePoolHistoryListResponse;
+}
+
+impl HasPagination for AutoscalePoolHistoryListResponse {
+    fn next_page(&self) -> Option<Url> {
+        self.links.next()
+    }
+}
+
+impl HasValue for AutoscalePoolHistoryListResponse {
+    type Value = Vec<AutoscalePoolHistoryEvent>;
+    fn value(self) -> Vec<AutoscalePoolHistoryEvent> {
+        self.history
+    }
+}
@@ -7,9 +7,19 @@ use crate::{ROOT_URL, STATIC_URL_ERROR};
 use chrono::{DateTime, Utc};
 use getset::{Getters, Setters};
 use serde::Serialize;
+use serde_json::{Map, Value};
 use std::fmt::Display;
 use url::Url;
 
+#[cfg(feature = "certificate-validation")]
+use self::certificate_fields::LeafCertificate;
+#[cfg(feature = "certificate-validation")]
+use crate::error::Error;
+#[cfg(feature = "certificate-validation")]
+use chrono::TimeZone;
+#[cfg(feature = "certificate-validation")]
+use openssl::{asn1::Asn1Time, pkey::PKey, x509::X509VerifyResult, x509::X509};
+
 const CERTIFICATES_SEGMENT: &str = "certificates";
 
 /// SSL certificates may be uploaded to DigitalOcean where they will be placed
@@ -33,9 +43,32 @@ pub struct Certificate {
     /// A time value given in ISO8601 combined date and time format that
     /// represents when the certificate was created.
     created_at: DateTime<Utc>,
+    /// A string representing the type of the certificate. The value will be
+    /// "custom" for a user-uploaded certificate or "lets_encrypt" for one
+    /// automatically generated with Let's Encrypt.
+    ///
+    /// *Note:* Since `type` is a keyword in Rust `kind` is used instead.
+    #[serde(rename = "type")]
+    kind: String, // 'type' is reserved in Rust.
+    /// A string representing the current state of the certificate. It may be
+    /// "pending", "verified", or "error". A certificate should be "verified"
+    /// before it is used in a forwarding rule.
+    state: String,
+    /// An array of fully qualified domain names (FQDNs) for which the
+    /// certificate was issued.
+    #[serde(default)]
+    dns_names: Vec<String>,
+    /// Any fields returned by the API which are not otherwise captured.
+    ///
+    /// *Note:* With the `strict` feature enabled these cause an error instead.
+    #[serde(flatten, deserialize_with = "super::deserialize_extra")]
+    extra: Map<String, Value>,
 }
 
 impl Certificate {
+    /// With the `certificate-validation` feature enabled, the PEM-formatted
+    /// inputs may be checked with `.validate()` before the request is sent.
+    ///
     /// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#create-a-new-certificate)
     pub fn create<S>(
         name: S,
@@ -61,6 +94,33 @@ impl Certificate {
         req
     }
 
+    /// Create a certificate automatically generated and managed by Let's
+    /// Encrypt. The domains must be managed by DigitalOcean's DNS.
+    ///
+    /// **Note:** The certificate will initially be "pending" until it is
+    /// issued. Check its `state` before using it.
+    ///
+    /// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#create-a-new-certificate)
+    pub fn lets_encrypt<S>(name: S, dns_names: Vec<S>) -> CertificateRequest<Create, Certificate>
+    where
+        S: AsRef<str> + Serialize + Display,
+    {
+        let mut url = ROOT_URL.clone();
+        url.path_segments_mut()
+            .expect(STATIC_URL_ERROR)
+            .push(CERTIFICATES_SEGMENT);
+
+        let mut req = Request::new(url);
+
+        req.set_body(json!({
+            "name": name,
+            "type": "lets_encrypt",
+            "dns_names": dns_names,
+        }));
+
+        req
+    }
+
     /// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#list-all-certificates)
     pub fn list() -> CertificateRequest<List, Vec<Certificate>> {
         let mut url = ROOT_URL.clone();
@@ -114,6 +174,97 @@ impl CertificateRequest<Create, Certificate> {
     }
 }
 
+/// Fields which are produced when checking certificates locally.
+#[cfg(feature = "certificate-validation")]
+pub mod certificate_fields {
+    use chrono::{DateTime, Utc};
+    use getset::Getters;
+
+    /// Details of a leaf certificate which passed local validation.
+    #[derive(Debug, Clone, Getters)]
+    #[get = "pub"]
+    pub struct LeafCertificate {
+        /// When the leaf certificate expires.
+        pub(crate) not_after: DateTime<Utc>,
+        /// The DNS names in the subject alternative names (SANs) of the leaf
+        /// certificate.
+        pub(crate) dns_names: Vec<String>,
+    }
+}
+
+#[cfg(feature = "certificate-validation")]
+impl CertificateRequest<Create, Certificate> {
+    /// Parse the PEM-formatted inputs of this request locally, without
+    /// sending anything to Digital Ocean. This checks that the private key
+    /// matches the leaf certificate and that each certificate in the chain (if
+    /// any) issued the one before it, starting from the leaf.
+    ///
+    /// **Note:** This requires the `certificate-validation` feature.
+    pub fn validate(&self) -> Result<LeafCertificate, Error> {
+        let invalid = |message: String| Error::InvalidCertificate(message);
+        let pem = |field: &str| -> Result<&str, Error> {
+            self.body()[field]
+                .as_str()
+                .ok_or_else(|| invalid(format!("{} is not set", field)))
+        };
+
+        let leaf = X509::from_pem(pem("leaf_certificate")?.as_bytes())
+            .map_err(|e| invalid(format!("leaf_certificate could not be parsed: {}", e)))?;
+        let private_key = PKey::private_key_from_pem(pem("private_key")?.as_bytes())
+            .map_err(|e| invalid(format!("private_key could not be parsed: {}", e)))?;
+        let leaf_key = leaf
+            .public_key()
+            .map_err(|e| invalid(format!("leaf_certificate has no public key: {}", e)))?;
+        if !leaf_key.public_eq(&private_key) {
+            return Err(invalid(String::from(
+                "private_key does not match leaf_certificate",
+            )));
+        }
+
+        if self.body()["certificate_chain"].is_string() {
+            let chain = X509::stack_from_pem(pem("certificate_chain")?.as_bytes())
+                .map_err(|e| invalid(format!("certificate_chain could not be parsed: {}", e)))?;
+            let mut current = &leaf;
+            for (index, issuer) in chain.iter().enumerate() {
+                let verified = issuer
+                    .public_key()
+                    .and_then(|key| current.verify(&key))
+                    .unwrap_or(false);
+                if issuer.issued(current) != X509VerifyResult::OK || !verified {
+                    return Err(invalid(format!(
+                        "certificate {} of certificate_chain did not issue the certificate before it",
+                        index
+                    )));
+                }
+                current = issuer;
+            }
+        }
+
+        let not_after = Asn1Time::from_unix(0)
+            .and_then(|epoch| epoch.diff(leaf.not_after()))
+            .ok()
+            .and_then(|diff| {
+                let seconds = i64::from(diff.days) * 86_400 + i64::from(diff.secs);
+                Utc.timestamp_opt(seconds, 0).single()
+            })
+            .ok_or_else(|| invalid(String::from("leaf_certificate has an invalid expiry")))?;
+        let dns_names = leaf
+            .subject_alt_names()
+            .map(|names| {
+                names
+                    .iter()
+                    .filter_map(|name| name.dnsname().map(String::from))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Ok(LeafCertificate {
+            not_after,
+            dns_names,
+        })
+    }
+}
+
 /// Response type returned from Digital Ocean.
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct CertificateResponse {
@@ -6,6 +6,7 @@ use crate::{ROOT_URL, STATIC_URL_ERROR};
 use chrono::{DateTime, Utc};
 use getset::{Getters, Setters};
 use serde::Serialize;
+use serde_json::{Map, Value};
 use std::fmt::Display;
 
 const IMAGES_SEGMENT: &str = "images";
@@ -46,6 +47,12 @@ pub struct CustomImage {
     description: String,
     /// The status of the image
     status: String,
+    /// Any fields returned by the API which are not otherwise captured.
+    ///
+    /// *Note:* With the `strict` feature enabled these cause an error instead.
+    #[get = "pub"]
+    #[serde(flatten, deserialize_with = "super::deserialize_extra")]
+    extra: Map<String, Value>,
 }
 
 impl CustomImage {
@@ -6,6 +6,7 @@ use crate::request::Request;
 use crate::{ROOT_URL, STATIC_URL_ERROR};
 use getset::{Getters, Setters};
 use serde::Serialize;
+use serde_json::{Map, Value};
 use std::fmt::Display;
 use std::net::IpAddr;
 use url::Url;
@@ -32,6 +33,11 @@ pub struct Domain {
     /// also be used to get information about the SOA record, which is created
     /// automatically and is not accessible as an individual record resource.
     zone_file: Option<String>,
+    /// Any fields returned by the API which are not otherwise captured.
+    ///
+    /// *Note:* With the `strict` feature enabled these cause an error instead.
+    #[serde(flatten, deserialize_with = "super::deserialize_extra")]
+    extra: Map<String, Value>,
 }
 
 impl Domain {
@@ -54,6 +60,26 @@ impl Domain {
         req
     }
 
+    /// Create a domain without an IP address, in which case no A record is
+    /// created for it.
+    ///
+    /// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#create-a-new-domain)
+    pub fn create_without_ip<N>(name: N) -> DomainRequest<Create, Domain>
+    where
+        N: AsRef<str> + Serialize + Display,
+    {
+        let mut url = ROOT_URL.clone();
+        url.path_segments_mut()
+            .expect(STATIC_URL_ERROR)
+            .push(DOMAINS_SEGMENT);
+
+        let mut req = Request::new(url);
+        req.set_body(json!({
+            "name": name,
+        }));
+        req
+    }
+
     /// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#list-all-domains)
     pub fn list() -> DomainRequest<List, Vec<Domain>> {
         let mut url = ROOT_URL.clone();
@@ -1,11 +1,14 @@
+use self::domain_record_fields::RecordData;
 use super::domain::Domain;
 use super::{ApiLinks, ApiMeta};
 use super::{HasPagination, HasResponse, HasValue};
+use crate::error::Error;
 use crate::method::{Create, Delete, Get, List, Update};
 use crate::request::{DomainRecordRequest, DomainRequest};
 use crate::STATIC_URL_ERROR;
 use getset::{Getters, Setters};
 use serde::Serialize;
+use serde_json::{Map, Value};
 use std::fmt::Display;
 use url::Url;
 
@@ -44,6 +47,233 @@ pub struct DomainRecord {
     ttl: usize,
     /// The weight for SRV records.
     weight: Option<usize>,
+    /// An unsigned integer between 0-255 used for CAA records.
+    #[serde(default)]
+    flags: Option<u8>,
+    /// The parameter tag for CAA records. Valid values are "issue",
+    /// "issuewild", or "iodef".
+    #[serde(default)]
+    tag: Option<String>,
+    /// Any fields returned by the API which are not otherwise captured.
+    ///
+    /// *Note:* With the `strict` feature enabled these cause an error instead.
+    #[serde(flatten, deserialize_with = "super::deserialize_extra")]
+    extra: Map<String, Value>,
+}
+
+/// Fields which exists inside Domain Records.
+pub mod domain_record_fields {
+    use crate::error::Error;
+    use serde_json::Value;
+    use std::net::{Ipv4Addr, Ipv6Addr};
+
+    /// The typed data of a domain record. Unlike the raw `kind` and `data`
+    /// strings this is checked locally before any request is sent.
+    ///
+    /// Host names may be relative to the domain, fully qualified (ending in
+    /// a `.`), or `@` to refer to the domain itself.
+    ///
+    /// When serialized the record type is under `type` and the data under
+    /// `data`, ex: `{ "type": "MX", "data": { "priority": 10, "host": "mail" } }`.
+    #[allow(clippy::upper_case_acronyms)]
+    #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+    #[serde(tag = "type", content = "data")]
+    pub enum RecordData {
+        /// Maps a host name to an IPv4 address.
+        A(Ipv4Addr),
+        /// Maps a host name to an IPv6 address.
+        AAAA(Ipv6Addr),
+        /// Defines an alias for another host name.
+        CNAME(String),
+        /// Specifies the mail server responsible for the domain.
+        MX { priority: usize, host: String },
+        /// Arbitrary text, often used for verification or SPF.
+        TXT(String),
+        /// Specifies the location of a service.
+        SRV {
+            priority: usize,
+            weight: usize,
+            port: usize,
+            target: String,
+        },
+        /// Specifies which certificate authorities may issue certificates for
+        /// the domain.
+        CAA {
+            flags: u8,
+            tag: String,
+            value: String,
+        },
+        /// Delegates a subdomain to a set of name servers.
+        NS(String),
+    }
+
+    impl RecordData {
+        /// The type of the record as used by the API (ex: "A", "MX").
+        pub fn kind(&self) -> &'static str {
+            match self {
+                RecordData::A(_) => "A",
+                RecordData::AAAA(_) => "AAAA",
+                RecordData::CNAME(_) => "CNAME",
+                RecordData::MX { .. } => "MX",
+                RecordData::TXT(_) => "TXT",
+                RecordData::SRV { .. } => "SRV",
+                RecordData::CAA { .. } => "CAA",
+                RecordData::NS(_) => "NS",
+            }
+        }
+
+        /// The value of the `data` attribute of the record.
+        pub fn data(&self) -> String {
+            match self {
+                RecordData::A(address) => address.to_string(),
+                RecordData::AAAA(address) => address.to_string(),
+                RecordData::CNAME(host) | RecordData::NS(host) => host.clone(),
+                RecordData::MX { host, .. } => host.clone(),
+                RecordData::TXT(text) => text.clone(),
+                RecordData::SRV { target, .. } => target.clone(),
+                RecordData::CAA { value, .. } => value.clone(),
+            }
+        }
+
+        /// Check the record data would be accepted by the API.
+        pub fn validate(&self) -> Result<(), Error> {
+            match self {
+                RecordData::A(_) | RecordData::AAAA(_) => Ok(()),
+                RecordData::CNAME(host) | RecordData::NS(host) => validate_host(host),
+                RecordData::MX { priority, host } => {
+                    validate_u16("priority", *priority)?;
+                    validate_host(host)
+                }
+                RecordData::TXT(text) if text.is_empty() => {
+                    Err(invalid(String::from("TXT data must not be empty")))
+                }
+                RecordData::TXT(_) => Ok(()),
+                RecordData::SRV {
+                    priority,
+                    weight,
+                    port,
+                    target,
+                } => {
+                    validate_u16("priority", *priority)?;
+                    validate_u16("weight", *weight)?;
+                    validate_u16("port", *port)?;
+                    validate_host(target)
+                }
+                RecordData::CAA { tag, value, .. } => {
+                    match tag.as_str() {
+                        "issue" | "issuewild" | "iodef" => (),
+                        _ => {
+                            return Err(invalid(format!(
+                                "CAA tag must be issue, issuewild or iodef, not {:?}",
+                                tag
+                            )))
+                        }
+                    }
+                    if value.is_empty() {
+                        return Err(invalid(String::from("CAA value must not be empty")));
+                    }
+                    Ok(())
+                }
+            }
+        }
+
+        /// The attributes of a request body describing this record.
+        pub(crate) fn to_body(&self) -> Value {
+            let mut body = json!({
+                "type": self.kind(),
+                "data": self.data(),
+            });
+            match self {
+                RecordData::MX { priority, .. } => {
+                    body["priority"] = json!(priority);
+                }
+                RecordData::SRV {
+                    priority,
+                    weight,
+                    port,
+                    ..
+                } => {
+                    body["priority"] = json!(priority);
+                    body["weight"] = json!(weight);
+                    body["port"] = json!(port);
+                }
+                RecordData::CAA { flags, tag, .. } => {
+                    body["flags"] = json!(flags);
+                    body["tag"] = json!(tag);
+                }
+                _ => (),
+            }
+            body
+        }
+    }
+
+    fn invalid(message: String) -> Error {
+        Error::InvalidDomainRecord(message)
+    }
+
+    fn validate_u16(field: &str, val: usize) -> Result<(), Error> {
+        if val > usize::from(u16::MAX) {
+            return Err(invalid(format!(
+                "{} must be at most 65535, not {}",
+                field, val
+            )));
+        }
+        Ok(())
+    }
+
+    // Host names are checked per RFC 1035, with underscores permitted for
+    // service labels (ex: `_sip._tcp`).
+    fn validate_host(host: &str) -> Result<(), Error> {
+        if host == "@" {
+            return Ok(());
+        }
+        let name = host.strip_suffix('.').unwrap_or(host);
+        let valid_label = |label: &str| {
+            !label.is_empty()
+                && label.len() <= 63
+                && !label.starts_with('-')
+                && !label.ends_with('-')
+                && label
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        };
+        if name.is_empty() || name.len() > 253 || !name.split('.').all(valid_label) {
+            return Err(invalid(format!("{:?} is not a valid host name", host)));
+        }
+        Ok(())
+    }
+}
+
+impl DomainRecord {
+    /// The typed data of this record, if it is of a supported type and its
+    /// data could be parsed.
+    pub fn record_data(&self) -> Option<RecordData> {
+        let data = self.data.clone();
+        let record = match self.kind.as_str() {
+            "A" => RecordData::A(data.parse().ok()?),
+            "AAAA" => RecordData::AAAA(data.parse().ok()?),
+            "CNAME" => RecordData::CNAME(data),
+            "MX" => RecordData::MX {
+                priority: self.priority?,
+                host: data,
+            },
+            "TXT" => RecordData::TXT(data),
+            "SRV" => RecordData::SRV {
+                priority: self.priority?,
+                weight: self.weight?,
+                port: self.port?,
+                target: data,
+            },
+            "CAA" => RecordData::CAA {
+                flags: self.flags?,
+                tag: self.tag.clone()?,
+                value: data,
+            },
+            "NS" => RecordData::NS(data),
+            _ => return None,
+        };
+        Some(record)
+    }
 }
 
 impl DomainRequest<Get, Domain> {
@@ -59,6 +289,15 @@ impl DomainRequest<Get, Domain> {
 }
 
 impl DomainRecordRequest<List, Vec<DomainRecord>> {
+    // The name of the domain these records belong to, without a final dot.
+    pub(crate) fn domain_name(&self) -> String {
+        self.url()
+            .path_segments()
+            .and_then(|segments| segments.rev().nth(1))
+            .map(|domain| domain.trim_end_matches('.').to_string())
+            .expect("A records request always contains a domain.")
+    }
+
     /// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#create-a-new-domain-record)
     pub fn create<S>(
         mut self,
@@ -80,6 +319,56 @@ impl DomainRecordRequest<List, Vec<DomainRecord>> {
         self.transmute()
     }
 
+    /// Create a record from typed data, which is checked before the request
+    /// is built.
+    ///
+    /// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#create-a-new-domain-record)
+    pub fn create_record<S>(
+        mut self,
+        name: S,
+        data: RecordData,
+    ) -> Result<DomainRecordRequest<Create, DomainRecord>, Error>
+    where
+        S: AsRef<str> + Display + Serialize,
+    {
+        data.validate()?;
+
+        let mut body = data.to_body();
+        body["name"] = json!(name);
+        self.set_body(body);
+
+        Ok(self.transmute())
+    }
+
+    /// Restrict the results to records with the given fully qualified name
+    /// (ex: "www.example.com"). This is typically combined with `kind`.
+    ///
+    /// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#list-all-domain-records)
+    pub fn name<S>(mut self, name: S) -> Self
+    where
+        S: AsRef<str> + Display + Serialize,
+    {
+        self.url_mut()
+            .query_pairs_mut()
+            .append_pair("name", name.as_ref());
+
+        self
+    }
+
+    /// Restrict the results to records of the given type (ex: "A", "MX").
+    ///
+    /// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#list-all-domain-records)
+    pub fn kind<S>(mut self, kind: S) -> Self
+    where
+        S: AsRef<str> + Display + Serialize,
+    {
+        self.url_mut()
+            .query_pairs_mut()
+            .append_pair("type", kind.as_ref());
+
+        self
+    }
+
     /// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#retrieve-an-existing-domain-record)
     pub fn get(mut self, id: usize) -> DomainRecordRequest<Get, DomainRecord> {
         self.url_mut()
@@ -176,6 +465,20 @@ impl DomainRecordRequest<Update, DomainRecord> {
         self.body_mut()["data"] = json!(val);
         self
     }
+    /// Set the type, data and type specific attributes of the record from
+    /// typed data, which is checked first.
+    ///
+    /// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#domain-records)
+    pub fn record_data(mut self, val: RecordData) -> Result<Self, Error> {
+        val.validate()?;
+
+        if let Value::Object(attributes) = val.to_body() {
+            for (key, value) in attributes {
+                self.body_mut()[key] = value;
+            }
+        }
+        Ok(self)
+    }
     /// The priority for SRV and MX records.
     ///
     /// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#domain-records)
@@ -0,0 +1,339 @@
+use super::domain::Domain;
+use super::domain_record::domain_record_fields::RecordData;
+use super::domain_record::DomainRecord;
+use crate::error::Error;
+use crate::method::List;
+use crate::request::{DomainRecordRequest, Executable};
+use crate::DigitalOcean;
+use std::fmt::{self, Display};
+
+/// A record which should exist on a domain, used to build a [`RecordPlan`].
+///
+/// The name may be relative to the domain, fully qualified (ending in a
+/// `.`), or `@` for the domain itself.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct DesiredRecord {
+    /// The name of the record.
+    pub name: String,
+    /// The typed data of the record.
+    pub data: RecordData,
+    /// The time to live of the record. When `None` the TTL of an existing
+    /// record is left as it is, and new records use the API's default.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ttl: Option<usize>,
+}
+
+impl DesiredRecord {
+    pub fn new<S>(name: S, data: RecordData) -> Self
+    where
+        S: AsRef<str>,
+    {
+        DesiredRecord {
+            name: name.as_ref().to_string(),
+            data,
+            ttl: None,
+        }
+    }
+    pub fn ttl(mut self, ttl: usize) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+}
+
+/// A single change in a [`RecordPlan`].
+#[derive(Debug, Clone)]
+pub enum RecordChange {
+    /// A record which does not yet exist.
+    Create(DesiredRecord),
+    /// A record which exists with the same type, name and data, but whose
+    /// other attributes (ex: TTL, priority) differ.
+    Update {
+        current: DomainRecord,
+        desired: DesiredRecord,
+    },
+    /// A record which exists but is not desired.
+    Delete(DomainRecord),
+}
+
+impl Display for RecordChange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RecordChange::Create(desired) => {
+                write!(f, "+ {}", describe(&desired.name, &desired.data))?;
+                if let Some(ttl) = desired.ttl {
+                    write!(f, " (ttl {})", ttl)?;
+                }
+                Ok(())
+            }
+            RecordChange::Update { current, desired } => {
+                write!(f, "~ {}", describe(&desired.name, &desired.data))?;
+                if let Some(ttl) = desired.ttl.filter(|ttl| ttl != current.ttl()) {
+                    write!(f, " (ttl {} -> {})", current.ttl(), ttl)?;
+                }
+                Ok(())
+            }
+            RecordChange::Delete(current) => write!(
+                f,
+                "- {} {} {}",
+                current.name(),
+                current.kind(),
+                current.data()
+            ),
+        }
+    }
+}
+
+/// The changes needed to bring the records of a domain in line with a desired
+/// set of records. Records are matched on their type, name and data.
+///
+/// Inspect the plan with `changes()`, or print it, before calling `apply()`.
+///
+/// **Note:** The SOA record and NS records of the domain itself are managed by
+/// Digital Ocean and are never changed.
+#[derive(Debug, Clone)]
+pub struct RecordPlan {
+    domain: String,
+    changes: Vec<RecordChange>,
+}
+
+impl RecordPlan {
+    /// Compute the changes needed to turn `current` into `desired`.
+    ///
+    /// Changes are ordered as they will be applied: records which conflict
+    /// with a CNAME are deleted first, then records are updated and created,
+    /// then any remaining records are deleted so that names keep resolving.
+    pub fn new<S>(domain: S, current: Vec<DomainRecord>, desired: Vec<DesiredRecord>) -> Self
+    where
+        S: AsRef<str>,
+    {
+        let domain = domain.as_ref().trim_end_matches('.').to_string();
+        let mut current = current
+            .into_iter()
+            .filter_map(|record| {
+                let data = record.record_data()?;
+                match data {
+                    RecordData::NS(_) if record.name() == "@" => None,
+                    _ => Some((key(&domain, record.name(), &data), data, record)),
+                }
+            })
+            .map(Some)
+            .collect::<Vec<_>>();
+
+        let mut updates = Vec::new();
+        let mut creates = Vec::new();
+        for desired in desired {
+            let desired_key = key(&domain, &desired.name, &desired.data);
+            let found = current.iter_mut().find(|entry| {
+                entry
+                    .as_ref()
+                    .map_or(false, |(key, _, _)| *key == desired_key)
+            });
+            match found.and_then(Option::take) {
+                Some((_, data, record)) => {
+                    let ttl_differs = desired.ttl.map_or(false, |ttl| ttl != *record.ttl());
+                    if ttl_differs || !same_attributes(&data, &desired.data) {
+                        updates.push(RecordChange::Update {
+                            current: record,
+                            desired,
+                        });
+                    }
+                }
+                None => creates.push(desired),
+            }
+        }
+
+        // A CNAME cannot share its name with any other record.
+        let cname_names = creates
+            .iter()
+            .filter(|desired| desired.data.kind() == "CNAME")
+            .map(|desired| name(&domain, &desired.name))
+            .collect::<Vec<_>>();
+        let created_names = creates
+            .iter()
+            .map(|desired| name(&domain, &desired.name))
+            .collect::<Vec<_>>();
+        let (conflicting, deletes): (Vec<_>, Vec<_>) = current
+            .into_iter()
+            .flatten()
+            .map(|(key, _, record)| (key, record))
+            .partition(|(key, record)| {
+                cname_names.contains(&key.1)
+                    || (record.kind() == "CNAME" && created_names.contains(&key.1))
+            });
+
+        let changes = conflicting
+            .into_iter()
+            .map(|(_, record)| RecordChange::Delete(record))
+            .chain(updates)
+            .chain(creates.into_iter().map(RecordChange::Create))
+            .chain(
+                deletes
+                    .into_iter()
+                    .map(|(_, record)| RecordChange::Delete(record)),
+            )
+            .collect();
+
+        RecordPlan { domain, changes }
+    }
+
+    /// The name of the domain the plan applies to.
+    pub fn domain(&self) -> &str {
+        &self.domain
+    }
+
+    /// The changes in the order they will be applied.
+    pub fn changes(&self) -> &[RecordChange] {
+        &self.changes
+    }
+
+    /// Whether the records of the domain already match.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Apply the changes in order, stopping at the first error. Changes before
+    /// the error will already have been made.
+    pub fn apply(&self, client: &DigitalOcean) -> Result<(), Error> {
+        for change in &self.changes {
+            let records = Domain::get(&self.domain).records();
+            match change {
+                RecordChange::Create(desired) => {
+                    let mut req = records
+                        .create_record(name(&self.domain, &desired.name), desired.data.clone())?;
+                    if let Some(ttl) = desired.ttl {
+                        req = req.ttl(ttl);
+                    }
+                    req.execute(client)?;
+                }
+                RecordChange::Update { current, desired } => {
+                    let mut req = records
+                        .update(*current.id())
+                        .record_data(desired.data.clone())?;
+                    if let Some(ttl) = desired.ttl {
+                        req = req.ttl(ttl);
+                    }
+                    req.execute(client)?;
+                }
+                RecordChange::Delete(current) => {
+                    records.delete(*current.id()).execute(client)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Display for RecordPlan {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for change in &self.changes {
+            writeln!(f, "{}", change)?;
+        }
+        Ok(())
+    }
+}
+
+impl DomainRecordRequest<List, Vec<DomainRecord>> {
+    /// List the current records of the domain and compute the changes needed
+    /// to match `desired`. Nothing is changed until the plan is applied.
+    pub fn plan(
+        self,
+        client: &DigitalOcean,
+        desired: Vec<DesiredRecord>,
+    ) -> Result<RecordPlan, Error> {
+        let domain = self.domain_name();
+        let current = self.execute(client)?;
+        Ok(RecordPlan::new(domain, current, desired))
+    }
+}
+
+fn describe(name: &str, data: &RecordData) -> String {
+    let mut description = format!("{} {} {}", name, data.kind(), data.data());
+    match data {
+        RecordData::MX { priority, .. } => {
+            description += &format!(" (priority {})", priority);
+        }
+        RecordData::SRV {
+            priority,
+            weight,
+            port,
+            ..
+        } => {
+            description += &format!(" (priority {}, weight {}, port {})", priority, weight, port);
+        }
+        RecordData::CAA { flags, tag, .. } => {
+            description += &format!(" (flags {}, tag {})", flags, tag);
+        }
+        _ => (),
+    }
+    description
+}
+
+// Records are matched on their type, name and data, normalized so that
+// relative and fully qualified names compare equal.
+fn key(domain: &str, record_name: &str, data: &RecordData) -> (String, String, String) {
+    let host = |host: &str| {
+        if host == "@" {
+            domain.to_ascii_lowercase()
+        } else {
+            host.trim_end_matches('.').to_ascii_lowercase()
+        }
+    };
+    let normalized = match data {
+        RecordData::CNAME(target) | RecordData::NS(target) => host(target),
+        RecordData::MX { host: target, .. } => host(target),
+        RecordData::SRV { target, .. } => host(target),
+        data => data.data(),
+    };
+    (
+        data.kind().to_string(),
+        name(domain, record_name),
+        normalized,
+    )
+}
+
+fn name(domain: &str, name: &str) -> String {
+    let name = name.to_ascii_lowercase();
+    let domain = domain.to_ascii_lowercase();
+    match name.strip_suffix('.') {
+        Some(fqdn) if fqdn == domain => String::from("@"),
+        Some(fqdn) => fqdn
+            .strip_suffix(&format!(".{}", domain))
+            .unwrap_or(fqdn)
+            .to_string(),
+        None => name,
+    }
+}
+
+// Whether the attributes other than type, name and data match.
+fn same_attributes(current: &RecordData, desired: &RecordData) -> bool {
+    match (current, desired) {
+        (RecordData::MX { priority: a, .. }, RecordData::MX { priority: b, .. }) => a == b,
+        (
+            RecordData::SRV {
+                priority: a_priority,
+                weight: a_weight,
+                port: a_port,
+                ..
+            },
+            RecordData::SRV {
+                priority: b_priority,
+                weight: b_weight,
+                port: b_port,
+                ..
+            },
+        ) => a_priority == b_priority && a_weight == b_weight && a_port == b_port,
+        (
+            RecordData::CAA {
+                flags: a_flags,
+                tag: a_tag,
+                ..
+            },
+            RecordData::CAA {
+                flags: b_flags,
+                tag: b_tag,
+                ..
+            },
+        ) => a_flags == b_flags && a_tag == b_tag,
+        _ => true,
+    }
+}
@@ -1,16 +1,22 @@
-use self::droplet_fields::{Kernel, Networks, NextBackupWindow};
+use self::droplet_fields::{BackupPolicy, DiskInfo, Kernel, Networks, NextBackupWindow};
 use super::snapshot::Snapshot;
 use super::{ApiLinks, ApiMeta};
 use super::{HasPagination, HasResponse, HasValue};
-use super::{Image, Region, Size};
+use super::{Ensured, Image, Region, Size};
+use crate::error::Error;
 use crate::method::{Create, Delete, Get, List};
 use crate::request::Request;
-use crate::request::{DropletRequest, SnapshotRequest};
-use crate::{ROOT_URL, STATIC_URL_ERROR};
+use crate::request::{DropletRequest, Executable, SnapshotRequest};
+use crate::{DigitalOcean, ROOT_URL, STATIC_URL_ERROR};
 use chrono::{DateTime, Utc};
 use getset::{Getters, Setters};
+use log::info;
 use serde::Serialize;
+use serde_json::{Map, Value};
 use std::fmt::Display;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, TcpStream};
+use std::thread;
+use std::time::{Duration, Instant};
 use url::Url;
 
 const DROPLETS_SEGMENT: &str = "droplets";
@@ -55,11 +61,15 @@ pub struct Droplet {
     /// An array of backup IDs of any backups that have been taken of the
     /// Droplet instance. Droplet backups are enabled at the time of the
     /// instance creation.
+    #[serde(default)]
     backup_ids: Vec<usize>,
     /// An array of snapshot IDs of any snapshots created from the Droplet
     /// instance.
+    #[serde(default)]
     snapshot_ids: Vec<usize>,
-    /// An array of features enabled on this Droplet.
+    /// An array of features enabled on this Droplet (ex: "backups", "ipv6",
+    /// "monitoring", "droplet_agent").
+    #[serde(default)]
     features: Vec<String>,
     /// The region that the Droplet instance is deployed in. When setting a
     /// region, the value should be the slug identifier for the region. When
@@ -92,43 +102,191 @@ pub struct Droplet {
     /// of the window during which the backup will start.
     next_backup_window: Option<NextBackupWindow>,
     /// An array of Tags the Droplet has been tagged with.
+    #[serde(default)]
     tags: Vec<String>,
     /// A flat array including the unique identifier for each Block Storage
     /// volume attached to the Droplet.
+    #[serde(default)]
     volume_ids: Vec<String>,
+    /// A string specifying the UUID of the VPC to which the Droplet is
+    /// assigned.
+    vpc_uuid: Option<String>,
+    /// An array of objects containing information about the disks available
+    /// to the Droplet.
+    #[serde(default)]
+    disk_info: Vec<DiskInfo>,
+    /// Any fields returned by the API which are not otherwise captured.
+    ///
+    /// *Note:* With the `strict` feature enabled these cause an error instead.
+    #[serde(flatten, deserialize_with = "super::deserialize_extra")]
+    extra: Map<String, Value>,
 }
 
 /// Fields which exists inside Droplets.
 pub mod droplet_fields {
     use chrono::{DateTime, Utc};
+    use serde::{Deserialize, Deserializer};
+    use std::fmt::{self, Display};
     use std::net::{Ipv4Addr, Ipv6Addr};
+    use std::str::FromStr;
+
     /// This exists in the `networks` field of a droplet.
     #[derive(Deserialize, Serialize, Debug, Clone)]
     pub struct Networks {
+        #[serde(default)]
         pub v4: Vec<NetworkV4>,
+        #[serde(default)]
         pub v6: Vec<NetworkV6>,
     }
 
+    impl Networks {
+        /// The first IPv4 network of a kind, which DigitalOcean treats as its
+        /// primary one.
+        pub fn primary_v4(&self, kind: &NetworkKind) -> Option<&NetworkV4> {
+            self.v4.iter().find(|network| network.kind == *kind)
+        }
+
+        /// The first IPv6 network of a kind, which DigitalOcean treats as its
+        /// primary one.
+        pub fn primary_v6(&self, kind: &NetworkKind) -> Option<&NetworkV6> {
+            self.v6.iter().find(|network| network.kind == *kind)
+        }
+
+        /// The public IPv4 address, once assigned.
+        pub fn public_ipv4(&self) -> Option<Ipv4Addr> {
+            self.primary_v4(&NetworkKind::Public).map(|network| network.ip_address)
+        }
+
+        /// The private (VPC) IPv4 address, once assigned.
+        pub fn private_ipv4(&self) -> Option<Ipv4Addr> {
+            self.primary_v4(&NetworkKind::Private).map(|network| network.ip_address)
+        }
+
+        /// The public IPv6 address, if IPv6 is enabled.
+        pub fn ipv6(&self) -> Option<Ipv6Addr> {
+            self.primary_v6(&NetworkKind::Public).map(|network| network.ip_address)
+        }
+
+        /// The gateway of the primary IPv4 network of a kind, if it reports
+        /// one.
+        pub fn gateway_v4(&self, kind: &NetworkKind) -> Option<Ipv4Addr> {
+            self.primary_v4(kind).and_then(|network| network.gateway)
+        }
+
+        /// The gateway of the primary IPv6 network of a kind, if it reports
+        /// one.
+        pub fn gateway_v6(&self, kind: &NetworkKind) -> Option<Ipv6Addr> {
+            self.primary_v6(kind).and_then(|network| network.gateway)
+        }
+    }
+
+    /// Whether a network is reachable from the internet, or only from within
+    /// its VPC.
+    #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+    #[serde(from = "String", into = "String")]
+    pub enum NetworkKind {
+        Public,
+        Private,
+        /// Any kind not known to this crate.
+        Other(String),
+    }
+
+    impl From<String> for NetworkKind {
+        fn from(kind: String) -> Self {
+            match kind.as_str() {
+                "public" => NetworkKind::Public,
+                "private" => NetworkKind::Private,
+                _ => NetworkKind::Other(kind),
+            }
+        }
+    }
+
+    impl From<NetworkKind> for String {
+        fn from(kind: NetworkKind) -> Self {
+            kind.to_string()
+        }
+    }
+
+    impl Display for NetworkKind {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            match self {
+                NetworkKind::Public => write!(f, "public"),
+                NetworkKind::Private => write!(f, "private"),
+                NetworkKind::Other(kind) => write!(f, "{}", kind),
+            }
+        }
+    }
+
     /// These exist in the `networks` field of a droplet.
     #[derive(Deserialize, Serialize, Debug, Clone)]
     pub struct NetworkV4 {
-        pub gateway: Ipv4Addr,
+        /// *Note:* Private networks may not report a gateway.
+        #[serde(default, deserialize_with = "lenient_address")]
+        pub gateway: Option<Ipv4Addr>,
         pub ip_address: Ipv4Addr,
         pub netmask: Ipv4Addr,
         /// *Note:* Since `type` is a keyword in Rust `kind` is used instead.
         #[serde(rename = "type")]
-        pub kind: String,
+        pub kind: NetworkKind,
+    }
+
+    impl NetworkV4 {
+        /// The number of leading bits of the netmask which are set, ex: `20`
+        /// for `255.255.240.0`.
+        pub fn prefix_len(&self) -> u32 {
+            u32::from(self.netmask).leading_ones()
+        }
+
+        /// The first address of the network, ex: `10.108.0.0` for
+        /// `10.108.0.2` and `255.255.240.0`.
+        pub fn network_address(&self) -> Ipv4Addr {
+            Ipv4Addr::from(u32::from(self.ip_address) & u32::from(self.netmask))
+        }
+
+        /// The network in CIDR notation, ex: `10.108.0.0/20`.
+        pub fn cidr(&self) -> String {
+            format!("{}/{}", self.network_address(), self.prefix_len())
+        }
     }
 
     /// These exist in the `networks` field of a droplet.
     #[derive(Deserialize, Serialize, Debug, Clone)]
     pub struct NetworkV6 {
-        pub gateway: Ipv6Addr,
+        /// *Note:* Private networks may not report a gateway.
+        #[serde(default, deserialize_with = "lenient_address")]
+        pub gateway: Option<Ipv6Addr>,
         pub ip_address: Ipv6Addr,
+        /// The prefix length, ex: `64`.
         pub netmask: usize,
         /// *Note:* Since `type` is a keyword in Rust `kind` is used instead.
         #[serde(rename = "type")]
-        pub kind: String,
+        pub kind: NetworkKind,
+    }
+
+    impl NetworkV6 {
+        /// The first address of the network, ex: `2604:a880:0:1010::` for
+        /// `2604:a880:0:1010::18a:a001` and a prefix length of 64.
+        pub fn network_address(&self) -> Ipv6Addr {
+            let mask = u128::MAX
+                .checked_shl(128u32.saturating_sub(self.netmask as u32))
+                .unwrap_or(0);
+            Ipv6Addr::from(u128::from(self.ip_address) & mask)
+        }
+
+        /// The network in CIDR notation, ex: `2604:a880:0:1010::/64`.
+        pub fn cidr(&self) -> String {
+            format!("{}/{}", self.network_address(), self.netmask)
+        }
+    }
+
+    // DigitalOcean reports missing gateways as `null`, `""` or `"nil"`.
+    fn lenient_address<'de, D, A>(deserializer: D) -> Result<Option<A>, D::Error>
+    where
+        D: Deserializer<'de>,
+        A: FromStr,
+    {
+        let val: Option<String> = Option::deserialize(deserializer)?;
+        Ok(val.and_then(|v| v.parse().ok()))
     }
 
     /// This exists in the `next_backup_window` field of a droplet.
@@ -145,6 +303,63 @@ pub mod droplet_fields {
         pub name: String,
         pub version: String,
     }
+
+    /// These exist in the `disk_info` field of a droplet.
+    #[derive(Deserialize, Serialize, Debug, Clone)]
+    pub struct DiskInfo {
+        /// The type of disk. This is either "local" or "scratch".
+        ///
+        /// *Note:* Since `type` is a keyword in Rust `kind` is used instead.
+        #[serde(rename = "type")]
+        pub kind: String,
+        pub size: DiskSize,
+    }
+
+    /// This exists in the `size` field of a droplet's `disk_info`.
+    #[derive(Deserialize, Serialize, Debug, Clone)]
+    pub struct DiskSize {
+        pub amount: usize,
+        /// The unit of `amount`, for example "gib".
+        pub unit: String,
+    }
+
+    /// Used to configure the backup schedule of a Droplet upon creation.
+    ///
+    /// `weekday` only applies to weekly backups. `hour` is the start of the
+    /// four hour backup window in UTC and must be one of 0, 4, 8, 12, 16 or 20.
+    #[derive(Deserialize, Serialize, Debug, Clone)]
+    pub struct BackupPolicy {
+        /// Either "daily" or "weekly".
+        pub plan: String,
+        /// The day of the week backups occur on (ex: "SUN", "MON", ...).
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub weekday: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub hour: Option<usize>,
+    }
+    impl BackupPolicy {
+        pub fn daily() -> Self {
+            BackupPolicy {
+                plan: "daily".to_string(),
+                weekday: None,
+                hour: None,
+            }
+        }
+        pub fn weekly<S>(weekday: S) -> Self
+        where
+            S: AsRef<str>,
+        {
+            BackupPolicy {
+                plan: "weekly".to_string(),
+                weekday: Some(weekday.as_ref().to_string()),
+                hour: None,
+            }
+        }
+        pub fn hour(mut self, hour: usize) -> Self {
+            self.hour = Some(hour);
+            self
+        }
+    }
 }
 
 impl Droplet {
@@ -231,6 +446,21 @@ impl Droplet {
         Request::new(url)
     }
 
+    /// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/droplets_list)
+    pub fn list_by_name<S>(name: S) -> DropletRequest<List, Vec<Droplet>>
+    where
+        S: AsRef<str> + Serialize,
+    {
+        let mut url = ROOT_URL.clone();
+        url.path_segments_mut()
+            .expect(STATIC_URL_ERROR)
+            .push(DROPLETS_SEGMENT);
+
+        url.query_pairs_mut().append_pair("name", name.as_ref());
+
+        Request::new(url)
+    }
+
     /// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#delete-a-droplet)
     pub fn delete(id: usize) -> DropletRequest<Delete, ()> {
         let mut url = ROOT_URL.clone();
@@ -267,6 +497,151 @@ impl Droplet {
 
         Request::new(url)
     }
+
+    /// Poll the Droplet until it is `active` and has the addresses (and
+    /// optionally open port) `readiness` asks for, returning it as it then is.
+    ///
+    /// Returns `Error::Timeout` if it is still not ready after the timeout.
+    pub fn wait_until_ready(
+        &self,
+        client: &DigitalOcean,
+        readiness: &Readiness,
+    ) -> Result<Droplet, Error> {
+        let start = Instant::now();
+        let mut droplet = self.clone();
+        loop {
+            if readiness.is_ready(&droplet, start) {
+                return Ok(droplet);
+            }
+            if start.elapsed() > readiness.timeout {
+                return Err(Error::Timeout(format!(
+                    "Droplet {} to become ready",
+                    self.id
+                )));
+            }
+            thread::sleep(readiness.poll_interval);
+            droplet = Droplet::get(self.id).execute(client)?;
+        }
+    }
+
+    /// The public IPv4 address, once assigned.
+    pub fn public_ipv4(&self) -> Option<Ipv4Addr> {
+        self.networks.public_ipv4()
+    }
+
+    /// The private (VPC) IPv4 address, once assigned.
+    pub fn private_ipv4(&self) -> Option<Ipv4Addr> {
+        self.networks.private_ipv4()
+    }
+
+    /// The public IPv6 address, if IPv6 is enabled.
+    pub fn ipv6(&self) -> Option<Ipv6Addr> {
+        self.networks.ipv6()
+    }
+}
+
+/// What [`Droplet::wait_until_ready`](struct.Droplet.html#method.wait_until_ready)
+/// waits for besides an `active` status.
+///
+/// By default this is a public IPv4 address, checked every 5 seconds for up
+/// to 5 minutes.
+#[derive(Debug, Clone)]
+pub struct Readiness {
+    public_ipv4: bool,
+    private_ipv4: bool,
+    ipv6: bool,
+    port: Option<u16>,
+    poll_interval: Duration,
+    timeout: Duration,
+}
+
+impl Default for Readiness {
+    fn default() -> Self {
+        Readiness {
+            public_ipv4: true,
+            private_ipv4: false,
+            ipv6: false,
+            port: None,
+            poll_interval: Duration::from_secs(5),
+            timeout: Duration::from_secs(5 * 60),
+        }
+    }
+}
+
+impl Readiness {
+    pub fn new() -> Self {
+        Readiness::default()
+    }
+
+    /// Whether to wait for a public IPv4 address. Defaults to `true`.
+    pub fn public_ipv4(mut self, wait: bool) -> Self {
+        self.public_ipv4 = wait;
+        self
+    }
+
+    /// Whether to wait for a private IPv4 address. Defaults to `false`.
+    pub fn private_ipv4(mut self, wait: bool) -> Self {
+        self.private_ipv4 = wait;
+        self
+    }
+
+    /// Whether to wait for a public IPv6 address. Defaults to `false`.
+    pub fn ipv6(mut self, wait: bool) -> Self {
+        self.ipv6 = wait;
+        self
+    }
+
+    /// Also wait for SSH (TCP port 22) to accept connections.
+    pub fn ssh(self) -> Self {
+        self.port(22)
+    }
+
+    /// Also wait for a TCP port to accept connections, on the public IPv4
+    /// address if there is one, otherwise the private IPv4 or IPv6 address.
+    pub fn port(mut self, port: u16) -> Self {
+        self.port = Some(port);
+        self
+    }
+
+    pub fn poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    fn is_ready(&self, droplet: &Droplet, start: Instant) -> bool {
+        if droplet.status != "active"
+            || (self.public_ipv4 && droplet.public_ipv4().is_none())
+            || (self.private_ipv4 && droplet.private_ipv4().is_none())
+            || (self.ipv6 && droplet.ipv6().is_none())
+        {
+            return false;
+        }
+        let port = match self.port {
+            Some(port) => port,
+            None => return true,
+        };
+        let address: Option<IpAddr> = droplet
+            .public_ipv4()
+            .or_else(|| droplet.private_ipv4())
+            .map(IpAddr::V4)
+            .or_else(|| droplet.ipv6().map(IpAddr::V6));
+        // Connecting may take no longer than the time left to wait.
+        let remaining = self
+            .timeout
+            .checked_sub(start.elapsed())
+            .unwrap_or_default()
+            .max(Duration::from_millis(1));
+        address.map_or(false, |address| {
+            let socket = SocketAddr::new(address, port);
+            info!("Connecting to {}", socket);
+            TcpStream::connect_timeout(&socket, self.poll_interval.min(remaining)).is_ok()
+        })
+    }
 }
 
 impl DropletRequest<Create, Droplet> {
@@ -342,6 +717,76 @@ impl DropletRequest<Create, Droplet> {
         self.body_mut()["tags"] = json!(val);
         self
     }
+    /// A string specifying the UUID of the VPC to which the Droplet will be
+    /// assigned. If excluded, the Droplet will be assigned to the account's
+    /// default VPC for the region.
+    ///
+    /// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/droplets_create)
+    pub fn vpc_uuid<S>(mut self, val: S) -> Self
+    where
+        S: AsRef<str> + Serialize,
+    {
+        self.body_mut()["vpc_uuid"] = json!(val);
+        self
+    }
+    /// A boolean indicating whether to install the DigitalOcean agent used
+    /// for providing access to the Droplet web console in the control panel.
+    ///
+    /// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/droplets_create)
+    pub fn with_droplet_agent(mut self, val: bool) -> Self {
+        self.body_mut()["with_droplet_agent"] = json!(val);
+        self
+    }
+    /// The backup schedule to use when automated backups are enabled with
+    /// `backups(true)`.
+    ///
+    /// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/droplets_create)
+    pub fn backup_policy(mut self, val: BackupPolicy) -> Self {
+        self.body_mut()["backup_policy"] = json!(val);
+        self
+    }
+
+    /// Return the Droplet with the same name in the same region if there is
+    /// one, otherwise create it.
+    ///
+    /// Returns `Error::Incompatible` if the existing Droplet has a different
+    /// size or image. An image given by slug is taken to match a Droplet
+    /// whose image no longer reports a slug, as happens once a distribution
+    /// image is retired, since the two cannot be compared.
+    pub fn ensure(self, client: &DigitalOcean) -> Result<Ensured<Droplet>, Error> {
+        let body = self.body();
+        let field = |name: &str| body[name].as_str().unwrap_or_default().to_string();
+        let (name, region) = (field("name"), field("region"));
+        let (size, image) = (field("size"), field("image"));
+
+        let existing = Droplet::list_by_name(&name)
+            .execute(client)?
+            .into_iter()
+            .find(|droplet| droplet.region.slug() == &region);
+        let droplet = match existing {
+            Some(droplet) => droplet,
+            None => return Ok(Ensured::Created(self.execute(client)?)),
+        };
+
+        let slug = droplet.image.slug().as_deref();
+        let same_image = match slug {
+            _ if droplet.image.id().to_string() == image => true,
+            Some(slug) => slug == image,
+            None => image.parse::<usize>().is_err(),
+        };
+        if droplet.size_slug != size || !same_image {
+            return Err(Error::Incompatible(format!(
+                "Droplet {} in {} has size {} and image {}, not {} and {}",
+                name,
+                region,
+                droplet.size_slug,
+                slug.unwrap_or(droplet.image.name()),
+                size,
+                image
+            )));
+        }
+        Ok(Ensured::Found(droplet))
+    }
 }
 
 impl DropletRequest<Create, Vec<Droplet>> {
@@ -417,6 +862,34 @@ impl DropletRequest<Create, Vec<Droplet>> {
         self.body_mut()["tags"] = json!(val);
         self
     }
+    /// A string specifying the UUID of the VPC to which the Droplet will be
+    /// assigned. If excluded, the Droplet will be assigned to the account's
+    /// default VPC for the region.
+    ///
+    /// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/droplets_create)
+    pub fn vpc_uuid<S>(mut self, val: S) -> Self
+    where
+        S: AsRef<str> + Serialize,
+    {
+        self.body_mut()["vpc_uuid"] = json!(val);
+        self
+    }
+    /// A boolean indicating whether to install the DigitalOcean agent used
+    /// for providing access to the Droplet web console in the control panel.
+    ///
+    /// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/droplets_create)
+    pub fn with_droplet_agent(mut self, val: bool) -> Self {
+        self.body_mut()["with_droplet_agent"] = json!(val);
+        self
+    }
+    /// The backup schedule to use when automated backups are enabled with
+    /// `backups(true)`.
+    ///
+    /// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/droplets_create)
+    pub fn backup_policy(mut self, val: BackupPolicy) -> Self {
+        self.body_mut()["backup_policy"] = json!(val);
+        self
+    }
 }
 
 impl DropletRequest<Get, Droplet> {
@@ -7,6 +7,7 @@ use crate::request::Request;
 use crate::{ROOT_URL, STATIC_URL_ERROR};
 use getset::{Getters, Setters};
 use serde::Serialize;
+use serde_json::{Map, Value};
 use std::fmt::Display;
 use std::net::IpAddr;
 use url::Url;
@@ -20,6 +21,10 @@ const FLOATING_IP_SEGMENT: &str = "floating_ips";
 ///
 /// Floating IPs are bound to a specific region.
 ///
+/// **Note:** DigitalOcean has renamed Floating IPs to Reserved IPs. Prefer
+/// [`ReservedIp`](struct.ReservedIp.html) and
+/// [`ReservedIpv6`](struct.ReservedIpv6.html) for new code.
+///
 /// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#floating-ips)
 #[derive(Deserialize, Serialize, Debug, Clone, Getters, Setters)]
 #[get = "pub"]
@@ -34,6 +39,11 @@ pub struct FloatingIp {
     /// a Floating IP, if it is assigned to a Droplet, the entire Droplet
     /// object will be returned. If it is not assigned, the value will be null.
     droplet: Option<Droplet>,
+    /// Any fields returned by the API which are not otherwise captured.
+    ///
+    /// *Note:* With the `strict` feature enabled these cause an error instead.
+    #[serde(flatten, deserialize_with = "super::deserialize_extra")]
+    extra: Map<String, Value>,
 }
 
 impl FloatingIp {
@@ -7,6 +7,7 @@ use crate::{ROOT_URL, STATIC_URL_ERROR};
 use chrono::{DateTime, Utc};
 use getset::{Getters, Setters};
 use serde::Serialize;
+use serde_json::{Map, Value};
 use std::fmt::Display;
 use url::Url;
 
@@ -56,6 +57,23 @@ pub struct Image {
     /// A time value given in ISO8601 combined date and time format that
     /// represents when the Image was created.
     created_at: DateTime<Utc>,
+    /// A status string indicating the state of a custom image. This may be
+    /// "NEW", "available", "pending", "deleted" or "retired".
+    status: Option<String>,
+    /// An array containing the names of the tags the image has been tagged
+    /// with.
+    #[serde(default)]
+    tags: Vec<String>,
+    /// An optional free-form text field to describe an image.
+    description: Option<String>,
+    /// A string containing information about errors that may occur when
+    /// importing a custom image.
+    error_message: Option<String>,
+    /// Any fields returned by the API which are not otherwise captured.
+    ///
+    /// *Note:* With the `strict` feature enabled these cause an error instead.
+    #[serde(flatten, deserialize_with = "super::deserialize_extra")]
+    extra: Map<String, Value>,
 }
 
 impl Image {
@@ -1,4 +1,6 @@
-use self::load_balancer_fields::{ForwardingRule, HealthCheck, StickySessions};
+use self::load_balancer_fields::{
+    Algorithm, Firewall, ForwardingRule, HealthCheck, HealthCheckProtocol, StickySessions,
+};
 use super::Region;
 use super::{ApiLinks, ApiMeta};
 use super::{HasPagination, HasResponse, HasValue};
@@ -9,6 +11,7 @@ use crate::{ROOT_URL, STATIC_URL_ERROR};
 use chrono::{DateTime, Utc};
 use getset::{Getters, Setters};
 use serde::Serialize;
+use serde_json::{Map, Value};
 use std::fmt::Display;
 use std::net::IpAddr;
 use url::Url;
@@ -32,9 +35,8 @@ pub struct LoadBalancer {
     /// Balancer.
     ip: IpAddr,
     /// The load balancing algorithm used to determine which backend Droplet
-    /// will be selected by a client. It must be either "round_robin" or
-    /// "least_connections".
-    algorithm: String,
+    /// will be selected by a client.
+    algorithm: Algorithm,
     /// A status string indicating the current state of the Load Balancer.
     /// This can be "new", "active", or "errored".
     status: String,
@@ -58,53 +60,220 @@ pub struct LoadBalancer {
     /// A boolean value indicating whether HTTP requests to the Load Balancer
     /// on port 80 will be redirected to HTTPS on port 443.
     redirect_http_to_https: bool,
+    /// The size of the Load Balancer as a slug (e.g. "lb-small"). Only
+    /// present on Load Balancers created with a fixed size.
+    #[serde(default)]
+    size: Option<String>,
+    /// How many nodes the Load Balancer contains. Each additional node
+    /// increases its maximum number of connections.
+    #[serde(default)]
+    size_unit: Option<usize>,
+    /// A string specifying the UUID of the VPC to which the Load Balancer is
+    /// assigned.
+    #[serde(default)]
+    vpc_uuid: Option<String>,
+    /// A boolean value indicating whether PROXY Protocol is in use.
+    #[serde(default)]
+    enable_proxy_protocol: bool,
+    /// A boolean value indicating whether HTTP keepalive connections are
+    /// maintained to target Droplets.
+    #[serde(default)]
+    enable_backend_keepalive: bool,
+    /// An integer value which configures the idle timeout for HTTP requests
+    /// to the target Droplets.
+    #[serde(default)]
+    http_idle_timeout_seconds: Option<usize>,
+    /// A boolean value indicating whether to disable automatic DNS record
+    /// creation for Let's Encrypt certificates that are added to the Load
+    /// Balancer.
+    #[serde(default)]
+    disable_lets_encrypt_dns_records: bool,
+    /// An object specifying allow and deny rules to control traffic to the
+    /// Load Balancer.
+    #[serde(default)]
+    firewall: Option<Firewall>,
+    /// Any fields returned by the API which are not otherwise captured.
+    ///
+    /// *Note:* With the `strict` feature enabled these cause an error instead.
+    #[serde(flatten, deserialize_with = "super::deserialize_extra")]
+    extra: Map<String, Value>,
 }
 
 /// Fields which exists inside Droplets.
 pub mod load_balancer_fields {
+    use std::fmt;
+
+    /// The protocol used for traffic to or from a Load Balancer.
+    #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+    #[serde(from = "String", into = "String")]
+    pub enum Protocol {
+        Http,
+        Https,
+        /// Only valid as an entry protocol, or as a target protocol when the
+        /// entry protocol is also `Http2`.
+        Http2,
+        /// Only valid as an entry protocol.
+        Http3,
+        Tcp,
+        Udp,
+        /// Any protocol not known to this crate.
+        Other(String),
+    }
+
+    impl From<String> for Protocol {
+        fn from(protocol: String) -> Self {
+            match protocol.as_str() {
+                "http" => Protocol::Http,
+                "https" => Protocol::Https,
+                "http2" => Protocol::Http2,
+                "http3" => Protocol::Http3,
+                "tcp" => Protocol::Tcp,
+                "udp" => Protocol::Udp,
+                _ => Protocol::Other(protocol),
+            }
+        }
+    }
+
+    impl From<Protocol> for String {
+        fn from(protocol: Protocol) -> Self {
+            protocol.to_string()
+        }
+    }
+
+    impl fmt::Display for Protocol {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            let val = match self {
+                Protocol::Http => "http",
+                Protocol::Https => "https",
+                Protocol::Http2 => "http2",
+                Protocol::Http3 => "http3",
+                Protocol::Tcp => "tcp",
+                Protocol::Udp => "udp",
+                Protocol::Other(protocol) => protocol,
+            };
+            write!(f, "{}", val)
+        }
+    }
+
+    /// The load balancing algorithm used to determine which backend Droplet
+    /// will be selected by a client.
+    #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+    #[serde(from = "String", into = "String")]
+    pub enum Algorithm {
+        RoundRobin,
+        LeastConnections,
+        /// Any algorithm not known to this crate.
+        Other(String),
+    }
+
+    impl From<String> for Algorithm {
+        fn from(algorithm: String) -> Self {
+            match algorithm.as_str() {
+                "round_robin" => Algorithm::RoundRobin,
+                "least_connections" => Algorithm::LeastConnections,
+                _ => Algorithm::Other(algorithm),
+            }
+        }
+    }
+
+    impl From<Algorithm> for String {
+        fn from(algorithm: Algorithm) -> Self {
+            algorithm.to_string()
+        }
+    }
+
+    impl fmt::Display for Algorithm {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            let val = match self {
+                Algorithm::RoundRobin => "round_robin",
+                Algorithm::LeastConnections => "least_connections",
+                Algorithm::Other(algorithm) => algorithm,
+            };
+            write!(f, "{}", val)
+        }
+    }
+
+    /// The protocol used for health checks sent to the backend Droplets.
+    #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+    #[serde(from = "String", into = "String")]
+    pub enum HealthCheckProtocol {
+        Http,
+        Https,
+        Tcp,
+        /// Any protocol not known to this crate.
+        Other(String),
+    }
+
+    impl From<String> for HealthCheckProtocol {
+        fn from(protocol: String) -> Self {
+            match protocol.as_str() {
+                "http" => HealthCheckProtocol::Http,
+                "https" => HealthCheckProtocol::Https,
+                "tcp" => HealthCheckProtocol::Tcp,
+                _ => HealthCheckProtocol::Other(protocol),
+            }
+        }
+    }
+
+    impl From<HealthCheckProtocol> for String {
+        fn from(protocol: HealthCheckProtocol) -> Self {
+            protocol.to_string()
+        }
+    }
+
+    impl fmt::Display for HealthCheckProtocol {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            let val = match self {
+                HealthCheckProtocol::Http => "http",
+                HealthCheckProtocol::Https => "https",
+                HealthCheckProtocol::Tcp => "tcp",
+                HealthCheckProtocol::Other(protocol) => protocol,
+            };
+            write!(f, "{}", val)
+        }
+    }
+
     /// This exists in the `forwarding_rules` field of a droplet.
     ///
     /// Forwarding rules determine how traffic will be routed from the Load
     /// Balancer to the Droplets assigned to it. They can be used to configure
-    /// the type of traffic (HTTP, HTTPS, or TCP) and to map ports on the Load
-    /// Balancer to ports on the Droplets. For SSL encrypted traffic, you may
-    /// also configure whether to use SSL termination at the Load Balancer (by
-    /// specifying an SSL certificate) or to pass the encrypted traffic
-    /// through to the Droplet. Currently, each Load Balancer may have up to 15
-    /// forwarding rules.
+    /// the type of traffic (HTTP, HTTPS, HTTP/2, HTTP/3, TCP, or UDP) and to
+    /// map ports on the Load Balancer to ports on the Droplets. For SSL
+    /// encrypted traffic, you may also configure whether to use SSL
+    /// termination at the Load Balancer (by specifying an SSL certificate) or
+    /// to pass the encrypted traffic through to the Droplet. Currently, each
+    /// Load Balancer may have up to 15 forwarding rules.
     #[derive(Deserialize, Serialize, Debug, Clone)]
     pub struct ForwardingRule {
-        /// The protocol used for traffic to the Load Balancer. The possible
-        /// values are: "http", "https", or "tcp".
-        pub entry_protocol: String,
+        /// The protocol used for traffic to the Load Balancer.
+        pub entry_protocol: Protocol,
         /// The port on which the Load Balancer instance will listen.
         pub entry_port: usize,
         /// The protocol used for traffic from the Load Balancer to the backend
-        /// Droplets. The possible values are: "http", "https", or "tcp".
-        pub target_protocol: String,
+        /// Droplets.
+        pub target_protocol: Protocol,
         /// An integer representing the port on the backend Droplets to which
         /// the Load Balancer will send traffic.
         pub target_port: usize,
         /// The ID of the TLS certificate used for SSL termination if enabled.
+        #[serde(default)]
         pub certificate_id: Option<String>,
         /// A boolean value indicating whether SSL encrypted traffic will be
         /// passed through to the backend Droplets.
+        #[serde(default)]
         pub tls_passthrough: bool,
     }
     impl ForwardingRule {
-        pub fn new<S>(
-            entry_protocol: S,
+        pub fn new(
+            entry_protocol: Protocol,
             entry_port: usize,
-            target_protocol: S,
+            target_protocol: Protocol,
             target_port: usize,
-        ) -> Self
-        where
-            S: AsRef<str>,
-        {
+        ) -> Self {
             ForwardingRule {
-                entry_protocol: entry_protocol.as_ref().to_string(),
+                entry_protocol,
                 entry_port,
-                target_protocol: target_protocol.as_ref().to_string(),
+                target_protocol,
                 target_port,
                 certificate_id: None,
                 tls_passthrough: false,
@@ -122,29 +291,27 @@ pub mod load_balancer_fields {
             self
         }
     }
-    impl<S> From<(S, usize, S, usize)> for ForwardingRule
-    where
-        S: AsRef<str>,
-    {
-        fn from(val: (S, usize, S, usize)) -> Self {
+    impl From<(Protocol, usize, Protocol, usize)> for ForwardingRule {
+        fn from(val: (Protocol, usize, Protocol, usize)) -> Self {
             ForwardingRule::new(val.0, val.1, val.2, val.3)
         }
     }
-    impl<S> From<(S, usize, S, usize, Option<S>)> for ForwardingRule
+    impl<S> From<(Protocol, usize, Protocol, usize, Option<S>)> for ForwardingRule
     where
-        S: AsRef<str>,
+        S: Into<String>,
     {
-        fn from(val: (S, usize, S, usize, Option<S>)) -> Self {
-            ForwardingRule::new(val.0, val.1, val.2, val.3).certificate_id(val.4)
+        fn from(val: (Protocol, usize, Protocol, usize, Option<S>)) -> Self {
+            ForwardingRule::new(val.0, val.1, val.2, val.3)
+                .certificate_id(val.4.map(Into::<String>::into))
         }
     }
-    impl<S> From<(S, usize, S, usize, Option<S>, bool)> for ForwardingRule
+    impl<S> From<(Protocol, usize, Protocol, usize, Option<S>, bool)> for ForwardingRule
     where
-        S: AsRef<str>,
+        S: Into<String>,
     {
-        fn from(val: (S, usize, S, usize, Option<S>, bool)) -> Self {
+        fn from(val: (Protocol, usize, Protocol, usize, Option<S>, bool)) -> Self {
             ForwardingRule::new(val.0, val.1, val.2, val.3)
-                .certificate_id(val.4)
+                .certificate_id(val.4.map(Into::<String>::into))
                 .tls_passthrough(val.5)
         }
     }
@@ -159,8 +326,7 @@ pub mod load_balancer_fields {
     #[derive(Deserialize, Serialize, Debug, Clone)]
     pub struct HealthCheck {
         /// The protocol used for health checks sent to the backend Droplets.
-        /// The possible values are "http" or "tcp".
-        pub protocol: String,
+        pub protocol: HealthCheckProtocol,
         /// An integer representing the port on the backend Droplets on which
         /// the health check will attempt a connection.
         pub port: usize,
@@ -203,6 +369,19 @@ pub mod load_balancer_fields {
         /// the sticky sessions type.
         pub cookie_ttl_seconds: Option<String>,
     }
+
+    /// This exists in the `firewall` field of a droplet.
+    ///
+    /// Rules are strings of the form "ip:1.2.3.4" or "cidr:1.2.0.0/16".
+    #[derive(Deserialize, Serialize, Debug, Clone, Default)]
+    pub struct Firewall {
+        /// Traffic from these sources will be allowed.
+        #[serde(default)]
+        pub allow: Vec<String>,
+        /// Traffic from these sources will be denied.
+        #[serde(default)]
+        pub deny: Vec<String>,
+    }
 }
 
 impl LoadBalancer {
@@ -284,14 +463,10 @@ impl LoadBalancer {
 
 impl LoadBalancerRequest<Create, LoadBalancer> {
     /// The load balancing algorithm used to determine which backend Droplet
-    /// will be selected by a client. It must be either "round_robin" or
-    /// "least_connections". The default value is "round_robin".
+    /// will be selected by a client. The default value is `RoundRobin`.
     ///
     /// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#create-a-new-load-balancer)
-    pub fn algorithm<S>(mut self, val: S) -> LoadBalancerRequest<Create, LoadBalancer>
-    where
-        S: Display + Serialize,
-    {
+    pub fn algorithm(mut self, val: Algorithm) -> LoadBalancerRequest<Create, LoadBalancer> {
         self.body_mut()["algorithm"] = json!(val);
         self
     }
@@ -324,19 +499,16 @@ impl LoadBalancerRequest<Create, LoadBalancer> {
     ///
     /// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#create-a-new-load-balancer)
     #[allow(clippy::too_many_arguments)]
-    pub fn health_check<S>(
+    pub fn health_check(
         mut self,
-        protocol: S,
+        protocol: HealthCheckProtocol,
         port: usize,
-        path: Option<S>,
+        path: Option<&str>,
         check_interval_seconds: Option<usize>,
         response_timeout_seconds: Option<usize>,
         unhealthy_threshold: Option<usize>,
         healthy_threshold: Option<usize>,
-    ) -> LoadBalancerRequest<Create, LoadBalancer>
-    where
-        S: AsRef<str> + Display + Serialize,
-    {
+    ) -> LoadBalancerRequest<Create, LoadBalancer> {
         self.body_mut()["health_check"] = json!({
             "protocol": protocol,
             "port": port,
@@ -418,6 +590,100 @@ impl LoadBalancerRequest<Create, LoadBalancer> {
         self.body_mut()["tag"] = json!(tag);
         self
     }
+    /// How many nodes the Load Balancer should contain. Each additional node
+    /// increases its maximum number of connections.
+    ///
+    /// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#create-a-new-load-balancer)
+    pub fn size_unit(mut self, val: usize) -> LoadBalancerRequest<Create, LoadBalancer> {
+        self.body_mut()["size_unit"] = json!(val);
+        self
+    }
+    /// The size of the Load Balancer as a slug (e.g. "lb-small").
+    ///
+    /// **Note:** Not intended to be used alongside the `size_unit` function.
+    ///
+    /// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#create-a-new-load-balancer)
+    pub fn size<S>(mut self, val: S) -> LoadBalancerRequest<Create, LoadBalancer>
+    where
+        S: AsRef<str> + Display + Serialize,
+    {
+        self.body_mut()["size"] = json!(val);
+        self
+    }
+    /// The UUID of the VPC to which the Load Balancer will be assigned.
+    ///
+    /// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#create-a-new-load-balancer)
+    pub fn vpc_uuid<S>(mut self, val: S) -> LoadBalancerRequest<Create, LoadBalancer>
+    where
+        S: AsRef<str> + Display + Serialize,
+    {
+        self.body_mut()["vpc_uuid"] = json!(val);
+        self
+    }
+    /// A boolean value indicating whether PROXY Protocol should be used to
+    /// pass information from connecting client requests to the backend
+    /// service. Default value is false.
+    ///
+    /// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#create-a-new-load-balancer)
+    pub fn enable_proxy_protocol(
+        mut self,
+        setting: bool,
+    ) -> LoadBalancerRequest<Create, LoadBalancer> {
+        self.body_mut()["enable_proxy_protocol"] = json!(setting);
+        self
+    }
+    /// A boolean value indicating whether HTTP keepalive connections are
+    /// maintained to target Droplets. Default value is false.
+    ///
+    /// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#create-a-new-load-balancer)
+    pub fn enable_backend_keepalive(
+        mut self,
+        setting: bool,
+    ) -> LoadBalancerRequest<Create, LoadBalancer> {
+        self.body_mut()["enable_backend_keepalive"] = json!(setting);
+        self
+    }
+    /// The idle timeout for HTTP requests to the target Droplets, between 30
+    /// and 600 seconds. Default value is 60.
+    ///
+    /// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#create-a-new-load-balancer)
+    pub fn http_idle_timeout_seconds(
+        mut self,
+        val: usize,
+    ) -> LoadBalancerRequest<Create, LoadBalancer> {
+        self.body_mut()["http_idle_timeout_seconds"] = json!(val);
+        self
+    }
+    /// A boolean value indicating whether to disable automatic DNS record
+    /// creation for Let's Encrypt certificates that are added to the Load
+    /// Balancer. Default value is false.
+    ///
+    /// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#create-a-new-load-balancer)
+    pub fn disable_lets_encrypt_dns_records(
+        mut self,
+        setting: bool,
+    ) -> LoadBalancerRequest<Create, LoadBalancer> {
+        self.body_mut()["disable_lets_encrypt_dns_records"] = json!(setting);
+        self
+    }
+    /// Allow and deny rules controlling traffic to the Load Balancer. Rules
+    /// are strings of the form "ip:1.2.3.4" or "cidr:1.2.0.0/16".
+    ///
+    /// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#create-a-new-load-balancer)
+    pub fn firewall<S>(
+        mut self,
+        allow: Vec<S>,
+        deny: Vec<S>,
+    ) -> LoadBalancerRequest<Create, LoadBalancer>
+    where
+        S: AsRef<str> + Display + Serialize,
+    {
+        self.body_mut()["firewall"] = json!({
+            "allow": allow,
+            "deny": deny,
+        });
+        self
+    }
 }
 
 impl LoadBalancerRequest<Update, LoadBalancer> {
@@ -442,14 +708,10 @@ impl LoadBalancerRequest<Update, LoadBalancer> {
         self
     }
     /// The load balancing algorithm used to determine which backend Droplet
-    /// will be selected by a client. It must be either "round_robin" or
-    /// "least_connections". The default value is "round_robin".
+    /// will be selected by a client. The default value is `RoundRobin`.
     ///
     /// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#update-a-load-balancer)
-    pub fn algorithm<S>(mut self, val: S) -> LoadBalancerRequest<Update, LoadBalancer>
-    where
-        S: Display + Serialize,
-    {
+    pub fn algorithm(mut self, val: Algorithm) -> LoadBalancerRequest<Update, LoadBalancer> {
         self.body_mut()["algorithm"] = json!(val);
         self
     }
@@ -482,19 +744,16 @@ impl LoadBalancerRequest<Update, LoadBalancer> {
     ///
     /// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#update-a-load-balancer)
     #[allow(clippy::too_many_arguments)]
-    pub fn health_check<S>(
+    pub fn health_check(
         mut self,
-        protocol: S,
+        protocol: HealthCheckProtocol,
         port: usize,
-        path: Option<S>,
+        path: Option<&str>,
         check_interval_seconds: Option<usize>,
         response_timeout_seconds: Option<usize>,
         unhealthy_threshold: Option<usize>,
         healthy_threshold: Option<usize>,
-    ) -> LoadBalancerRequest<Update, LoadBalancer>
-    where
-        S: AsRef<str> + Display + Serialize,
-    {
+    ) -> LoadBalancerRequest<Update, LoadBalancer> {
         self.body_mut()["health_check"] = json!({
             "protocol": protocol,
             "port": port,
@@ -576,6 +835,100 @@ impl LoadBalancerRequest<Update, LoadBalancer> {
         self.body_mut()["tag"] = json!(tag);
         self
     }
+    /// How many nodes the Load Balancer should contain. Each additional node
+    /// increases its maximum number of connections.
+    ///
+    /// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#update-a-load-balancer)
+    pub fn size_unit(mut self, val: usize) -> LoadBalancerRequest<Update, LoadBalancer> {
+        self.body_mut()["size_unit"] = json!(val);
+        self
+    }
+    /// The size of the Load Balancer as a slug (e.g. "lb-small").
+    ///
+    /// **Note:** Not intended to be used alongside the `size_unit` function.
+    ///
+    /// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#update-a-load-balancer)
+    pub fn size<S>(mut self, val: S) -> LoadBalancerRequest<Update, LoadBalancer>
+    where
+        S: AsRef<str> + Display + Serialize,
+    {
+        self.body_mut()["size"] = json!(val);
+        self
+    }
+    /// The UUID of the VPC to which the Load Balancer will be assigned.
+    ///
+    /// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#update-a-load-balancer)
+    pub fn vpc_uuid<S>(mut self, val: S) -> LoadBalancerRequest<Update, LoadBalancer>
+    where
+        S: AsRef<str> + Display + Serialize,
+    {
+        self.body_mut()["vpc_uuid"] = json!(val);
+        self
+    }
+    /// A boolean value indicating whether PROXY Protocol should be used to
+    /// pass information from connecting client requests to the backend
+    /// service. Default value is false.
+    ///
+    /// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#update-a-load-balancer)
+    pub fn enable_proxy_protocol(
+        mut self,
+        setting: bool,
+    ) -> LoadBalancerRequest<Update, LoadBalancer> {
+        self.body_mut()["enable_proxy_protocol"] = json!(setting);
+        self
+    }
+    /// A boolean value indicating whether HTTP keepalive connections are
+    /// maintained to target Droplets. Default value is false.
+    ///
+    /// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#update-a-load-balancer)
+    pub fn enable_backend_keepalive(
+        mut self,
+        setting: bool,
+    ) -> LoadBalancerRequest<Update, LoadBalancer> {
+        self.body_mut()["enable_backend_keepalive"] = json!(setting);
+        self
+    }
+    /// The idle timeout for HTTP requests to the target Droplets, between 30
+    /// and 600 seconds. Default value is 60.
+    ///
+    /// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#update-a-load-balancer)
+    pub fn http_idle_timeout_seconds(
+        mut self,
+        val: usize,
+    ) -> LoadBalancerRequest<Update, LoadBalancer> {
+        self.body_mut()["http_idle_timeout_seconds"] = json!(val);
+        self
+    }
+    /// A boolean value indicating whether to disable automatic DNS record
+    /// creation for Let's Encrypt certificates that are added to the Load
+    /// Balancer. Default value is false.
+    ///
+    /// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#update-a-load-balancer)
+    pub fn disable_lets_encrypt_dns_records(
+        mut self,
+        setting: bool,
+    ) -> LoadBalancerRequest<Update, LoadBalancer> {
+        self.body_mut()["disable_lets_encrypt_dns_records"] = json!(setting);
+        self
+    }
+    /// Allow and deny rules controlling traffic to the Load Balancer. Rules
+    /// are strings of the form "ip:1.2.3.4" or "cidr:1.2.0.0/16".
+    ///
+    /// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#update-a-load-balancer)
+    pub fn firewall<S>(
+        mut self,
+        allow: Vec<S>,
+        deny: Vec<S>,
+    ) -> LoadBalancerRequest<Update, LoadBalancer>
+    where
+        S: AsRef<str> + Display + Serialize,
+    {
+        self.body_mut()["firewall"] = json!({
+            "allow": allow,
+            "deny": deny,
+        });
+        self
+    }
 }
 
 impl LoadBalancerRequest<Get, LoadBalancer> {
@@ -2,10 +2,12 @@
 
 mod account;
 mod action;
+mod autoscale_pool;
 mod certificate;
 mod custom_image;
 mod domain;
 mod domain_record;
+mod domain_record_sync;
 mod droplet;
 mod droplet_action;
 mod floating_ip;
@@ -13,29 +15,46 @@ mod floating_ip_action;
 mod image;
 mod image_action;
 mod load_balancer;
+mod one_click;
 mod region;
+mod reserved_ip;
+mod reserved_ip_action;
+mod reserved_ipv6;
+mod reserved_ipv6_action;
 mod size;
 mod snapshot;
 mod ssh_key;
 mod tag;
 mod volume;
 mod volume_action;
+mod zone_file;
 
-use serde::de::DeserializeOwned;
+use serde::de::{DeserializeOwned, Deserializer, Error as _};
+use serde::Deserialize;
+use serde_json::{Map, Value};
 use url::Url;
 use url_serde;
 
 pub use self::account::Account;
 pub use self::action::Action;
+pub use self::autoscale_pool::{
+    autoscale_pool_fields, AutoscalePool, AutoscalePoolHistoryEvent, AutoscalePoolMember,
+};
+#[cfg(feature = "certificate-validation")]
+pub use self::certificate::certificate_fields;
 pub use self::certificate::Certificate;
 pub use self::custom_image::CustomImage;
 pub use self::domain::Domain;
-pub use self::domain_record::DomainRecord;
-pub use self::droplet::{droplet_fields, Droplet};
+pub use self::domain_record::{domain_record_fields, DomainRecord};
+pub use self::domain_record_sync::{DesiredRecord, RecordChange, RecordPlan};
+pub use self::droplet::{droplet_fields, Droplet, Readiness};
 pub use self::floating_ip::FloatingIp;
 pub use self::image::Image;
 pub use self::load_balancer::{load_balancer_fields, LoadBalancer};
+pub use self::one_click::{OneClick, OneClickInstallation};
 pub use self::region::Region;
+pub use self::reserved_ip::ReservedIp;
+pub use self::reserved_ipv6::ReservedIpv6;
 pub use self::size::Size;
 pub use self::snapshot::Snapshot;
 pub use self::ssh_key::SshKey;
@@ -45,6 +64,37 @@ pub use self::volume::Volume;
 // Defined in https://developers.digitalocean.com/documentation/v2/#links
 pub const MAX_PER_PAGE: usize = 200;
 
+/// The outcome of an `ensure` call, which creates a resource only if it does
+/// not already exist.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Ensured<T> {
+    /// A compatible resource already existed.
+    Found(T),
+    /// No such resource existed, so it was created.
+    Created(T),
+}
+
+impl<T> Ensured<T> {
+    pub fn was_created(&self) -> bool {
+        match self {
+            Ensured::Found(_) => false,
+            Ensured::Created(_) => true,
+        }
+    }
+
+    pub fn value(&self) -> &T {
+        match self {
+            Ensured::Found(value) | Ensured::Created(value) => value,
+        }
+    }
+
+    pub fn into_inner(self) -> T {
+        match self {
+            Ensured::Found(value) | Ensured::Created(value) => value,
+        }
+    }
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone)]
 struct ApiLinks {
     pages: Option<ApiPages>,
@@ -52,13 +102,7 @@ struct ApiLinks {
 
 impl ApiLinks {
     fn next(&self) -> Option<Url> {
-        match self.pages {
-            Some(ref pages) => match pages.next {
-                Some(ref v) => Some(v.clone()),
-                None => None,
-            },
-            None => None,
-        }
+        self.pages.as_ref().and_then(|pages| pages.next.clone())
     }
 }
 
@@ -79,6 +123,21 @@ struct ApiMeta {
     total: usize,
 }
 
+// Captures the fields of a resource which are not otherwise known to this
+// crate. With the `strict` feature enabled, any such field is an error so
+// changes in the API schema are caught early.
+fn deserialize_extra<'de, D>(deserializer: D) -> Result<Map<String, Value>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let extra = Map::deserialize(deserializer)?;
+    if cfg!(feature = "strict") && !extra.is_empty() {
+        let fields = extra.keys().cloned().collect::<Vec<_>>().join(", ");
+        return Err(D::Error::custom(format!("unknown fields: {}", fields)));
+    }
+    Ok(extra)
+}
+
 pub trait HasPagination {
     fn next_page(&self) -> Option<Url>;
 }
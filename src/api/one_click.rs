@@ -0,0 +1,166 @@
+use super::{ApiLinks, ApiMeta};
+use super::{HasPagination, HasResponse, HasValue};
+use crate::method::{Create, List};
+use crate::request::OneClickRequest;
+use crate::request::Request;
+use crate::{ROOT_URL, STATIC_URL_ERROR};
+use getset::{Getters, Setters};
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::fmt::{self, Display};
+use url::Url;
+
+const ONE_CLICKS_SEGMENT: &str = "1-clicks";
+const KUBERNETES_SEGMENT: &str = "kubernetes";
+
+/// 1-Click applications are pre-built Droplet images or Kubernetes apps with
+/// software, features, and configuration details already set up for you.
+/// They can be found in the DigitalOcean Marketplace.
+///
+/// A `OneClick` implements `Display` as its slug, so it may be passed
+/// directly as the image of [`Droplet::create`](struct.Droplet.html#method.create).
+///
+/// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#1-click-applications)
+#[derive(Deserialize, Serialize, Debug, Clone, Getters, Setters)]
+#[get = "pub"]
+pub struct OneClick {
+    /// The slug identifier for the 1-Click application.
+    slug: String,
+    /// The type of the 1-Click application. This is either "droplet" or
+    /// "kubernetes".
+    ///
+    /// *Note:* Since `type` is a keyword in Rust `kind` is used instead.
+    #[serde(rename = "type")]
+    kind: String, // 'type' is reserved in Rust.
+    /// Any fields returned by the API which are not otherwise captured.
+    ///
+    /// *Note:* With the `strict` feature enabled these cause an error instead.
+    #[serde(flatten, deserialize_with = "super::deserialize_extra")]
+    extra: Map<String, Value>,
+}
+
+impl Display for OneClick {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.slug)
+    }
+}
+
+/// The message returned when installing 1-Click applications onto a
+/// Kubernetes cluster. The installation itself happens asynchronously.
+#[derive(Deserialize, Serialize, Debug, Clone, Getters, Setters)]
+#[get = "pub"]
+pub struct OneClickInstallation {
+    /// A message about the result of the request.
+    message: String,
+    /// Any fields returned by the API which are not otherwise captured.
+    ///
+    /// *Note:* With the `strict` feature enabled these cause an error instead.
+    #[serde(flatten, deserialize_with = "super::deserialize_extra")]
+    extra: Map<String, Value>,
+}
+
+impl OneClick {
+    /// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#list-1-click-applications)
+    pub fn list() -> OneClickRequest<List, Vec<OneClick>> {
+        let mut url = ROOT_URL.clone();
+        url.path_segments_mut()
+            .expect(STATIC_URL_ERROR)
+            .push(ONE_CLICKS_SEGMENT);
+
+        Request::new(url)
+    }
+
+    /// Install one or more Kubernetes 1-Click applications (by slug) onto an
+    /// existing Kubernetes cluster.
+    ///
+    /// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#install-kubernetes-1-click-applications)
+    pub fn install_kubernetes<U, A>(
+        cluster_uuid: U,
+        addon_slugs: Vec<A>,
+    ) -> OneClickRequest<Create, OneClickInstallation>
+    where
+        U: AsRef<str> + Serialize + Display,
+        A: AsRef<str> + Serialize + Display,
+    {
+        let mut url = ROOT_URL.clone();
+        url.path_segments_mut()
+            .expect(STATIC_URL_ERROR)
+            .push(ONE_CLICKS_SEGMENT)
+            .push(KUBERNETES_SEGMENT);
+
+        let mut req = Request::new(url);
+        req.set_body(json!({
+            "addon_slugs": addon_slugs,
+            "cluster_uuid": cluster_uuid,
+        }));
+        req
+    }
+}
+
+impl OneClickRequest<List, Vec<OneClick>> {
+    /// Restrict the results to a type of 1-Click application. This is either
+    /// "droplet" or "kubernetes".
+    ///
+    /// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#list-1-click-applications)
+    pub fn kind<S>(mut self, kind: S) -> Self
+    where
+        S: AsRef<str> + Serialize + Display,
+    {
+        self.url_mut()
+            .query_pairs_mut()
+            .append_pair("type", kind.as_ref());
+
+        self
+    }
+    /// Restrict the results to Droplet 1-Click applications.
+    ///
+    /// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#list-1-click-applications)
+    pub fn droplet(self) -> Self {
+        self.kind("droplet")
+    }
+    /// Restrict the results to Kubernetes 1-Click applications.
+    ///
+    /// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#list-1-click-applications)
+    pub fn kubernetes(self) -> Self {
+        self.kind("kubernetes")
+    }
+}
+
+/// Response type returned from Digital Ocean.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct OneClickListResponse {
+    #[serde(rename = "1_clicks")]
+    one_clicks: Vec<OneClick>,
+    // This endpoint does not currently paginate.
+    links: Option<ApiLinks>,
+    meta: Option<ApiMeta>,
+}
+
+impl HasResponse for Vec<OneClick> {
+    type Response = OneClickListResponse;
+}
+
+impl HasPagination for OneClickListResponse {
+    fn next_page(&self) -> Option<Url> {
+        self.links.as_ref().and_then(ApiLinks::next)
+    }
+}
+
+impl HasValue for OneClickListResponse {
+    type Value = Vec<OneClick>;
+    fn value(self) -> Vec<OneClick> {
+        self.one_clicks
+    }
+}
+
+// The installation message is returned at the top level of the response.
+impl HasResponse for OneClickInstallation {
+    type Response = OneClickInstallation;
+}
+
+impl HasValue for OneClickInstallation {
+    type Value = OneClickInstallation;
+    fn value(self) -> OneClickInstallation {
+        self
+    }
+}
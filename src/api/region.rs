@@ -5,6 +5,7 @@ use crate::request::RegionRequest;
 use crate::request::Request;
 use crate::{ROOT_URL, STATIC_URL_ERROR};
 use getset::{Getters, Setters};
+use serde_json::{Map, Value};
 use url::Url;
 
 const REGIONS_SEGMENT: &str = "regions";
@@ -35,6 +36,11 @@ pub struct Region {
     /// This attribute is set to an array which contains features available in
     /// this region
     features: Vec<String>,
+    /// Any fields returned by the API which are not otherwise captured.
+    ///
+    /// *Note:* With the `strict` feature enabled these cause an error instead.
+    #[serde(flatten, deserialize_with = "super::deserialize_extra")]
+    extra: Map<String, Value>,
 }
 
 impl Region {
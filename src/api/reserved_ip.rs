@@ -0,0 +1,177 @@
+use super::{ApiLinks, ApiMeta};
+use super::{Droplet, Region};
+use super::{HasPagination, HasResponse, HasValue};
+use crate::method::{Create, Delete, Get, List};
+use crate::request::Request;
+use crate::request::ReservedIpRequest;
+use crate::{ROOT_URL, STATIC_URL_ERROR};
+use getset::{Getters, Setters};
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::fmt::Display;
+use std::net::Ipv4Addr;
+use url::Url;
+
+const RESERVED_IP_SEGMENT: &str = "reserved_ips";
+
+/// Reserved IP objects represent a publicly-accessible static IPv4 address
+/// that can be mapped to one of your Droplets. They can be used to create
+/// highly available setups or other configurations requiring movable
+/// addresses.
+///
+/// Reserved IPs are bound to a specific region. They were previously known
+/// as [`FloatingIp`](struct.FloatingIp.html)s. For IPv6 addresses see
+/// [`ReservedIpv6`](struct.ReservedIpv6.html).
+///
+/// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#tag/Reserved-IPs)
+#[derive(Deserialize, Serialize, Debug, Clone, Getters, Setters)]
+#[get = "pub"]
+pub struct ReservedIp {
+    /// The public IP address of the Reserved IP. It also serves as its
+    /// identifier.
+    ip: Ipv4Addr,
+    /// The region that the Reserved IP is reserved to. When you query a
+    /// Reserved IP, the entire region object will be returned.
+    region: Region,
+    /// The Droplet that the Reserved IP has been assigned to. When you query
+    /// a Reserved IP, if it is assigned to a Droplet, the entire Droplet
+    /// object will be returned. If it is not assigned, the value will be null.
+    droplet: Option<Droplet>,
+    /// A boolean value indicating whether or not the Reserved IP has pending
+    /// actions preventing new ones from being submitted.
+    locked: bool,
+    /// The UUID of the project to which the Reserved IP belongs.
+    project_id: Option<String>,
+    /// Any fields returned by the API which are not otherwise captured.
+    ///
+    /// *Note:* With the `strict` feature enabled these cause an error instead.
+    #[serde(flatten, deserialize_with = "super::deserialize_extra")]
+    extra: Map<String, Value>,
+}
+
+impl ReservedIp {
+    /// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/reservedIPs_list)
+    pub fn list() -> ReservedIpRequest<List, Vec<ReservedIp>> {
+        let mut url = ROOT_URL.clone();
+        url.path_segments_mut()
+            .expect(STATIC_URL_ERROR)
+            .push(RESERVED_IP_SEGMENT);
+
+        Request::new(url)
+    }
+
+    /// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/reservedIPs_create)
+    pub fn for_droplet(id: usize) -> ReservedIpRequest<Create, ReservedIp> {
+        let mut url = ROOT_URL.clone();
+        url.path_segments_mut()
+            .expect(STATIC_URL_ERROR)
+            .push(RESERVED_IP_SEGMENT);
+
+        let mut req = Request::new(url);
+        req.set_body(json!({
+            "droplet_id": id,
+        }));
+        req
+    }
+
+    /// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/reservedIPs_create)
+    pub fn for_region<S>(id: S) -> ReservedIpRequest<Create, ReservedIp>
+    where
+        S: AsRef<str> + Display + Serialize,
+    {
+        let mut url = ROOT_URL.clone();
+        url.path_segments_mut()
+            .expect(STATIC_URL_ERROR)
+            .push(RESERVED_IP_SEGMENT);
+
+        let mut req = Request::new(url);
+        req.set_body(json!({
+            "region": id,
+        }));
+        req
+    }
+
+    /// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/reservedIPs_get)
+    pub fn get<I>(id: I) -> ReservedIpRequest<Get, ReservedIp>
+    where
+        I: Into<Ipv4Addr>,
+    {
+        let mut url = ROOT_URL.clone();
+        url.path_segments_mut()
+            .expect(STATIC_URL_ERROR)
+            .push(RESERVED_IP_SEGMENT)
+            .push(&id.into().to_string());
+
+        Request::new(url)
+    }
+
+    /// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/reservedIPs_delete)
+    pub fn delete<I>(id: I) -> ReservedIpRequest<Delete, ()>
+    where
+        I: Into<Ipv4Addr>,
+    {
+        let mut url = ROOT_URL.clone();
+        url.path_segments_mut()
+            .expect(STATIC_URL_ERROR)
+            .push(RESERVED_IP_SEGMENT)
+            .push(&id.into().to_string());
+
+        Request::new(url)
+    }
+}
+
+impl ReservedIpRequest<Create, ReservedIp> {
+    /// The UUID of the project to which the Reserved IP will belong. Only
+    /// applies when reserving to a region.
+    ///
+    /// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/reservedIPs_create)
+    pub fn project_id<S>(mut self, val: S) -> Self
+    where
+        S: AsRef<str> + Display + Serialize,
+    {
+        self.body_mut()["project_id"] = json!(val);
+        self
+    }
+}
+
+/// Response type returned from Digital Ocean.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct ReservedIpResponse {
+    reserved_ip: ReservedIp,
+}
+
+impl HasResponse for ReservedIp {
+    type Response = ReservedIpResponse;
+}
+
+impl HasValue for ReservedIpResponse {
+    type Value = ReservedIp;
+    fn value(self) -> ReservedIp {
+        self.reserved_ip
+    }
+}
+
+/// Response type returned from Digital Ocean.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct ReservedIpListResponse {
+    reserved_ips: Vec<ReservedIp>,
+    links: ApiLinks,
+    meta: ApiMeta,
+}
+
+impl HasResponse for Vec<ReservedIp> {
+    type Response = ReservedIpListResponse;
+}
+
+impl HasPagination for ReservedIpListResponse {
+    fn next_page(&self) -> Option<Url> {
+        self.links.next()
+    }
+}
+
+impl HasValue for ReservedIpListResponse {
+    type Value = Vec<ReservedIp>;
+    fn value(self) -> Vec<ReservedIp> {
+        self.reserved_ips
+    }
+}
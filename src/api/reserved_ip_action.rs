@@ -0,0 +1,59 @@
+use super::reserved_ip::ReservedIp;
+use super::Action;
+use crate::method::{Create, Get, List};
+use crate::request::{ReservedIpActionRequest, ReservedIpRequest};
+use crate::STATIC_URL_ERROR;
+
+const RESERVED_IP_ACTIONS_SEGMENT: &str = "actions";
+
+impl ReservedIpRequest<Get, ReservedIp> {
+    /// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/reservedIPsActions_list)
+    pub fn actions(mut self) -> ReservedIpActionRequest<List, Vec<Action>> {
+        self.url_mut()
+            .path_segments_mut()
+            .expect(STATIC_URL_ERROR)
+            .push(RESERVED_IP_ACTIONS_SEGMENT);
+
+        self.transmute()
+    }
+
+    /// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/reservedIPsActions_get)
+    pub fn action(mut self, id: usize) -> ReservedIpActionRequest<Get, Action> {
+        self.url_mut()
+            .path_segments_mut()
+            .expect(STATIC_URL_ERROR)
+            .push(RESERVED_IP_ACTIONS_SEGMENT)
+            .push(&id.to_string());
+
+        self.transmute()
+    }
+
+    /// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/reservedIPsActions_post)
+    pub fn unassign(mut self) -> ReservedIpActionRequest<Create, Action> {
+        self.url_mut()
+            .path_segments_mut()
+            .expect(STATIC_URL_ERROR)
+            .push(RESERVED_IP_ACTIONS_SEGMENT);
+
+        self.set_body(json!({
+            "type": "unassign",
+        }));
+
+        self.transmute()
+    }
+
+    /// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/reservedIPsActions_post)
+    pub fn assign(mut self, id: usize) -> ReservedIpActionRequest<Create, Action> {
+        self.url_mut()
+            .path_segments_mut()
+            .expect(STATIC_URL_ERROR)
+            .push(RESERVED_IP_ACTIONS_SEGMENT);
+
+        self.set_body(json!({
+            "type": "assign",
+            "droplet_id": id,
+        }));
+
+        self.transmute()
+    }
+}
@@ -0,0 +1,143 @@
+use super::Droplet;
+use super::{ApiLinks, ApiMeta};
+use super::{HasPagination, HasResponse, HasValue};
+use crate::method::{Create, Delete, Get, List};
+use crate::request::Request;
+use crate::request::ReservedIpv6Request;
+use crate::{ROOT_URL, STATIC_URL_ERROR};
+use chrono::{DateTime, Utc};
+use getset::{Getters, Setters};
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::fmt::Display;
+use std::net::Ipv6Addr;
+use url::Url;
+
+const RESERVED_IPV6_SEGMENT: &str = "reserved_ipv6";
+
+/// Reserved IPv6 objects represent a publicly-accessible static IPv6 address
+/// that can be mapped to one of your Droplets.
+///
+/// Reserved IPv6 addresses are bound to a specific region. For IPv4
+/// addresses see [`ReservedIp`](struct.ReservedIp.html).
+///
+/// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#tag/Reserved-IPv6)
+#[derive(Deserialize, Serialize, Debug, Clone, Getters, Setters)]
+#[get = "pub"]
+pub struct ReservedIpv6 {
+    /// The public IPv6 address of the Reserved IPv6. It also serves as its
+    /// identifier.
+    ip: Ipv6Addr,
+    /// The slug of the region that the Reserved IPv6 is reserved to.
+    region_slug: String,
+    /// A time value given in ISO8601 combined date and time format that
+    /// represents when the Reserved IPv6 was reserved.
+    reserved_at: Option<DateTime<Utc>>,
+    /// The Droplet that the Reserved IPv6 has been assigned to. If it is not
+    /// assigned, the value will be null.
+    droplet: Option<Droplet>,
+    /// Any fields returned by the API which are not otherwise captured.
+    ///
+    /// *Note:* With the `strict` feature enabled these cause an error instead.
+    #[serde(flatten, deserialize_with = "super::deserialize_extra")]
+    extra: Map<String, Value>,
+}
+
+impl ReservedIpv6 {
+    /// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/reservedIPv6_list)
+    pub fn list() -> ReservedIpv6Request<List, Vec<ReservedIpv6>> {
+        let mut url = ROOT_URL.clone();
+        url.path_segments_mut()
+            .expect(STATIC_URL_ERROR)
+            .push(RESERVED_IPV6_SEGMENT);
+
+        Request::new(url)
+    }
+
+    /// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/reservedIPv6_create)
+    pub fn for_region<S>(id: S) -> ReservedIpv6Request<Create, ReservedIpv6>
+    where
+        S: AsRef<str> + Display + Serialize,
+    {
+        let mut url = ROOT_URL.clone();
+        url.path_segments_mut()
+            .expect(STATIC_URL_ERROR)
+            .push(RESERVED_IPV6_SEGMENT);
+
+        let mut req = Request::new(url);
+        req.set_body(json!({
+            "region_slug": id,
+        }));
+        req
+    }
+
+    /// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/reservedIPv6_get)
+    pub fn get<I>(id: I) -> ReservedIpv6Request<Get, ReservedIpv6>
+    where
+        I: Into<Ipv6Addr>,
+    {
+        let mut url = ROOT_URL.clone();
+        url.path_segments_mut()
+            .expect(STATIC_URL_ERROR)
+            .push(RESERVED_IPV6_SEGMENT)
+            .push(&id.into().to_string());
+
+        Request::new(url)
+    }
+
+    /// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/reservedIPv6_delete)
+    pub fn delete<I>(id: I) -> ReservedIpv6Request<Delete, ()>
+    where
+        I: Into<Ipv6Addr>,
+    {
+        let mut url = ROOT_URL.clone();
+        url.path_segments_mut()
+            .expect(STATIC_URL_ERROR)
+            .push(RESERVED_IPV6_SEGMENT)
+            .push(&id.into().to_string());
+
+        Request::new(url)
+    }
+}
+
+/// Response type returned from Digital Ocean.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct ReservedIpv6Response {
+    reserved_ipv6: ReservedIpv6,
+}
+
+impl HasResponse for ReservedIpv6 {
+    type Response = ReservedIpv6Response;
+}
+
+impl HasValue for ReservedIpv6Response {
+    type Value = ReservedIpv6;
+    fn value(self) -> ReservedIpv6 {
+        self.reserved_ipv6
+    }
+}
+
+/// Response type returned from Digital Ocean.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct ReservedIpv6ListResponse {
+    reserved_ipv6s: Vec<ReservedIpv6>,
+    links: ApiLinks,
+    meta: ApiMeta,
+}
+
+impl HasResponse for Vec<ReservedIpv6> {
+    type Response = ReservedIpv6ListResponse;
+}
+
+impl HasPagination for ReservedIpv6ListResponse {
+    fn next_page(&self) -> Option<Url> {
+        self.links.next()
+    }
+}
+
+impl HasValue for ReservedIpv6ListResponse {
+    type Value = Vec<ReservedIpv6>;
+    fn value(self) -> Vec<ReservedIpv6> {
+        self.reserved_ipv6s
+    }
+}
@@ -0,0 +1,38 @@
+use super::reserved_ipv6::ReservedIpv6;
+use super::Action;
+use crate::method::{Create, Get};
+use crate::request::{ReservedIpv6ActionRequest, ReservedIpv6Request};
+use crate::STATIC_URL_ERROR;
+
+const RESERVED_IPV6_ACTIONS_SEGMENT: &str = "actions";
+
+impl ReservedIpv6Request<Get, ReservedIpv6> {
+    /// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/reservedIPv6Actions_post)
+    pub fn unassign(mut self) -> ReservedIpv6ActionRequest<Create, Action> {
+        self.url_mut()
+            .path_segments_mut()
+            .expect(STATIC_URL_ERROR)
+            .push(RESERVED_IPV6_ACTIONS_SEGMENT);
+
+        self.set_body(json!({
+            "type": "unassign",
+        }));
+
+        self.transmute()
+    }
+
+    /// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/reservedIPv6Actions_post)
+    pub fn assign(mut self, id: usize) -> ReservedIpv6ActionRequest<Create, Action> {
+        self.url_mut()
+            .path_segments_mut()
+            .expect(STATIC_URL_ERROR)
+            .push(RESERVED_IPV6_ACTIONS_SEGMENT);
+
+        self.set_body(json!({
+            "type": "assign",
+            "droplet_id": id,
+        }));
+
+        self.transmute()
+    }
+}
@@ -5,6 +5,7 @@ use crate::request::Request;
 use crate::request::SizeRequest;
 use crate::{ROOT_URL, STATIC_URL_ERROR};
 use getset::{Getters, Setters};
+use serde_json::{Map, Value};
 use url::Url;
 
 const SIZES_SEGMENT: &str = "sizes";
@@ -49,6 +50,14 @@ pub struct Size {
     /// An array containing the region slugs where this size is available for
     /// Droplet creates.
     regions: Vec<String>,
+    /// A string describing the class of Droplets created from this size, ex:
+    /// "Basic" or "General Purpose".
+    description: Option<String>,
+    /// Any fields returned by the API which are not otherwise captured.
+    ///
+    /// *Note:* With the `strict` feature enabled these cause an error instead.
+    #[serde(flatten, deserialize_with = "super::deserialize_extra")]
+    extra: Map<String, Value>,
 }
 
 impl Size {
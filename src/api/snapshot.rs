@@ -6,6 +6,7 @@ use crate::request::SnapshotRequest;
 use crate::{ROOT_URL, STATIC_URL_ERROR};
 use chrono::{DateTime, Utc};
 use getset::{Getters, Setters};
+use serde_json::{Map, Value};
 use url::Url;
 
 const SNAPSHOT_SEGMENT: &str = "snapshots";
@@ -39,6 +40,11 @@ pub struct Snapshot {
     min_disk_size: usize,
     /// The billable size of the snapshot in gigabytes.
     size_gigabytes: f64,
+    /// Any fields returned by the API which are not otherwise captured.
+    ///
+    /// *Note:* With the `strict` feature enabled these cause an error instead.
+    #[serde(flatten, deserialize_with = "super::deserialize_extra")]
+    extra: Map<String, Value>,
 }
 
 impl Snapshot {
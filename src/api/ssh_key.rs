@@ -1,11 +1,13 @@
 use super::{ApiLinks, ApiMeta};
-use super::{HasPagination, HasResponse, HasValue};
+use super::{Ensured, HasPagination, HasResponse, HasValue};
+use crate::error::Error;
 use crate::method::{Create, Delete, Get, List, Update};
 use crate::request::Request;
-use crate::request::SshKeyRequest;
-use crate::{ROOT_URL, STATIC_URL_ERROR};
+use crate::request::{Executable, SshKeyRequest};
+use crate::{DigitalOcean, ROOT_URL, STATIC_URL_ERROR};
 use getset::{Getters, Setters};
 use serde::Serialize;
+use serde_json::{Map, Value};
 use std::fmt::Display;
 use url::Url;
 
@@ -38,6 +40,11 @@ pub struct SshKey {
     /// This is the human-readable display name for the given SSH key. This
     /// is used to easily identify the SSH keys when they are displayed.
     name: String,
+    /// Any fields returned by the API which are not otherwise captured.
+    ///
+    /// *Note:* With the `strict` feature enabled these cause an error instead.
+    #[serde(flatten, deserialize_with = "super::deserialize_extra")]
+    extra: Map<String, Value>,
 }
 
 impl SshKey {
@@ -117,6 +124,39 @@ impl SshKey {
     }
 }
 
+impl SshKeyRequest<Create, SshKey> {
+    /// Return the SSH key with the same name if there is one, otherwise
+    /// create it.
+    ///
+    /// Returns `Error::Incompatible` if the existing key has a different
+    /// public key.
+    pub fn ensure(self, client: &DigitalOcean) -> Result<Ensured<SshKey>, Error> {
+        let name = self.body()["name"].as_str().unwrap_or_default().to_string();
+        let public_key = self.body()["public_key"]
+            .as_str()
+            .unwrap_or_default()
+            .trim()
+            .to_string();
+
+        let existing = SshKey::list()
+            .execute(client)?
+            .into_iter()
+            .find(|key| key.name == name);
+        let key = match existing {
+            Some(key) => key,
+            None => return Ok(Ensured::Created(self.execute(client)?)),
+        };
+
+        if key.public_key.trim() != public_key {
+            return Err(Error::Incompatible(format!(
+                "SSH key {} has a different public key ({})",
+                name, key.fingerprint
+            )));
+        }
+        Ok(Ensured::Found(key))
+    }
+}
+
 impl SshKeyRequest<Update, SshKey> {
     /// The name to give the new SSH key in your account.
     ///
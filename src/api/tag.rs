@@ -1,12 +1,13 @@
 use super::{ApiLinks, ApiMeta};
-use super::{HasPagination, HasResponse, HasValue};
+use super::{Ensured, HasPagination, HasResponse, HasValue};
+use crate::error::Error;
 use crate::method::{Create, Delete, Get, List};
 use crate::request::Request;
-use crate::request::TagRequest;
-use crate::{ROOT_URL, STATIC_URL_ERROR};
+use crate::request::{Executable, TagRequest};
+use crate::{DigitalOcean, ROOT_URL, STATIC_URL_ERROR};
 use getset::{Getters, Setters};
 use serde::Serialize;
-use serde_json::Value;
+use serde_json::{Map, Value};
 use std::fmt::Display;
 use url::Url;
 
@@ -30,6 +31,11 @@ pub struct Tag {
     /// An embedded object containing key value pairs of resource type and
     /// resource statistics.
     resources: Value,
+    /// Any fields returned by the API which are not otherwise captured.
+    ///
+    /// *Note:* With the `strict` feature enabled these cause an error instead.
+    #[serde(flatten, deserialize_with = "super::deserialize_extra")]
+    extra: Map<String, Value>,
 }
 
 impl Tag {
@@ -63,7 +69,7 @@ impl Tag {
         Request::new(url)
     }
     /// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#retrieve-a-tag)
-    pub fn list() -> TagRequest<List, Tag> {
+    pub fn list() -> TagRequest<List, Vec<Tag>> {
         let mut url = ROOT_URL.clone();
         url.path_segments_mut()
             .expect(STATIC_URL_ERROR)
@@ -86,6 +92,18 @@ impl Tag {
     }
 }
 
+impl TagRequest<Create, Tag> {
+    /// Return the tag if it exists, otherwise create it.
+    pub fn ensure(self, client: &DigitalOcean) -> Result<Ensured<Tag>, Error> {
+        let name = self.body()["name"].as_str().unwrap_or_default().to_string();
+        match Tag::get(name).execute(client) {
+            Ok(tag) => Ok(Ensured::Found(tag)),
+            Err(Error::NotFound) => Ok(Ensured::Created(self.execute(client)?)),
+            Err(e) => Err(e),
+        }
+    }
+}
+
 impl TagRequest<Get, Tag> {
     /// Accepts tuples matching `(id, type)`. Currently the only `type` is `"droplet"`.
     ///
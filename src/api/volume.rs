@@ -1,14 +1,16 @@
 use super::region::Region;
 use super::snapshot::Snapshot;
 use super::{ApiLinks, ApiMeta};
-use super::{HasPagination, HasResponse, HasValue};
+use super::{Ensured, HasPagination, HasResponse, HasValue};
+use crate::error::Error;
 use crate::method::{Create, Delete, Get, List};
 use crate::request::Request;
-use crate::request::{SnapshotRequest, VolumeRequest};
-use crate::{ROOT_URL, STATIC_URL_ERROR};
+use crate::request::{Executable, SnapshotRequest, VolumeRequest};
+use crate::{DigitalOcean, ROOT_URL, STATIC_URL_ERROR};
 use chrono::{DateTime, Utc};
 use getset::{Getters, Setters};
 use serde::Serialize;
+use serde_json::{Map, Value};
 use std::fmt::Display;
 use url::Url;
 
@@ -47,6 +49,16 @@ pub struct Volume {
     /// A time value given in ISO8601 combined date and time format that
     /// represents when the Block Storage volume was created.
     created_at: DateTime<Utc>,
+    /// The file system the volume was formatted with, if it was formatted on
+    /// creation.
+    filesystem_type: Option<String>,
+    /// The label of the volume's file system, if it was given one.
+    filesystem_label: Option<String>,
+    /// Any fields returned by the API which are not otherwise captured.
+    ///
+    /// *Note:* With the `strict` feature enabled these cause an error instead.
+    #[serde(flatten, deserialize_with = "super::deserialize_extra")]
+    extra: Map<String, Value>,
 }
 
 impl Volume {
@@ -137,6 +149,17 @@ impl Volume {
 }
 
 impl VolumeRequest<List, Vec<Volume>> {
+    /// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/volumes_list)
+    pub fn name<S>(mut self, name: S) -> Self
+    where
+        S: AsRef<str> + Serialize + Display,
+    {
+        self.url_mut()
+            .query_pairs_mut()
+            .append_pair("name", name.as_ref());
+
+        self
+    }
     /// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#list-all-block-storage-volumes)
     pub fn region<S>(mut self, region: S) -> Self
     where
@@ -216,6 +239,66 @@ impl VolumeRequest<Create, Volume> {
         self.body_mut()["snapshot_id"] = json!(val);
         self
     }
+
+    /// The file system to format the volume with, ex: `ext4` or `xfs`.
+    ///
+    /// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/volumes_create)
+    pub fn filesystem_type<S>(mut self, val: S) -> Self
+    where
+        S: AsRef<str> + Serialize + Display,
+    {
+        self.body_mut()["filesystem_type"] = json!(val);
+        self
+    }
+
+    /// The label to give the volume's file system. Only used along with
+    /// `filesystem_type`.
+    ///
+    /// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/volumes_create)
+    pub fn filesystem_label<S>(mut self, val: S) -> Self
+    where
+        S: AsRef<str> + Serialize + Display,
+    {
+        self.body_mut()["filesystem_label"] = json!(val);
+        self
+    }
+
+    /// Return the volume with the same name, in the same region if one was
+    /// given, if there is one, otherwise create it.
+    ///
+    /// Returns `Error::Incompatible` if the existing volume has a different
+    /// size, or a different file system when one was given.
+    pub fn ensure(self, client: &DigitalOcean) -> Result<Ensured<Volume>, Error> {
+        let name = self.body()["name"].as_str().unwrap_or_default().to_string();
+        let region = self.body()["region"].as_str().map(String::from);
+        let size = self.body()["size_gigabytes"].as_f64().unwrap_or_default();
+        let filesystem = self.body()["filesystem_type"].as_str().map(String::from);
+
+        let mut list = Volume::list().name(&name);
+        if let Some(ref region) = region {
+            list = list.region(region);
+        }
+        let volume = match list.execute(client)?.into_iter().next() {
+            Some(volume) => volume,
+            None => return Ok(Ensured::Created(self.execute(client)?)),
+        };
+
+        if f64::from(volume.size_gigabytes) != size {
+            return Err(Error::Incompatible(format!(
+                "volume {} is {} GB, not {} GB",
+                name, volume.size_gigabytes, size
+            )));
+        }
+        if filesystem.is_some() && volume.filesystem_type != filesystem {
+            return Err(Error::Incompatible(format!(
+                "volume {} has file system {}, not {}",
+                name,
+                volume.filesystem_type.as_deref().unwrap_or("none"),
+                filesystem.unwrap_or_default()
+            )));
+        }
+        Ok(Ensured::Found(volume))
+    }
 }
 
 /// Response type returned from Digital Ocean.
@@ -0,0 +1,471 @@
+use super::domain::Domain;
+use super::domain_record::domain_record_fields::RecordData;
+use super::domain_record::DomainRecord;
+use crate::error::Error;
+use crate::method::{Create, List};
+use crate::request::DomainRecordRequest;
+use std::fmt::Write;
+use std::iter::Peekable;
+use std::str::Chars;
+
+// The longest string permitted in a TXT record, in bytes (RFC 1035 3.3).
+const MAX_CHARACTER_STRING: usize = 255;
+
+impl Domain {
+    /// Export the domain and its records as an RFC 1035 zone file. Names are
+    /// written relative to the domain.
+    ///
+    /// **Note:** The SOA record and records of unsupported types are omitted,
+    /// as Digital Ocean manages or does not accept them.
+    pub fn to_zone_file(&self, records: &[DomainRecord]) -> String {
+        let mut zone = String::new();
+        writeln!(zone, "$ORIGIN {}.", self.name()).expect("Writing to a String cannot fail.");
+        if let Some(ttl) = self.ttl() {
+            writeln!(zone, "$TTL {}", ttl).expect("Writing to a String cannot fail.");
+        }
+
+        for record in records {
+            if let Some(data) = record.record_data() {
+                writeln!(
+                    zone,
+                    "{}\t{}\tIN\t{}\t{}",
+                    record.name(),
+                    record.ttl(),
+                    data.kind(),
+                    rdata(&data)
+                )
+                .expect("Writing to a String cannot fail.");
+            }
+        }
+        zone
+    }
+}
+
+impl DomainRecordRequest<List, Vec<DomainRecord>> {
+    /// Parse an RFC 1035 zone file into the requests needed to create its
+    /// records on this domain. `$ORIGIN` and `$TTL` directives, relative
+    /// names, and TXT records made of several strings are supported. The
+    /// origin defaults to the domain itself.
+    ///
+    /// **Note:** SOA records and NS records for the domain itself are skipped
+    /// as they are managed by Digital Ocean.
+    pub fn from_zone_file(
+        &self,
+        zone: &str,
+    ) -> Result<Vec<DomainRecordRequest<Create, DomainRecord>>, Error> {
+        let domain = format!("{}.", self.domain_name());
+
+        let mut origin = domain.clone();
+        let mut default_ttl = None;
+        let mut last_owner: Option<String> = None;
+        let mut requests = Vec::new();
+
+        for entry in tokenize(zone)? {
+            let line = entry.line;
+            let invalid = |message: String| invalid(line, message);
+            let mut tokens = entry.tokens.into_iter().peekable();
+
+            let first = match tokens.peek() {
+                Some(token) => token,
+                None => continue,
+            };
+            if !first.quoted && first.text.starts_with('$') {
+                let directive = tokens.next().expect("Token was peeked.").text;
+                let argument = tokens
+                    .next()
+                    .ok_or_else(|| invalid(format!("{} requires an argument", directive)))?;
+                match directive.to_uppercase().as_str() {
+                    "$ORIGIN" => origin = absolute(&argument.text, &origin),
+                    "$TTL" => {
+                        default_ttl =
+                            Some(parse_ttl(&argument.text).ok_or_else(|| {
+                                invalid(format!("invalid TTL {:?}", argument.text))
+                            })?)
+                    }
+                    _ => return Err(invalid(format!("unsupported directive {}", directive))),
+                }
+                continue;
+            }
+
+            let owner = if entry.inherits_owner {
+                last_owner
+                    .clone()
+                    .ok_or_else(|| invalid(String::from("record has no owner name")))?
+            } else {
+                let owner = tokens.next().expect("Token was peeked.");
+                absolute(&owner.text, &origin)
+            };
+            last_owner = Some(owner.clone());
+
+            // The TTL and class may appear in either order before the type.
+            let mut ttl = None;
+            let mut kind = None;
+            for token in tokens.by_ref() {
+                let text = token.text.to_uppercase();
+                if text == "IN" {
+                    continue;
+                } else if ["CH", "HS", "CS"].contains(&text.as_str()) {
+                    return Err(invalid(format!("unsupported class {}", text)));
+                } else if ttl.is_none() && text.starts_with(|c: char| c.is_ascii_digit()) {
+                    ttl = Some(
+                        parse_ttl(&text)
+                            .ok_or_else(|| invalid(format!("invalid TTL {:?}", text)))?,
+                    );
+                } else {
+                    kind = Some(text);
+                    break;
+                }
+            }
+            let kind = kind.ok_or_else(|| invalid(String::from("record has no type")))?;
+            let rdata = tokens.map(|token| token.text).collect::<Vec<_>>();
+
+            let data = match parse_rdata(&kind, &rdata, &origin) {
+                Ok(Some(data)) => data,
+                Ok(None) => continue,
+                Err(message) => return Err(invalid(message)),
+            };
+            if let RecordData::NS(_) = data {
+                if owner.eq_ignore_ascii_case(&domain) {
+                    continue;
+                }
+            }
+
+            let name = relative(&owner, &domain)
+                .ok_or_else(|| invalid(format!("{} is outside of {}", owner, domain)))?;
+            let mut request = self
+                .clone()
+                .create_record(name, data)
+                .map_err(|e| match e {
+                    Error::InvalidDomainRecord(message) => invalid(message),
+                    e => e,
+                })?;
+            if let Some(ttl) = ttl.or(default_ttl) {
+                request = request.ttl(ttl);
+            }
+            requests.push(request);
+        }
+
+        Ok(requests)
+    }
+}
+
+fn invalid(line: usize, message: String) -> Error {
+    Error::InvalidZoneFile(format!("line {}: {}", line, message))
+}
+
+// Formats the data of a record as it appears in a zone file.
+fn rdata(data: &RecordData) -> String {
+    let host = |host: &str| {
+        if host == "@" || host.ends_with('.') {
+            host.to_string()
+        } else {
+            // The API returns fully qualified names without the final dot.
+            format!("{}.", host)
+        }
+    };
+    match data {
+        RecordData::A(address) => address.to_string(),
+        RecordData::AAAA(address) => address.to_string(),
+        RecordData::CNAME(target) | RecordData::NS(target) => host(target),
+        RecordData::MX {
+            priority,
+            host: target,
+        } => format!("{} {}", priority, host(target)),
+        RecordData::TXT(text) => character_strings(text),
+        RecordData::SRV {
+            priority,
+            weight,
+            port,
+            target,
+        } => format!("{} {} {} {}", priority, weight, port, host(target)),
+        RecordData::CAA { flags, tag, value } => format!("{} {} {}", flags, tag, quote(value)),
+    }
+}
+
+// Splits text into quoted strings no longer than permitted.
+fn character_strings(text: &str) -> String {
+    let mut strings = Vec::new();
+    let mut current = String::new();
+    for c in text.chars() {
+        if current.len() + c.len_utf8() > MAX_CHARACTER_STRING {
+            strings.push(quote(&current));
+            current.clear();
+        }
+        current.push(c);
+    }
+    strings.push(quote(&current));
+    strings.join(" ")
+}
+
+fn quote(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+// Returns `Ok(None)` for records which are skipped.
+fn parse_rdata(kind: &str, rdata: &[String], origin: &str) -> Result<Option<RecordData>, String> {
+    let expect = |count: usize| {
+        if rdata.len() == count {
+            Ok(())
+        } else {
+            Err(format!(
+                "{} record expects {} fields, found {}",
+                kind,
+                count,
+                rdata.len()
+            ))
+        }
+    };
+    let number = |text: &str| {
+        text.parse::<usize>()
+            .map_err(|_| format!("{:?} is not a number", text))
+    };
+
+    let data = match kind {
+        "SOA" => return Ok(None),
+        "A" => {
+            expect(1)?;
+            RecordData::A(
+                rdata[0]
+                    .parse()
+                    .map_err(|_| format!("{:?} is not an IPv4 address", rdata[0]))?,
+            )
+        }
+        "AAAA" => {
+            expect(1)?;
+            RecordData::AAAA(
+                rdata[0]
+                    .parse()
+                    .map_err(|_| format!("{:?} is not an IPv6 address", rdata[0]))?,
+            )
+        }
+        "CNAME" => {
+            expect(1)?;
+            RecordData::CNAME(absolute(&rdata[0], origin))
+        }
+        "NS" => {
+            expect(1)?;
+            RecordData::NS(absolute(&rdata[0], origin))
+        }
+        "MX" => {
+            expect(2)?;
+            RecordData::MX {
+                priority: number(&rdata[0])?,
+                host: absolute(&rdata[1], origin),
+            }
+        }
+        "TXT" => {
+            if rdata.is_empty() {
+                return Err(String::from("TXT record expects at least 1 field"));
+            }
+            RecordData::TXT(rdata.concat())
+        }
+        "SRV" => {
+            expect(4)?;
+            RecordData::SRV {
+                priority: number(&rdata[0])?,
+                weight: number(&rdata[1])?,
+                port: number(&rdata[2])?,
+                target: absolute(&rdata[3], origin),
+            }
+        }
+        "CAA" => {
+            expect(3)?;
+            RecordData::CAA {
+                flags: rdata[0]
+                    .parse()
+                    .map_err(|_| format!("{:?} is not valid CAA flags", rdata[0]))?,
+                tag: rdata[1].clone(),
+                value: rdata[2].clone(),
+            }
+        }
+        _ => return Err(format!("unsupported record type {}", kind)),
+    };
+    Ok(Some(data))
+}
+
+// Makes a name fully qualified, relative to the origin.
+fn absolute(name: &str, origin: &str) -> String {
+    if name == "@" {
+        origin.to_string()
+    } else if name.ends_with('.') {
+        name.to_string()
+    } else {
+        format!("{}.{}", name, origin)
+    }
+}
+
+// Makes a fully qualified name relative to the domain, as the API expects.
+fn relative(name: &str, domain: &str) -> Option<String> {
+    let lower = name.to_ascii_lowercase();
+    let domain = domain.to_ascii_lowercase();
+    if lower == domain {
+        Some(String::from("@"))
+    } else {
+        lower
+            .strip_suffix(&format!(".{}", domain))
+            .map(|_| name[..name.len() - domain.len() - 1].to_string())
+    }
+}
+
+// Parses a TTL in seconds, or with BIND style units (ex: "1h30m").
+fn parse_ttl(text: &str) -> Option<usize> {
+    if let Ok(seconds) = text.parse() {
+        return Some(seconds);
+    }
+    let mut total = 0usize;
+    let mut digits = String::new();
+    for c in text.chars() {
+        if c.is_ascii_digit() {
+            digits.push(c);
+            continue;
+        }
+        let unit = match c.to_ascii_lowercase() {
+            's' => 1,
+            'm' => 60,
+            'h' => 60 * 60,
+            'd' => 24 * 60 * 60,
+            'w' => 7 * 24 * 60 * 60,
+            _ => return None,
+        };
+        total = total.checked_add(digits.parse::<usize>().ok()?.checked_mul(unit)?)?;
+        digits.clear();
+    }
+    if digits.is_empty() {
+        Some(total)
+    } else {
+        None
+    }
+}
+
+// A single entry of a zone file, which may span several lines in parentheses.
+struct Entry {
+    line: usize,
+    inherits_owner: bool,
+    tokens: Vec<Token>,
+}
+
+struct Token {
+    text: String,
+    quoted: bool,
+}
+
+fn tokenize(zone: &str) -> Result<Vec<Entry>, Error> {
+    let mut entries = Vec::new();
+    let mut tokens = Vec::new();
+    let mut current: Option<String> = None;
+    let mut depth = 0;
+    let mut line = 1;
+    let mut entry_line = 1;
+    let mut inherits_owner = false;
+    let mut at_line_start = true;
+
+    fn finish(current: &mut Option<String>, tokens: &mut Vec<Token>) {
+        if let Some(text) = current.take() {
+            tokens.push(Token {
+                text,
+                quoted: false,
+            });
+        }
+    }
+
+    let mut chars = zone.chars().peekable();
+    while let Some(c) = chars.next() {
+        if at_line_start && depth == 0 {
+            entry_line = line;
+            inherits_owner = c == ' ' || c == '\t';
+        }
+        at_line_start = false;
+
+        match c {
+            '"' => {
+                finish(&mut current, &mut tokens);
+                let mut text = String::new();
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => text.push(escaped(&mut chars, line)?),
+                        Some(c) => {
+                            if c == '\n' {
+                                line += 1;
+                            }
+                            text.push(c);
+                        }
+                        None => return Err(invalid(line, String::from("unterminated string"))),
+                    }
+                }
+                tokens.push(Token { text, quoted: true });
+            }
+            ';' => {
+                while chars.peek().map_or(false, |c| *c != '\n') {
+                    chars.next();
+                }
+            }
+            '(' => {
+                finish(&mut current, &mut tokens);
+                depth += 1;
+            }
+            ')' => {
+                finish(&mut current, &mut tokens);
+                if depth == 0 {
+                    return Err(invalid(line, String::from("unbalanced parentheses")));
+                }
+                depth -= 1;
+            }
+            '\n' => {
+                finish(&mut current, &mut tokens);
+                line += 1;
+                if depth == 0 {
+                    if !tokens.is_empty() {
+                        entries.push(Entry {
+                            line: entry_line,
+                            inherits_owner,
+                            tokens: std::mem::take(&mut tokens),
+                        });
+                    }
+                    at_line_start = true;
+                }
+            }
+            c if c.is_whitespace() => finish(&mut current, &mut tokens),
+            '\\' => {
+                let c = escaped(&mut chars, line)?;
+                current.get_or_insert_with(String::new).push(c);
+            }
+            c => current.get_or_insert_with(String::new).push(c),
+        }
+    }
+
+    finish(&mut current, &mut tokens);
+    if depth != 0 {
+        return Err(invalid(line, String::from("unbalanced parentheses")));
+    }
+    if !tokens.is_empty() {
+        entries.push(Entry {
+            line: entry_line,
+            inherits_owner,
+            tokens,
+        });
+    }
+    Ok(entries)
+}
+
+// Reads the character following a `\`, which is either literal or a `\DDD`
+// decimal escape.
+fn escaped(chars: &mut Peekable<Chars>, line: usize) -> Result<char, Error> {
+    let first = chars
+        .next()
+        .ok_or_else(|| invalid(line, String::from("incomplete escape")))?;
+    if !first.is_ascii_digit() {
+        return Ok(first);
+    }
+    let mut digits = first.to_string();
+    for _ in 0..2 {
+        match chars.next() {
+            Some(c) if c.is_ascii_digit() => digits.push(c),
+            _ => return Err(invalid(line, String::from("incomplete escape"))),
+        }
+    }
+    digits
+        .parse::<u8>()
+        .map(char::from)
+        .map_err(|_| invalid(line, format!("invalid escape \\{}", digits)))
+}
@@ -0,0 +1,131 @@
+//! Executing many requests concurrently.
+//!
+//! A [`Batch`](struct.Batch.html) runs a collection of requests of the same
+//! type on a bounded number of threads, and returns the result of each in the
+//! order the requests were given. All threads use the same client, so they
+//! share its [`RateLimit`](../rate_limit/struct.RateLimit.html), if any.
+//!
+//! ```rust,no_run
+//! # extern crate digitalocean;
+//! use digitalocean::batch::Batch;
+//! use digitalocean::prelude::*;
+//! use digitalocean::rate_limit::RateLimit;
+//!
+//! # fn main() -> Result<(), digitalocean::error::Error> {
+//! let client = DigitalOcean::new("token")?.rate_limit(RateLimit::per_minute(250));
+//!
+//! let droplets = Droplet::list_by_tag("web").execute(&client)?;
+//! let resizes = droplets
+//!     .iter()
+//!     .map(|droplet| Droplet::get(*droplet.id()).resize("s-2vcpu-4gb", false));
+//!
+//! for result in Batch::new(resizes).concurrency(8).stop_on_error().execute(&client) {
+//!     let action = result?;
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::api::HasResponse;
+use crate::error::Error;
+use crate::method::Method;
+use crate::request::{Executable, Request};
+use crate::DigitalOcean;
+use log::info;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+const DEFAULT_CONCURRENCY: usize = 4;
+
+/// A collection of requests to be executed concurrently.
+#[derive(Debug, Clone)]
+pub struct Batch<A, V>
+where
+    A: Method,
+{
+    requests: Vec<Request<A, V>>,
+    concurrency: usize,
+    stop_on_error: bool,
+}
+
+impl<A, V> Batch<A, V>
+where
+    A: Method,
+{
+    pub fn new<I>(requests: I) -> Self
+    where
+        I: IntoIterator<Item = Request<A, V>>,
+    {
+        Batch {
+            requests: requests.into_iter().collect(),
+            concurrency: DEFAULT_CONCURRENCY,
+            stop_on_error: false,
+        }
+    }
+
+    /// Execute at most `concurrency` requests at once. Defaults to 4.
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Stop sending requests once one fails. Requests which were not sent
+    /// result in `Error::Cancelled`.
+    pub fn stop_on_error(mut self) -> Self {
+        self.stop_on_error = true;
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.requests.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.requests.is_empty()
+    }
+
+    /// Execute every request, returning their results in order.
+    pub fn execute(self, instance: &DigitalOcean) -> Vec<Result<V, Error>>
+    where
+        Request<A, V>: Executable<V> + Send,
+        V: HasResponse + Send,
+    {
+        let stop_on_error = self.stop_on_error;
+        let count = self.requests.len();
+        let workers = self.concurrency.min(count);
+        info!("Executing {} requests on {} threads.", count, workers);
+
+        let queue = Mutex::new(self.requests.into_iter().enumerate());
+        let results: Mutex<Vec<Option<Result<V, Error>>>> =
+            Mutex::new((0..count).map(|_| None).collect());
+        let stopped = AtomicBool::new(false);
+
+        thread::scope(|scope| {
+            for _ in 0..workers {
+                scope.spawn(|| loop {
+                    if stopped.load(Ordering::SeqCst) {
+                        break;
+                    }
+                    let next = queue.lock().unwrap_or_else(|e| e.into_inner()).next();
+                    let (index, request) = match next {
+                        Some(next) => next,
+                        None => break,
+                    };
+                    let result = request.execute(instance);
+                    if result.is_err() && stop_on_error {
+                        stopped.store(true, Ordering::SeqCst);
+                    }
+                    results.lock().unwrap_or_else(|e| e.into_inner())[index] = Some(result);
+                });
+            }
+        });
+
+        results
+            .into_inner()
+            .unwrap_or_else(|e| e.into_inner())
+            .into_iter()
+            .map(|result| result.unwrap_or(Err(Error::Cancelled)))
+            .collect()
+    }
+}
@@ -0,0 +1,495 @@
+//! A command-line tool for the DigitalOcean API, built on this crate.
+//!
+//! ```bash
+//! export DIGITALOCEAN_TOKEN=...
+//! digitalocean droplet list
+//! digitalocean --context staging droplet list
+//! digitalocean --output json droplet get 3164444
+//! digitalocean droplet action resize 3164444 s-2vcpu-2gb --wait
+//! ```
+
+use digitalocean::api::{Action, Domain, DomainRecord, Droplet, LoadBalancer, Readiness, Volume};
+use digitalocean::config::Context;
+use digitalocean::request::Executable;
+use digitalocean::DigitalOcean;
+use serde::Serialize;
+use std::process;
+use std::str::FromStr;
+use std::time::Duration;
+use structopt::StructOpt;
+
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+const TIMEOUT: Duration = Duration::from_secs(10 * 60);
+
+#[derive(StructOpt, Debug)]
+#[structopt(name = "digitalocean", about = "Manage DigitalOcean resources.")]
+struct Options {
+    /// The API token to authenticate with, instead of the context's token.
+    #[structopt(long, env = "DIGITALOCEAN_TOKEN", hide_env_values = true)]
+    token: Option<String>,
+    /// The context to use from the config file.
+    #[structopt(long, env = "DIGITALOCEAN_CONTEXT")]
+    context: Option<String>,
+    /// The output format: table, json or yaml.
+    #[structopt(short, long, default_value = "table")]
+    output: Output,
+    #[structopt(subcommand)]
+    command: Command,
+}
+
+#[derive(StructOpt, Debug)]
+enum Command {
+    /// Manage Droplets.
+    Droplet(DropletCommand),
+    /// Manage domains and their records.
+    Domain(DomainCommand),
+    /// Manage Block Storage volumes.
+    Volume(VolumeCommand),
+    /// Manage Load Balancers.
+    #[structopt(name = "lb")]
+    LoadBalancer(LoadBalancerCommand),
+    /// Inspect actions.
+    Action(ActionCommand),
+}
+
+#[derive(StructOpt, Debug)]
+enum DropletCommand {
+    /// List all Droplets.
+    List {
+        /// Only list Droplets with this tag.
+        #[structopt(long)]
+        tag: Option<String>,
+    },
+    /// Show a Droplet.
+    Get { id: usize },
+    /// Create a Droplet.
+    Create {
+        name: String,
+        /// Defaults to the region of the context.
+        #[structopt(long)]
+        region: Option<String>,
+        #[structopt(long)]
+        size: String,
+        #[structopt(long)]
+        image: String,
+        /// The ID or fingerprint of an SSH key to embed. May be repeated.
+        /// Defaults to the SSH keys of the context.
+        #[structopt(long = "ssh-key")]
+        ssh_keys: Vec<String>,
+        /// A tag to apply. May be repeated.
+        #[structopt(long = "tag")]
+        tags: Vec<String>,
+        #[structopt(long)]
+        ipv6: bool,
+        #[structopt(long)]
+        monitoring: bool,
+        /// Wait until the Droplet is active and has its public addresses.
+        #[structopt(long)]
+        wait: bool,
+    },
+    /// Delete a Droplet.
+    Delete { id: usize },
+    /// Perform an action on a Droplet.
+    Action(DropletActionCommand),
+}
+
+#[derive(StructOpt, Debug)]
+enum DropletActionCommand {
+    /// Reboot a Droplet.
+    Reboot {
+        id: usize,
+        /// Wait until the action has completed.
+        #[structopt(long)]
+        wait: bool,
+    },
+    /// Power off a Droplet.
+    PowerOff {
+        id: usize,
+        /// Wait until the action has completed.
+        #[structopt(long)]
+        wait: bool,
+    },
+    /// Power on a Droplet.
+    PowerOn {
+        id: usize,
+        /// Wait until the action has completed.
+        #[structopt(long)]
+        wait: bool,
+    },
+    /// Resize a Droplet. It must be powered off first.
+    Resize {
+        id: usize,
+        size: String,
+        /// Also resize the disk, which cannot be undone.
+        #[structopt(long)]
+        disk: bool,
+        /// Wait until the action has completed.
+        #[structopt(long)]
+        wait: bool,
+    },
+    /// Take a snapshot of a Droplet.
+    Snapshot {
+        id: usize,
+        name: String,
+        /// Wait until the action has completed.
+        #[structopt(long)]
+        wait: bool,
+    },
+}
+
+#[derive(StructOpt, Debug)]
+enum DomainCommand {
+    /// List all domains.
+    List,
+    /// List the records of a domain.
+    Records { domain: String },
+}
+
+#[derive(StructOpt, Debug)]
+enum VolumeCommand {
+    /// List all volumes.
+    List,
+    /// Attach a volume to a Droplet.
+    Attach {
+        id: String,
+        droplet: usize,
+        /// Wait until the action has completed.
+        #[structopt(long)]
+        wait: bool,
+    },
+    /// Detach a volume from a Droplet.
+    Detach {
+        id: String,
+        droplet: usize,
+        /// Wait until the action has completed.
+        #[structopt(long)]
+        wait: bool,
+    },
+}
+
+#[derive(StructOpt, Debug)]
+enum LoadBalancerCommand {
+    /// List all Load Balancers.
+    List,
+    /// Show a Load Balancer.
+    Get { id: String },
+    /// Add Droplets to a Load Balancer.
+    AddDroplets { id: String, droplets: Vec<usize> },
+    /// Remove Droplets from a Load Balancer.
+    RemoveDroplets { id: String, droplets: Vec<usize> },
+}
+
+#[derive(StructOpt, Debug)]
+enum ActionCommand {
+    /// Show an action.
+    Get {
+        id: usize,
+        /// Wait until the action has completed.
+        #[structopt(long)]
+        wait: bool,
+    },
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Output {
+    Table,
+    Json,
+    Yaml,
+}
+
+impl FromStr for Output {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "table" => Ok(Output::Table),
+            "json" => Ok(Output::Json),
+            "yaml" => Ok(Output::Yaml),
+            _ => Err(format!("unknown output format {:?}", s)),
+        }
+    }
+}
+
+/// Values which can be printed as rows of a table.
+trait Tabular {
+    fn headers() -> Vec<&'static str>;
+    fn row(&self) -> Vec<String>;
+}
+
+impl Tabular for Droplet {
+    fn headers() -> Vec<&'static str> {
+        vec![
+            "ID",
+            "NAME",
+            "PUBLIC IPV4",
+            "REGION",
+            "SIZE",
+            "STATUS",
+            "TAGS",
+        ]
+    }
+    fn row(&self) -> Vec<String> {
+        let public_ipv4 = self
+            .public_ipv4()
+            .map(|address| address.to_string())
+            .unwrap_or_default();
+        vec![
+            self.id().to_string(),
+            self.name().clone(),
+            public_ipv4,
+            self.region().slug().clone(),
+            self.size_slug().clone(),
+            self.status().clone(),
+            self.tags().join(","),
+        ]
+    }
+}
+
+impl Tabular for Domain {
+    fn headers() -> Vec<&'static str> {
+        vec!["NAME", "TTL"]
+    }
+    fn row(&self) -> Vec<String> {
+        vec![
+            self.name().clone(),
+            self.ttl().map(|ttl| ttl.to_string()).unwrap_or_default(),
+        ]
+    }
+}
+
+impl Tabular for DomainRecord {
+    fn headers() -> Vec<&'static str> {
+        vec![
+            "ID", "TYPE", "NAME", "DATA", "PRIORITY", "PORT", "TTL", "WEIGHT",
+        ]
+    }
+    fn row(&self) -> Vec<String> {
+        let optional = |val: &Option<usize>| val.map(|v| v.to_string()).unwrap_or_default();
+        vec![
+            self.id().to_string(),
+            self.kind().clone(),
+            self.name().clone(),
+            self.data().clone(),
+            optional(self.priority()),
+            optional(self.port()),
+            self.ttl().to_string(),
+            optional(self.weight()),
+        ]
+    }
+}
+
+impl Tabular for Volume {
+    fn headers() -> Vec<&'static str> {
+        vec!["ID", "NAME", "SIZE", "REGION", "DROPLETS"]
+    }
+    fn row(&self) -> Vec<String> {
+        vec![
+            self.id().clone(),
+            self.name().clone(),
+            format!("{} GiB", self.size_gigabytes()),
+            self.region().slug().clone(),
+            join(self.droplet_ids()),
+        ]
+    }
+}
+
+impl Tabular for LoadBalancer {
+    fn headers() -> Vec<&'static str> {
+        vec!["ID", "NAME", "IP", "REGION", "STATUS", "DROPLETS"]
+    }
+    fn row(&self) -> Vec<String> {
+        vec![
+            self.id().clone(),
+            self.name().clone(),
+            self.ip().to_string(),
+            self.region().slug().clone(),
+            self.status().clone(),
+            join(self.droplet_ids()),
+        ]
+    }
+}
+
+impl Tabular for Action {
+    fn headers() -> Vec<&'static str> {
+        vec!["ID", "STATUS", "RESOURCE", "STARTED", "COMPLETED"]
+    }
+    fn row(&self) -> Vec<String> {
+        vec![
+            self.id().to_string(),
+            self.status().clone(),
+            format!("{} {}", self.resource_type(), self.resource_id()),
+            self.started_at().to_rfc3339(),
+            self.completed_at()
+                .map(|at| at.to_rfc3339())
+                .unwrap_or_default(),
+        ]
+    }
+}
+
+fn join(ids: &[usize]) -> String {
+    ids.iter()
+        .map(|id| id.to_string())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+fn print<T>(output: Output, values: &[T]) -> Result<(), Box<dyn std::error::Error>>
+where
+    T: Tabular + Serialize,
+{
+    match output {
+        Output::Json => println!("{}", serde_json::to_string_pretty(values)?),
+        Output::Yaml => println!("{}", serde_yaml::to_string(values)?),
+        Output::Table => {
+            let headers = T::headers()
+                .into_iter()
+                .map(String::from)
+                .collect::<Vec<_>>();
+            let rows = values.iter().map(T::row).collect::<Vec<_>>();
+            let mut widths = headers.iter().map(String::len).collect::<Vec<_>>();
+            for row in &rows {
+                for (width, cell) in widths.iter_mut().zip(row) {
+                    *width = (*width).max(cell.chars().count());
+                }
+            }
+            for row in Some(&headers).into_iter().chain(&rows) {
+                let line = row
+                    .iter()
+                    .zip(&widths)
+                    .map(|(cell, width)| format!("{:width$}", cell, width = width))
+                    .collect::<Vec<_>>()
+                    .join("  ");
+                println!("{}", line.trim_end());
+            }
+        }
+    }
+    Ok(())
+}
+
+fn print_action(
+    client: &DigitalOcean,
+    output: Output,
+    action: Action,
+    wait: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let action = if wait {
+        action.wait(client, POLL_INTERVAL, TIMEOUT)?
+    } else {
+        action
+    };
+    print(output, &[action])
+}
+
+fn run(options: Options) -> Result<(), Box<dyn std::error::Error>> {
+    let context = Context::resolve(options.context.as_deref(), options.token)?;
+    let client = DigitalOcean::from_context(&context)?;
+    let output = options.output;
+
+    match options.command {
+        Command::Droplet(command) => match command {
+            DropletCommand::List { tag } => {
+                let req = match tag {
+                    Some(tag) => Droplet::list_by_tag(tag),
+                    None => Droplet::list(),
+                };
+                print(output, &req.execute(&client)?)
+            }
+            DropletCommand::Get { id } => print(output, &[Droplet::get(id).execute(&client)?]),
+            DropletCommand::Create {
+                name,
+                region,
+                size,
+                image,
+                ssh_keys,
+                tags,
+                ipv6,
+                monitoring,
+                wait,
+            } => {
+                let region = region
+                    .or_else(|| context.region().clone())
+                    .ok_or("--region is required as the context has no default region")?;
+                let ssh_keys = if ssh_keys.is_empty() {
+                    context.ssh_keys().clone()
+                } else {
+                    ssh_keys
+                };
+                let mut droplet = Droplet::create(name, region, size, image)
+                    .ssh_keys(ssh_keys)
+                    .tags(tags)
+                    .ipv6(ipv6)
+                    .monitoring(monitoring)
+                    .execute(&client)?;
+                if wait {
+                    let readiness = Readiness::new()
+                        .ipv6(ipv6)
+                        .poll_interval(POLL_INTERVAL)
+                        .timeout(TIMEOUT);
+                    droplet = droplet.wait_until_ready(&client, &readiness)?;
+                }
+                print(output, &[droplet])
+            }
+            DropletCommand::Delete { id } => Ok(Droplet::delete(id).execute(&client)?),
+            DropletCommand::Action(command) => {
+                let (req, wait) = match command {
+                    DropletActionCommand::Reboot { id, wait } => (Droplet::get(id).reboot(), wait),
+                    DropletActionCommand::PowerOff { id, wait } => {
+                        (Droplet::get(id).power(false), wait)
+                    }
+                    DropletActionCommand::PowerOn { id, wait } => {
+                        (Droplet::get(id).power(true), wait)
+                    }
+                    DropletActionCommand::Resize {
+                        id,
+                        size,
+                        disk,
+                        wait,
+                    } => (Droplet::get(id).resize(size, disk), wait),
+                    DropletActionCommand::Snapshot { id, name, wait } => {
+                        (Droplet::get(id).snapshot(name), wait)
+                    }
+                };
+                print_action(&client, output, req.execute(&client)?, wait)
+            }
+        },
+        Command::Domain(command) => match command {
+            DomainCommand::List => print(output, &Domain::list().execute(&client)?),
+            DomainCommand::Records { domain } => {
+                print(output, &Domain::get(domain).records().execute(&client)?)
+            }
+        },
+        Command::Volume(command) => match command {
+            VolumeCommand::List => print(output, &Volume::list().execute(&client)?),
+            VolumeCommand::Attach { id, droplet, wait } => {
+                let action = Volume::get(id).attach(droplet).execute(&client)?;
+                print_action(&client, output, action, wait)
+            }
+            VolumeCommand::Detach { id, droplet, wait } => {
+                let action = Volume::get(id).detach(droplet).execute(&client)?;
+                print_action(&client, output, action, wait)
+            }
+        },
+        Command::LoadBalancer(command) => match command {
+            LoadBalancerCommand::List => print(output, &LoadBalancer::list().execute(&client)?),
+            LoadBalancerCommand::Get { id } => {
+                print(output, &[LoadBalancer::get(id).execute(&client)?])
+            }
+            LoadBalancerCommand::AddDroplets { id, droplets } => Ok(LoadBalancer::get(id)
+                .add_droplets(droplets)
+                .execute(&client)?),
+            LoadBalancerCommand::RemoveDroplets { id, droplets } => Ok(LoadBalancer::get(id)
+                .remove_droplets(droplets)
+                .execute(&client)?),
+        },
+        Command::Action(ActionCommand::Get { id, wait }) => {
+            print_action(&client, output, Action::get(id).execute(&client)?, wait)
+        }
+    }
+}
+
+fn main() {
+    if let Err(e) = run(Options::from_args()) {
+        eprintln!("Error: {}", e);
+        process::exit(1);
+    }
+}
@@ -0,0 +1,257 @@
+//! Caching rarely changing resources.
+//!
+//! Regions, sizes, distribution images and the account change rarely, yet are
+//! often fetched on every run of a tool. A client given a
+//! [`Cache`](struct.Cache.html) answers `Region::list()`, `Size::list()`,
+//! `Image::distributions()` and `Account::get()` from it until their
+//! time-to-live passes. Mutations of images or the account invalidate the
+//! related entries.
+//!
+//! A cache may be persisted to a file so that it is kept between runs. It
+//! holds the responses of a single account, so should not be shared between
+//! clients of different accounts.
+//!
+//! ```rust,no_run
+//! # extern crate digitalocean;
+//! use digitalocean::cache::{Cache, CachedResource};
+//! use digitalocean::prelude::*;
+//! use std::time::Duration;
+//!
+//! # fn main() -> Result<(), digitalocean::error::Error> {
+//! let cache = Cache::persistent("/tmp/digitalocean-cache.json", Duration::from_secs(24 * 60 * 60))?
+//!     .ttl(CachedResource::Account, Duration::from_secs(60 * 60));
+//! let client = DigitalOcean::new("token")?.cache(cache);
+//!
+//! // Only sent once a day.
+//! let regions = Region::list().execute(&client)?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::client::Response;
+use crate::error::Error;
+use chrono::{DateTime, Utc};
+use log::info;
+use reqwest::StatusCode;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+use url::Url;
+
+/// A resource which may be cached.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum CachedResource {
+    /// `Region::list()`
+    Regions,
+    /// `Size::list()`
+    Sizes,
+    /// `Image::distributions()`
+    Distributions,
+    /// `Account::get()`
+    Account,
+}
+
+impl CachedResource {
+    const ALL: [CachedResource; 4] = [
+        CachedResource::Regions,
+        CachedResource::Sizes,
+        CachedResource::Distributions,
+        CachedResource::Account,
+    ];
+
+    // The resource a `GET` of `url` returns, if it may be cached.
+    fn of(url: &Url) -> Option<Self> {
+        let last = url
+            .path_segments()?
+            .rev()
+            .find(|segment| !segment.is_empty())?;
+        match last {
+            "regions" => Some(CachedResource::Regions),
+            "sizes" => Some(CachedResource::Sizes),
+            "account" => Some(CachedResource::Account),
+            "images"
+                if url
+                    .query_pairs()
+                    .any(|(key, value)| key == "type" && value == "distribution") =>
+            {
+                Some(CachedResource::Distributions)
+            }
+            _ => None,
+        }
+    }
+
+    // The resources a mutation of `url` may change.
+    fn affected_by(url: &Url) -> Vec<Self> {
+        let segments = url
+            .path_segments()
+            .map(|segments| segments.collect::<Vec<_>>())
+            .unwrap_or_default();
+        let mut affected = Vec::new();
+        if segments.contains(&"images") {
+            affected.push(CachedResource::Distributions);
+        }
+        if segments.contains(&"account") {
+            affected.push(CachedResource::Account);
+        }
+        affected
+    }
+}
+
+/// A cached response.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+struct Entry {
+    resource: CachedResource,
+    stored_at: DateTime<Utc>,
+    #[serde(default)]
+    headers: BTreeMap<String, String>,
+    body: String,
+}
+
+/// Responses of rarely changing resources, kept until their time-to-live
+/// passes.
+#[derive(Debug)]
+pub struct Cache {
+    ttls: BTreeMap<CachedResource, Duration>,
+    path: Option<PathBuf>,
+    // Keyed by URL, as lists may span several pages.
+    entries: Mutex<BTreeMap<String, Entry>>,
+}
+
+impl Cache {
+    /// Keep every cacheable resource in memory for `ttl`.
+    pub fn new(ttl: Duration) -> Self {
+        Cache {
+            ttls: CachedResource::ALL
+                .iter()
+                .map(|&resource| (resource, ttl))
+                .collect(),
+            path: None,
+            entries: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    /// Keep every cacheable resource for `ttl`, in `path` as well as in
+    /// memory. Entries already in `path` are loaded, and the file is
+    /// rewritten whenever an entry is added.
+    pub fn persistent<P>(path: P, ttl: Duration) -> Result<Self, Error>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        let entries = if path.exists() {
+            let contents = fs::read_to_string(path)
+                .map_err(|e| Error::Cache(format!("{}: {}", path.display(), e)))?;
+            serde_json::from_str(&contents)?
+        } else {
+            BTreeMap::new()
+        };
+        let mut cache = Cache::new(ttl);
+        cache.path = Some(path.to_path_buf());
+        cache.entries = Mutex::new(entries);
+        Ok(cache)
+    }
+
+    /// Keep `resource` for `ttl` instead. A `ttl` of zero stops it being
+    /// cached.
+    pub fn ttl(mut self, resource: CachedResource, ttl: Duration) -> Self {
+        self.ttls.insert(resource, ttl);
+        self
+    }
+
+    /// Remove the entries of `resource`.
+    pub fn invalidate(&self, resource: CachedResource) {
+        self.retain(|entry| entry.resource != resource);
+    }
+
+    /// Remove all entries.
+    pub fn clear(&self) {
+        self.retain(|_| false);
+    }
+
+    // The cached response to a `GET` of `url`, if it has not expired.
+    pub(crate) fn get(&self, url: &Url) -> Option<Response> {
+        let resource = CachedResource::of(url)?;
+        let entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        let entry = entries.get(url.as_str())?;
+        if entry.resource != resource || self.is_expired(entry) {
+            return None;
+        }
+        info!("Serving {} from cache.", url);
+        Some(Response {
+            status: StatusCode::OK,
+            headers: entry.headers.clone(),
+            body: entry.body.clone(),
+        })
+    }
+
+    // Stores a successful response to a `GET` of `url`, if it may be cached.
+    pub(crate) fn insert(&self, url: &Url, response: &Response) {
+        let resource = match CachedResource::of(url) {
+            Some(resource) if response.status == StatusCode::OK => resource,
+            _ => return,
+        };
+        if self.ttl_of(resource) == Duration::from_secs(0) {
+            return;
+        }
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries.insert(
+            url.to_string(),
+            Entry {
+                resource,
+                stored_at: Utc::now(),
+                headers: response.headers.clone(),
+                body: response.body.clone(),
+            },
+        );
+        self.persist(&entries);
+    }
+
+    // Removes the entries a mutation of `url` may have changed.
+    pub(crate) fn invalidate_url(&self, url: &Url) {
+        for resource in CachedResource::affected_by(url) {
+            self.invalidate(resource);
+        }
+    }
+
+    fn ttl_of(&self, resource: CachedResource) -> Duration {
+        self.ttls
+            .get(&resource)
+            .cloned()
+            .unwrap_or_else(|| Duration::from_secs(0))
+    }
+
+    fn is_expired(&self, entry: &Entry) -> bool {
+        let age = Utc::now()
+            .signed_duration_since(entry.stored_at)
+            .to_std()
+            .unwrap_or_default();
+        age >= self.ttl_of(entry.resource)
+    }
+
+    fn retain<F>(&self, keep: F)
+    where
+        F: Fn(&Entry) -> bool,
+    {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        let before = entries.len();
+        entries.retain(|_, entry| keep(entry));
+        if entries.len() != before {
+            self.persist(&entries);
+        }
+    }
+
+    // A cache which cannot be written is only a slower one, so failures are
+    // logged rather than failing the request.
+    fn persist(&self, entries: &BTreeMap<String, Entry>) {
+        if let Some(ref path) = self.path {
+            let written = serde_json::to_string_pretty(entries)
+                .map_err(|e| e.to_string())
+                .and_then(|contents| fs::write(path, contents).map_err(|e| e.to_string()));
+            if let Err(e) = written {
+                info!("Could not write cache to {:?}: {}", path, e);
+            }
+        }
+    }
+}
@@ -0,0 +1,233 @@
+//! Recording API interactions to a file and replaying them later.
+//!
+//! In [`Mode::Record`](enum.Mode.html) a client sends requests as usual and
+//! writes each request (method, URL and body) and its response (status,
+//! headers and body) to a cassette file. The client's token is replaced with
+//! `<TOKEN>` wherever it appears.
+//!
+//! In [`Mode::Replay`](enum.Mode.html) nothing is sent. Each request must
+//! match the next recorded request exactly, and is answered with the recorded
+//! response. This allows code using the client to be tested deterministically.
+//!
+//! ```rust,no_run
+//! # extern crate digitalocean;
+//! use digitalocean::api::Droplet;
+//! use digitalocean::cassette::Cassette;
+//! use digitalocean::error::Error;
+//! use digitalocean::prelude::*;
+//!
+//! # fn main() -> Result<(), Error> {
+//! let cassette = Cassette::replaying("tests/cassettes/droplets.json")?;
+//! let client = DigitalOcean::new("unused")?.cassette(cassette);
+//!
+//! let droplets = Droplet::list().execute(&client)?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::client::Response;
+use crate::error::Error;
+use log::info;
+use reqwest::{Method, StatusCode};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use url::Url;
+
+const TOKEN_PLACEHOLDER: &str = "<TOKEN>";
+
+/// Whether a cassette records or replays interactions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    Record,
+    Replay,
+}
+
+/// A recorded request and the response it received.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct Interaction {
+    pub request: RecordedRequest,
+    pub response: RecordedResponse,
+}
+
+/// A request as it was sent.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct RecordedRequest {
+    pub method: String,
+    pub url: String,
+    #[serde(default)]
+    pub body: Value,
+}
+
+/// A response as it was received.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct RecordedResponse {
+    pub status: u16,
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+    #[serde(default)]
+    pub body: String,
+}
+
+/// A file of recorded interactions.
+#[derive(Debug)]
+pub struct Cassette {
+    path: PathBuf,
+    mode: Mode,
+    interactions: Mutex<Vec<Interaction>>,
+    // The index of the next interaction to replay.
+    position: Mutex<usize>,
+}
+
+impl Cassette {
+    /// Record interactions to `path`, replacing anything already there. The
+    /// file is rewritten after each request.
+    pub fn recording<P>(path: P) -> Self
+    where
+        P: AsRef<Path>,
+    {
+        Cassette {
+            path: path.as_ref().to_path_buf(),
+            mode: Mode::Record,
+            interactions: Mutex::new(Vec::new()),
+            position: Mutex::new(0),
+        }
+    }
+
+    /// Replay the interactions recorded in `path`.
+    pub fn replaying<P>(path: P) -> Result<Self, Error>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        let contents = fs::read_to_string(path)
+            .map_err(|e| Error::Cassette(format!("{}: {}", path.display(), e)))?;
+        Ok(Cassette::from_interactions(serde_json::from_str(&contents)?).path(path))
+    }
+
+    /// Replay the given interactions.
+    pub fn from_interactions(interactions: Vec<Interaction>) -> Self {
+        Cassette {
+            path: PathBuf::new(),
+            mode: Mode::Replay,
+            interactions: Mutex::new(interactions),
+            position: Mutex::new(0),
+        }
+    }
+
+    fn path(mut self, path: &Path) -> Self {
+        self.path = path.to_path_buf();
+        self
+    }
+
+    pub fn mode(&self) -> Mode {
+        self.mode
+    }
+
+    /// The interactions recorded so far, or being replayed.
+    pub fn interactions(&self) -> Vec<Interaction> {
+        self.interactions
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// Whether every recorded interaction has been replayed.
+    pub fn is_finished(&self) -> bool {
+        let position = *self.position.lock().unwrap_or_else(|e| e.into_inner());
+        position >= self.interactions().len()
+    }
+
+    pub(crate) fn record_interaction(
+        &self,
+        method: &Method,
+        url: &Url,
+        body: Option<&Value>,
+        response: &Response,
+        token: &str,
+    ) -> Result<(), Error> {
+        if self.mode != Mode::Record {
+            return Ok(());
+        }
+        let scrub = |text: &str| {
+            if token.is_empty() {
+                text.to_string()
+            } else {
+                text.replace(token, TOKEN_PLACEHOLDER)
+            }
+        };
+        let body = body.cloned().unwrap_or(Value::Null);
+        let interaction = Interaction {
+            request: RecordedRequest {
+                method: method.to_string(),
+                url: scrub(url.as_str()),
+                body: serde_json::from_str(&scrub(&body.to_string()))?,
+            },
+            response: RecordedResponse {
+                status: response.status.as_u16(),
+                headers: response
+                    .headers
+                    .iter()
+                    .map(|(name, value)| (name.clone(), scrub(value)))
+                    .collect(),
+                body: scrub(&response.body),
+            },
+        };
+
+        let mut interactions = self.interactions.lock().unwrap_or_else(|e| e.into_inner());
+        interactions.push(interaction);
+        info!(
+            "Recording interaction {} to {:?}",
+            interactions.len(),
+            self.path
+        );
+        fs::write(&self.path, serde_json::to_string_pretty(&*interactions)?)
+            .map_err(|e| Error::Cassette(format!("{}: {}", self.path.display(), e)))
+    }
+
+    pub(crate) fn replay_interaction(
+        &self,
+        method: &Method,
+        url: &Url,
+        body: Option<&Value>,
+    ) -> Result<Response, Error> {
+        let interactions = self.interactions.lock().unwrap_or_else(|e| e.into_inner());
+        let mut position = self.position.lock().unwrap_or_else(|e| e.into_inner());
+        let request = RecordedRequest {
+            method: method.to_string(),
+            url: url.to_string(),
+            body: body.cloned().unwrap_or(Value::Null),
+        };
+
+        let interaction = interactions.get(*position).ok_or_else(|| {
+            Error::Cassette(format!(
+                "no recorded interaction left for {} {}",
+                request.method, request.url
+            ))
+        })?;
+        if interaction.request != request {
+            return Err(Error::Cassette(format!(
+                "interaction {} expected {} {} {}, got {} {} {}",
+                *position + 1,
+                interaction.request.method,
+                interaction.request.url,
+                interaction.request.body,
+                request.method,
+                request.url,
+                request.body
+            )));
+        }
+        *position += 1;
+        info!("Replaying interaction {}", *position);
+
+        let status = StatusCode::from_u16(interaction.response.status)
+            .map_err(|e| Error::Cassette(e.to_string()))?;
+        Ok(Response {
+            status,
+            headers: interaction.response.headers.clone(),
+            body: interaction.response.body.clone(),
+        })
+    }
+}
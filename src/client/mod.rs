@@ -1,3 +1,4 @@
 /// Later we can make a different client and implement it as a feature.
 mod reqwest;
 pub use self::reqwest::Client;
+pub use self::reqwest::Response;
@@ -1,13 +1,21 @@
 pub use reqwest::Client;
 
 use crate::api::{HasPagination, HasResponse, HasValue, MAX_PER_PAGE};
+use crate::cassette::Mode;
+use crate::dry_run::DRY_RUN_HEADER;
 use crate::error::Error;
+use crate::instrument::Execution;
+use crate::interceptor::OutgoingRequest;
 use crate::method::{Create, Delete, Get, List, Update};
 use crate::request::Request;
 use crate::DigitalOcean;
 use log::info;
-use reqwest::StatusCode;
-use reqwest::{RequestBuilder, Response};
+use reqwest::{Method, StatusCode};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::time::Instant;
+use url::Url;
 
 impl DigitalOcean {
     pub(crate) fn get<V>(&self, request: Request<Get, V>) -> Result<V, Error>
@@ -15,21 +23,21 @@ impl DigitalOcean {
         V: HasResponse,
     {
         info!("GET {:?}", request.url());
-        let req = self.client.get(request.url().clone());
+        Execution::run("GET", request.url(), |execution| {
+            let response = self.fetch(execution, Method::GET, self.rebase(request.url()), None)?;
 
-        let mut response = self.fetch(req)?;
-
-        match response.status() {
-            // Successes
-            StatusCode::OK => (),
-            // Not Found
-            StatusCode::NOT_FOUND => Err(Error::NotFound)?,
-            // Errors
-            e => Err(Error::UnexpectedStatus(e))?,
-        };
+            match response.status() {
+                // Successes
+                StatusCode::OK => (),
+                // Not Found
+                StatusCode::NOT_FOUND => Err(Error::NotFound)?,
+                // Errors
+                e => Err(Error::UnexpectedStatus(e))?,
+            };
 
-        let deserialized: V::Response = response.json()?;
-        Ok(deserialized.value())
+            let deserialized: V::Response = response.json()?;
+            Ok(deserialized.value())
+        })
     }
 
     pub(crate) fn list<V>(&self, request: Request<List, Vec<V>>) -> Result<Vec<V>, Error>
@@ -38,77 +46,83 @@ impl DigitalOcean {
         <Vec<V> as HasResponse>::Response: HasPagination,
     {
         info!("LIST {:?}", request.url());
-        // This may be a paginated response. We need to buffer.
-        let mut buffer = Vec::new();
-        let mut current_url = request.url().clone();
-
-        match request.method().0 {
-            Some(limit) if limit < MAX_PER_PAGE => {
-                current_url
-                    .query_pairs_mut()
-                    .append_pair("per_page", &limit.to_string());
-            }
-            _ => {
-                current_url
-                    .query_pairs_mut()
-                    .append_pair("per_page", &MAX_PER_PAGE.to_string());
-            }
-        };
+        Execution::run("GET", request.url(), |execution| {
+            // This may be a paginated response. We need to buffer.
+            let mut buffer = Vec::new();
+            let mut current_url = self.rebase(request.url());
 
-        loop {
-            let req = self.client.get(current_url.clone());
-            let mut response = self.fetch(req)?;
-
-            match response.status() {
-                StatusCode::OK => (),
-                // Not Found
-                StatusCode::NOT_FOUND => Err(Error::NotFound)?,
-                // Errors
-                e => Err(Error::UnexpectedStatus(e))?,
+            match request.method().0 {
+                Some(limit) if limit < MAX_PER_PAGE => {
+                    current_url
+                        .query_pairs_mut()
+                        .append_pair("per_page", &limit.to_string());
+                }
+                _ => {
+                    current_url
+                        .query_pairs_mut()
+                        .append_pair("per_page", &MAX_PER_PAGE.to_string());
+                }
             };
 
-            let deserialized: <Vec<V> as HasResponse>::Response = response.json()?;
+            for page in 1.. {
+                execution.page(page);
+                let response = self.fetch(execution, Method::GET, current_url.clone(), None)?;
 
-            let next_page = deserialized.next_page();
-            buffer.extend(deserialized.value());
+                match response.status() {
+                    StatusCode::OK => (),
+                    // Not Found
+                    StatusCode::NOT_FOUND => Err(Error::NotFound)?,
+                    // Errors
+                    e => Err(Error::UnexpectedStatus(e))?,
+                };
 
-            current_url = match next_page {
-                Some(v) => v,
-                None => break,
-            };
+                let deserialized: <Vec<V> as HasResponse>::Response = response.json()?;
 
-            if let Some(limit) = request.method().0 {
-                let buffer_size = buffer.len();
-                let remaining = limit - buffer_size;
-                if buffer_size >= limit {
-                    break;
-                } else if remaining < MAX_PER_PAGE {
-                    current_url
-                        .query_pairs_mut()
-                        .append_pair("per_page", &remaining.to_string());
+                let next_page = deserialized.next_page();
+                buffer.extend(deserialized.value());
+
+                current_url = match next_page {
+                    Some(v) => self.rebase(&v),
+                    None => break,
+                };
+
+                if let Some(limit) = request.method().0 {
+                    let buffer_size = buffer.len();
+                    let remaining = limit - buffer_size;
+                    if buffer_size >= limit {
+                        break;
+                    } else if remaining < MAX_PER_PAGE {
+                        current_url
+                            .query_pairs_mut()
+                            .append_pair("per_page", &remaining.to_string());
+                    }
                 }
+                info!("Fetching next page...")
             }
-            info!("Fetching next page...")
-        }
 
-        Ok(buffer)
+            Ok(buffer)
+        })
     }
 
     // Delete requests do not return content.
     pub(crate) fn delete<V>(&self, request: Request<Delete, V>) -> Result<(), Error> {
         info!("DELETE {:?}", request.url());
-        let req = self.client.delete(request.url().clone());
-
-        let response = self.fetch(req)?;
+        Execution::run("DELETE", request.url(), |execution| {
+            // Some deletions, such as removing Droplets from a Load Balancer, have a body.
+            let body = Some(request.body()).filter(|body| !body.is_null());
+            let response =
+                self.fetch(execution, Method::DELETE, self.rebase(request.url()), body)?;
 
-        match response.status() {
-            // Successes
-            StatusCode::NO_CONTENT => (), // Delete success
-            // Errors
-            e => Err(Error::UnexpectedStatus(e))?,
-        };
+            match response.status() {
+                // Successes
+                StatusCode::NO_CONTENT => (), // Delete success
+                StatusCode::ACCEPTED => (),   // Delete success (async)
+                // Errors
+                e => Err(Error::UnexpectedStatus(e))?,
+            };
 
-        Ok(())
+            Ok(())
+        })
     }
 
     pub(crate) fn post<V>(&self, request: Request<Create, V>) -> Result<V, Error>
@@ -116,26 +130,35 @@ impl DigitalOcean {
         V: HasResponse,
     {
         info!("POST {:?}", request.url());
-        let req = self
-            .client
-            .post(request.url().clone())
-            .json(&request.body().clone());
-
-        let mut response = self.fetch(req)?;
-
-        match response.status() {
-            // Successes
-            StatusCode::CREATED => (),  // Post Success
-            StatusCode::ACCEPTED => (), // Post Success (async)
-            // Errors
-            StatusCode::UNPROCESSABLE_ENTITY => {
-                Err(Error::UnprocessableEntity(response.json()?))?
-            }
-            e => Err(Error::UnexpectedStatus(e))?,
-        };
+        Execution::run("POST", request.url(), |execution| {
+            let response = self.fetch(
+                execution,
+                Method::POST,
+                self.rebase(request.url()),
+                Some(request.body()),
+            )?;
+
+            match response.status() {
+                // Successes
+                StatusCode::OK => (),      // Post Success (no resource created)
+                StatusCode::CREATED => (), // Post Success
+                StatusCode::ACCEPTED => (), // Post Success (async)
+                StatusCode::NO_CONTENT => (), // Post Success (ex: tagging resources)
+                // Errors
+                StatusCode::UNPROCESSABLE_ENTITY => {
+                    Err(Error::UnprocessableEntity(response.json()?))?
+                }
+                e => Err(Error::UnexpectedStatus(e))?,
+            };
 
-        let deserialized: V::Response = response.json()?;
-        Ok(deserialized.value())
+            // Without content only a response of `()` can be produced.
+            let deserialized: V::Response = if response.status() == StatusCode::NO_CONTENT {
+                serde_json::from_value(Value::Null)?
+            } else {
+                response.json()?
+            };
+            Ok(deserialized.value())
+        })
     }
 
     pub(crate) fn put<V>(&self, request: Request<Update, V>) -> Result<V, Error>
@@ -143,31 +166,184 @@ impl DigitalOcean {
         V: HasResponse,
     {
         info!("PUT {:?}", request.url());
-        let req = self
-            .client
-            .put(request.url().clone())
-            .json(&request.body().clone());
+        Execution::run("PUT", request.url(), |execution| {
+            let response = self.fetch(
+                execution,
+                Method::PUT,
+                self.rebase(request.url()),
+                Some(request.body()),
+            )?;
 
-        let mut response = self.fetch(req)?;
+            match response.status() {
+                // Successes
+                StatusCode::OK => (), // Update success
+                // Errors
+                StatusCode::UNPROCESSABLE_ENTITY => {
+                    Err(Error::UnprocessableEntity(response.json()?))?
+                }
+                e => Err(Error::UnexpectedStatus(e))?,
+            };
+
+            let deserialized: V::Response = response.json()?;
+            Ok(deserialized.value())
+        })
+    }
+
+    fn fetch(
+        &self,
+        execution: &Execution,
+        method: Method,
+        url: Url,
+        body: Option<&Value>,
+    ) -> Result<Response, Error> {
+        let mut request = OutgoingRequest::new(method, url, body.cloned());
 
-        match response.status() {
-            // Successes
-            StatusCode::OK => (), // Update success
-            // Errors
-            StatusCode::UNPROCESSABLE_ENTITY => {
-                Err(Error::UnprocessableEntity(response.json()?))?
+        // Only interceptors which saw the request see its response.
+        let mut entered = 0;
+        let mut intercepted = None;
+        for interceptor in &self.interceptors {
+            entered += 1;
+            intercepted = interceptor.on_request(&mut request)?;
+            if intercepted.is_some() {
+                break;
             }
-            e => Err(Error::UnexpectedStatus(e))?,
+        }
+
+        let started = Instant::now();
+        let mut response = match intercepted {
+            Some(response) => response,
+            None => self.send(&request)?,
         };
+        let elapsed = started.elapsed();
+        execution.response(response.status.as_u16(), elapsed);
+
+        for interceptor in self.interceptors[..entered].iter().rev() {
+            interceptor.on_response(&request, &mut response, elapsed)?;
+        }
+        Ok(response)
+    }
 
-        let deserialized: V::Response = response.json()?;
-        Ok(deserialized.value())
+    fn send(&self, request: &OutgoingRequest) -> Result<Response, Error> {
+        let OutgoingRequest {
+            ref method,
+            ref url,
+            ref body,
+            ..
+        } = *request;
+        if let Some(ref journal) = self.journal {
+            if *method != Method::GET {
+                return Ok(journal.record(method, url, body.as_ref()));
+            }
+        }
+        if let Some(ref cache) = self.cache {
+            if *method == Method::GET {
+                if let Some(response) = cache.get(url) {
+                    return Ok(response);
+                }
+            } else {
+                cache.invalidate_url(url);
+            }
+        }
+        let response = self.exchange(request)?;
+        if let Some(ref cache) = self.cache {
+            if *method == Method::GET {
+                cache.insert(url, &response);
+            }
+        }
+        Ok(response)
     }
 
-    fn fetch(&self, dispatch: RequestBuilder) -> Result<Response, Error> {
-        let response = dispatch.bearer_auth(self.token.clone()).send()?;
+    // Replays the request from a cassette, or sends it.
+    fn exchange(&self, request: &OutgoingRequest) -> Result<Response, Error> {
+        let OutgoingRequest {
+            ref method,
+            ref url,
+            ref body,
+            ..
+        } = *request;
+        if let Some(ref cassette) = self.cassette {
+            if cassette.mode() == Mode::Replay {
+                return cassette.replay_interaction(method, url, body.as_ref());
+            }
+        }
+
+        if let Some(ref limit) = self.rate_limit {
+            limit.acquire();
+        }
+        let token = self.token.token()?;
+        let mut dispatch = self
+            .client
+            .request(method.clone(), url.clone())
+            .bearer_auth(&token);
+        for (name, value) in &request.headers {
+            dispatch = dispatch.header(name.as_str(), value.as_str());
+        }
+        if let Some(ref body) = *body {
+            dispatch = dispatch.json(body);
+        }
+        let response = Response::read(dispatch.send()?)?;
 
         info!("Response status: {:?}", response.status());
+        if let Some(ref limit) = self.rate_limit {
+            limit.observe(&response.headers);
+        }
+        if let Some(ref cassette) = self.cassette {
+            cassette.record_interaction(method, url, body.as_ref(), &response, &token)?;
+        }
         Ok(response)
     }
 }
+
+/// A response whose body has been read, so that it can be recorded,
+/// replayed or intercepted.
+#[derive(Debug, Clone)]
+pub struct Response {
+    pub status: StatusCode,
+    /// Header names are lowercase.
+    pub headers: BTreeMap<String, String>,
+    pub body: String,
+}
+
+impl Response {
+    /// A response with no headers, such as one made up by an interceptor.
+    pub fn new<S>(status: StatusCode, body: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Response {
+            status,
+            headers: BTreeMap::new(),
+            body: body.into(),
+        }
+    }
+
+    fn read(mut response: reqwest::Response) -> Result<Self, Error> {
+        let headers = response
+            .headers()
+            .iter()
+            .filter_map(|(name, value)| Some((name.to_string(), value.to_str().ok()?.to_string())))
+            .collect();
+        Ok(Response {
+            status: response.status(),
+            headers,
+            body: response.text()?,
+        })
+    }
+
+    pub fn status(&self) -> StatusCode {
+        self.status
+    }
+
+    pub fn json<T>(&self) -> Result<T, Error>
+    where
+        T: DeserializeOwned,
+    {
+        serde_json::from_str(&self.body).map_err(|e| {
+            if self.headers.contains_key(DRY_RUN_HEADER) {
+                Error::DryRun
+            } else {
+                Error::JsonError(e)
+            }
+        })
+    }
+}
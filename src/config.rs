@@ -0,0 +1,305 @@
+//! Named contexts loaded from a config file or the environment.
+//!
+//! A context holds everything needed to talk to one account: its token, and
+//! optionally a different API endpoint, a default region and default SSH keys.
+//! The layout follows `doctl`'s config, written as TOML:
+//!
+//! ```toml
+//! # The context used when none is chosen.
+//! context = "staging"
+//!
+//! # The `default` context.
+//! access-token = "..."
+//!
+//! [auth-contexts]
+//! # As in doctl, a context may be just a token...
+//! production = "..."
+//!
+//! # ...or a table with further settings.
+//! [auth-contexts.staging]
+//! access-token = "..."
+//! api-url = "https://api.staging.example.com/v2"
+//! region = "nyc3"
+//! ssh-keys = ["3b:16:bf:e4:8b:00:8b:b8:59:8c:a9:d3:f0:19:45:fa"]
+//! ```
+//!
+//! [`Context::from_env()`](struct.Context.html#method.from_env) reads the
+//! file from `$DIGITALOCEAN_CONFIG`, or `digitalocean/config.toml` in
+//! `$XDG_CONFIG_HOME` (`~/.config` by default). The context is chosen with
+//! `DIGITALOCEAN_CONTEXT`, and `DIGITALOCEAN_TOKEN` overrides its token. With
+//! no config file, `DIGITALOCEAN_TOKEN` alone is enough.
+//!
+//! ```rust,no_run
+//! # extern crate digitalocean;
+//! use digitalocean::DigitalOcean;
+//!
+//! # fn main() -> Result<(), digitalocean::error::Error> {
+//! let client = DigitalOcean::from_env()?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! *Note:* This module requires the `config` feature.
+
+use crate::error::Error;
+use crate::DigitalOcean;
+use getset::Getters;
+use std::collections::HashMap;
+use std::env;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use url::Url;
+use url_serde;
+
+/// The name of the context made of the top-level settings.
+pub const DEFAULT_CONTEXT: &str = "default";
+
+/// The contents of a config file.
+#[derive(Deserialize, Serialize, Debug, Clone, Default, Getters)]
+#[serde(rename_all = "kebab-case")]
+#[get = "pub"]
+pub struct Config {
+    /// The context used when none is chosen.
+    #[serde(default)]
+    context: Option<String>,
+    /// The settings of the `default` context.
+    #[serde(flatten)]
+    default: ContextSettings,
+    /// The other contexts, by name.
+    #[serde(default)]
+    auth_contexts: HashMap<String, ContextEntry>,
+}
+
+/// An entry of `auth-contexts`, either a token as in doctl or a table of
+/// settings.
+#[derive(Deserialize, Serialize, Clone)]
+#[serde(untagged)]
+pub enum ContextEntry {
+    Token(String),
+    Settings(ContextSettings),
+}
+
+/// The settings of a single context as written in a config file.
+#[derive(Deserialize, Serialize, Clone, Default)]
+#[serde(rename_all = "kebab-case")]
+pub struct ContextSettings {
+    #[serde(default)]
+    pub access_token: Option<String>,
+    #[serde(default, with = "url_serde")]
+    pub api_url: Option<Url>,
+    #[serde(default)]
+    pub region: Option<String>,
+    #[serde(default)]
+    pub ssh_keys: Vec<String>,
+}
+
+/// A resolved context, ready to create a client from.
+#[derive(Clone, Getters)]
+#[get = "pub"]
+pub struct Context {
+    /// The name of the context.
+    name: String,
+    /// The API token.
+    token: String,
+    /// A different API endpoint to use, if any.
+    api_url: Option<Url>,
+    /// The region to use when none is given.
+    region: Option<String>,
+    /// The SSH keys (IDs or fingerprints) to use when none are given.
+    ssh_keys: Vec<String>,
+}
+
+// Tokens are redacted so that they are never logged.
+const REDACTED: &str = "<redacted>";
+
+impl fmt::Debug for ContextEntry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ContextEntry::Token(_) => f.debug_tuple("Token").field(&REDACTED).finish(),
+            ContextEntry::Settings(settings) => f.debug_tuple("Settings").field(settings).finish(),
+        }
+    }
+}
+
+impl fmt::Debug for ContextSettings {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ContextSettings")
+            .field(
+                "access_token",
+                &self.access_token.as_ref().map(|_| REDACTED),
+            )
+            .field("api_url", &self.api_url)
+            .field("region", &self.region)
+            .field("ssh_keys", &self.ssh_keys)
+            .finish()
+    }
+}
+
+impl fmt::Debug for Context {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Context")
+            .field("name", &self.name)
+            .field("token", &REDACTED)
+            .field("api_url", &self.api_url)
+            .field("region", &self.region)
+            .field("ssh_keys", &self.ssh_keys)
+            .finish()
+    }
+}
+
+impl FromStr for Config {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        toml::from_str(s).map_err(|e| Error::InvalidConfig(e.to_string()))
+    }
+}
+
+impl Config {
+    /// Read a config file.
+    pub fn load<P>(path: P) -> Result<Self, Error>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        let contents = fs::read_to_string(path)
+            .map_err(|e| Error::InvalidConfig(format!("{}: {}", path.display(), e)))?;
+        contents.parse()
+    }
+
+    /// Where the config file is read from by default: `$DIGITALOCEAN_CONFIG`,
+    /// otherwise `digitalocean/config.toml` in `$XDG_CONFIG_HOME` or
+    /// `~/.config`.
+    pub fn default_path() -> Option<PathBuf> {
+        if let Some(path) = env::var_os("DIGITALOCEAN_CONFIG") {
+            return Some(PathBuf::from(path));
+        }
+        let config_home = env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".config")))?;
+        Some(config_home.join("digitalocean").join("config.toml"))
+    }
+
+    /// The names of all contexts, including `default` if it has a token.
+    pub fn names(&self) -> Vec<String> {
+        let mut names = self.auth_contexts.keys().cloned().collect::<Vec<_>>();
+        if self.default.access_token.is_some() {
+            names.push(DEFAULT_CONTEXT.to_string());
+        }
+        names.sort();
+        names
+    }
+
+    /// Resolve a context by name, or the configured `context` if `None`.
+    /// Settings missing from a named context are taken from the top level.
+    pub fn get(&self, name: Option<&str>) -> Result<Context, Error> {
+        self.get_with_token(name, None)
+    }
+
+    /// Resolve a context as in [`get()`](#method.get), overriding its token
+    /// with `token` if given. The context then need not have a token of its
+    /// own.
+    pub fn get_with_token(
+        &self,
+        name: Option<&str>,
+        token: Option<String>,
+    ) -> Result<Context, Error> {
+        let name = name.or(self.context.as_deref()).unwrap_or(DEFAULT_CONTEXT);
+        let settings = match self.auth_contexts.get(name) {
+            Some(ContextEntry::Token(token)) => ContextSettings {
+                access_token: Some(token.clone()),
+                ..Default::default()
+            },
+            Some(ContextEntry::Settings(settings)) => settings.clone(),
+            None if name == DEFAULT_CONTEXT => self.default.clone(),
+            None => return Err(Error::InvalidConfig(format!("no context named {:?}", name))),
+        };
+        let token = token.or(settings.access_token).ok_or_else(|| {
+            Error::InvalidConfig(format!("context {:?} has no access-token", name))
+        })?;
+
+        Ok(Context {
+            name: name.to_string(),
+            token,
+            api_url: settings.api_url.or_else(|| self.default.api_url.clone()),
+            region: settings.region.or_else(|| self.default.region.clone()),
+            ssh_keys: if settings.ssh_keys.is_empty() {
+                self.default.ssh_keys.clone()
+            } else {
+                settings.ssh_keys
+            },
+        })
+    }
+}
+
+impl Context {
+    /// Create a context from a token alone.
+    pub fn new<T>(token: T) -> Self
+    where
+        T: Into<String>,
+    {
+        Context {
+            name: DEFAULT_CONTEXT.to_string(),
+            token: token.into(),
+            api_url: None,
+            region: None,
+            ssh_keys: Vec::new(),
+        }
+    }
+
+    /// Resolve the context chosen by the environment, as described in the
+    /// [module documentation](index.html).
+    pub fn from_env() -> Result<Self, Error> {
+        let name = env::var("DIGITALOCEAN_CONTEXT").ok();
+        let token = env::var("DIGITALOCEAN_TOKEN").ok();
+        Context::resolve(name.as_deref(), token)
+    }
+
+    /// Resolve a context by name from the default config file, overriding its
+    /// token with `token` if given. Without a config file only the `default`
+    /// context exists, and `token` is required.
+    pub fn resolve<T>(name: Option<&str>, token: Option<T>) -> Result<Self, Error>
+    where
+        T: Into<String>,
+    {
+        let token = token.map(Into::into);
+        let path = Config::default_path().filter(|path| path.exists());
+
+        let context = match (path, name, token) {
+            (Some(path), name, token) => Config::load(path)?.get_with_token(name, token)?,
+            (None, None, Some(token)) => Context::new(token),
+            (None, Some(DEFAULT_CONTEXT), Some(token)) => Context::new(token),
+            (None, Some(name), _) => {
+                return Err(Error::InvalidConfig(format!(
+                    "no config file to find context {:?} in",
+                    name
+                )))
+            }
+            (None, None, None) => {
+                return Err(Error::InvalidConfig(String::from(
+                    "no token was given and there is no config file",
+                )))
+            }
+        };
+        Ok(context)
+    }
+}
+
+impl DigitalOcean {
+    /// Create a DigitalOcean client for a context.
+    pub fn from_context(context: &Context) -> Result<Self, Error> {
+        let client = DigitalOcean::new(context.token.clone())?;
+        Ok(match context.api_url {
+            Some(ref url) => client.api_url(url.clone()),
+            None => client,
+        })
+    }
+
+    /// Create a DigitalOcean client for the context chosen by the
+    /// environment. See [`Context::from_env()`](config/struct.Context.html#method.from_env).
+    pub fn from_env() -> Result<Self, Error> {
+        DigitalOcean::from_context(&Context::from_env()?)
+    }
+}
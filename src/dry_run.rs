@@ -0,0 +1,125 @@
+//! Collecting mutations instead of sending them.
+//!
+//! A client in dry-run mode sends `Get` and `List` requests as usual, so that
+//! a job can look at the account and decide what to do, but records `Create`,
+//! `Update` and `Delete` requests in a [`Journal`](struct.Journal.html)
+//! instead of sending them.
+//!
+//! ```rust,no_run
+//! # extern crate digitalocean;
+//! use digitalocean::api::Droplet;
+//! use digitalocean::error::Error;
+//! use digitalocean::prelude::*;
+//!
+//! # fn main() -> Result<(), Error> {
+//! let client = DigitalOcean::new("token")?.dry_run();
+//!
+//! for droplet in Droplet::list_by_tag("stale").execute(&client)? {
+//!     Droplet::delete(*droplet.id()).execute(&client)?;
+//! }
+//!
+//! // Nothing was deleted, but the journal shows what would have been.
+//! print!("{}", client.journal().unwrap());
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! *Note:* Requests which return nothing (such as deletions) succeed as
+//! usual. Requests which return a resource (such as creating a Droplet) fail
+//! with `Error::DryRun` once journaled, as there is no resource to return.
+
+use crate::client::Response;
+use log::info;
+use reqwest::{Method, StatusCode};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fmt::{self, Display};
+use std::sync::Mutex;
+use url::Url;
+use url_serde;
+
+/// Marks responses made up for requests which were not sent.
+pub(crate) const DRY_RUN_HEADER: &str = "x-dry-run";
+
+/// A request which would have been sent.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct JournalEntry {
+    pub method: String,
+    #[serde(with = "url_serde")]
+    pub url: Url,
+    #[serde(default)]
+    pub body: Value,
+}
+
+impl Display for JournalEntry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}", self.method, self.url)?;
+        if !self.body.is_null() {
+            write!(f, " {}", self.body)?;
+        }
+        Ok(())
+    }
+}
+
+/// The requests a client in dry-run mode did not send, in order.
+#[derive(Debug, Default)]
+pub struct Journal {
+    entries: Mutex<Vec<JournalEntry>>,
+}
+
+impl Journal {
+    pub fn entries(&self) -> Vec<JournalEntry> {
+        self.entries
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries().is_empty()
+    }
+
+    /// Remove all entries.
+    pub fn clear(&self) {
+        self.entries
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clear();
+    }
+
+    // Records a request and makes up the response it would have received.
+    pub(crate) fn record(&self, method: &Method, url: &Url, body: Option<&Value>) -> Response {
+        let entry = JournalEntry {
+            method: method.to_string(),
+            url: url.clone(),
+            body: body.cloned().unwrap_or(Value::Null),
+        };
+        info!("Dry run: {}", entry);
+        self.entries
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(entry);
+
+        let status = match *method {
+            Method::DELETE => StatusCode::NO_CONTENT,
+            Method::PUT => StatusCode::OK,
+            _ => StatusCode::ACCEPTED,
+        };
+        let mut headers = BTreeMap::new();
+        headers.insert(DRY_RUN_HEADER.to_string(), String::from("true"));
+        Response {
+            status,
+            headers,
+            body: String::from("null"),
+        }
+    }
+}
+
+impl Display for Journal {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for entry in self.entries() {
+            writeln!(f, "{}", entry)?;
+        }
+        Ok(())
+    }
+}
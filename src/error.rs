@@ -28,4 +28,50 @@ pub enum Error {
     /// `reqwest` to learn more about how to handle these errors.
     #[error("{0}")]
     ReqwestError(#[from] reqwest::Error),
+    /// A response could not be deserialized.
+    #[error("{0}")]
+    JsonError(#[from] serde_json::Error),
+    /// A cassette could not be read or written, or a request did not match the
+    /// recorded interaction.
+    #[error("Cassette error: {0}")]
+    Cassette(String),
+    /// A cache file could not be read.
+    #[error("Cache error: {0}")]
+    Cache(String),
+    /// The request was journaled by a client in dry-run mode, so there is no
+    /// resource to return.
+    #[error("Dry run: the request was not sent so returned nothing")]
+    DryRun,
+    /// A raw HTTP request could not be parsed.
+    #[error("Invalid request: {0}")]
+    InvalidRequest(String),
+    /// The data of a domain record is invalid for its type.
+    #[error("Invalid domain record: {0}")]
+    InvalidDomainRecord(String),
+    /// A zone file could not be parsed.
+    #[error("Invalid zone file: {0}")]
+    InvalidZoneFile(String),
+    /// An action finished with an `errored` status.
+    #[error("Action {0} errored")]
+    ActionErrored(usize),
+    /// Waiting for an action or resource took longer than allowed.
+    #[error("Timed out waiting for {0}")]
+    Timeout(String),
+    /// A resource with the same name already exists with different settings.
+    #[error("Incompatible resource: {0}")]
+    Incompatible(String),
+    /// The request was not sent as another request in its batch failed.
+    #[error("Cancelled: another request in the batch failed")]
+    Cancelled,
+    /// An OAuth request was refused, or a token could not be refreshed.
+    #[error("OAuth error: {0}")]
+    OAuth(String),
+    /// A config file could not be read, or does not contain the chosen context. Only returned
+    /// with the `config` feature.
+    #[error("Invalid config: {0}")]
+    InvalidConfig(String),
+    /// The PEM-formatted inputs of a certificate were found to be invalid when checked locally.
+    /// Only returned with the `certificate-validation` feature.
+    #[error("Invalid certificate: {0}")]
+    InvalidCertificate(String),
 }
@@ -0,0 +1,807 @@
+//! Declarative management of infrastructure.
+//!
+//! A [`Spec`](struct.Spec.html) describes the droplets, volumes, floating IPs,
+//! load balancers, tags and domains which should exist. It can be written by
+//! hand, or deserialized with `serde` from any format. Planning a spec fetches
+//! the current state of the account and produces a [`Plan`](struct.Plan.html)
+//! of typed [`Change`](enum.Change.html)s, which may be inspected or printed
+//! before it is applied.
+//!
+//! ```rust,no_run
+//! # extern crate digitalocean;
+//! # extern crate serde_json;
+//! use digitalocean::infrastructure::Spec;
+//! use digitalocean::DigitalOcean;
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let client = DigitalOcean::new("token")?;
+//! let spec: Spec = serde_json::from_str(r#"{
+//!     "droplets": [
+//!         { "name": "web", "region": "nyc3", "size": "s-1vcpu-1gb", "image": "ubuntu-20-04-x64" }
+//!     ],
+//!     "floating_ips": [{ "droplet": "web" }]
+//! }"#)?;
+//!
+//! let plan = spec.plan(&client)?;
+//! print!("{}", plan);
+//! plan.apply(&client)?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Resources are matched by name (floating IPs by address or by the droplet
+//! they are assigned to). Resources which are not in the spec are never
+//! changed or deleted.
+
+use crate::api::load_balancer_fields::{Algorithm, ForwardingRule, HealthCheck};
+use crate::api::{
+    Action, DesiredRecord, Domain, DomainRecord, Droplet, FloatingIp, LoadBalancer, Readiness,
+    RecordPlan, Tag, Volume,
+};
+use crate::error::Error;
+use crate::request::Executable;
+use crate::DigitalOcean;
+use log::info;
+use std::collections::HashMap;
+use std::fmt::{self, Display};
+use std::net::IpAddr;
+use std::thread;
+use std::time::{Duration, Instant};
+
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(5);
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10 * 60);
+
+/// The desired state of an account.
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+pub struct Spec {
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub droplets: Vec<DropletSpec>,
+    #[serde(default)]
+    pub volumes: Vec<VolumeSpec>,
+    #[serde(default)]
+    pub floating_ips: Vec<FloatingIpSpec>,
+    #[serde(default)]
+    pub load_balancers: Vec<LoadBalancerSpec>,
+    #[serde(default)]
+    pub domains: Vec<DomainSpec>,
+}
+
+/// A Droplet which should exist. A different `size` causes the Droplet to be
+/// resized, which requires it to be powered off briefly.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct DropletSpec {
+    pub name: String,
+    pub region: String,
+    pub size: String,
+    pub image: String,
+    #[serde(default)]
+    pub ssh_keys: Vec<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub backups: bool,
+    #[serde(default)]
+    pub ipv6: bool,
+    #[serde(default)]
+    pub monitoring: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_data: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vpc_uuid: Option<String>,
+}
+
+/// A Block Storage volume which should exist, optionally attached to a
+/// Droplet (by name). A larger `size_gigabytes` causes the volume to be
+/// resized; volumes are never shrunk.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct VolumeSpec {
+    pub name: String,
+    pub region: String,
+    pub size_gigabytes: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub droplet: Option<String>,
+}
+
+/// A floating IP which should be assigned to a Droplet (by name). If `ip` is
+/// given that address is assigned, otherwise a new one is created if the
+/// Droplet has none.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct FloatingIpSpec {
+    pub droplet: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ip: Option<IpAddr>,
+}
+
+/// A Load Balancer which should exist, distributing traffic to Droplets (by
+/// name) or to a tag.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct LoadBalancerSpec {
+    pub name: String,
+    pub region: String,
+    pub forwarding_rules: Vec<ForwardingRule>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub algorithm: Option<Algorithm>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub health_check: Option<HealthCheck>,
+    #[serde(default)]
+    pub droplets: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tag: Option<String>,
+    #[serde(default)]
+    pub redirect_http_to_https: bool,
+}
+
+/// A domain which should exist. When `records` is given the records of the
+/// domain are kept in line with it, as with a
+/// [`RecordPlan`](../api/struct.RecordPlan.html).
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct DomainSpec {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ip_address: Option<IpAddr>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub records: Option<Vec<DesiredRecord>>,
+}
+
+/// The current state of an account, as far as a [`Spec`](struct.Spec.html)
+/// is concerned.
+#[derive(Debug, Clone, Default)]
+pub struct State {
+    pub tags: Vec<Tag>,
+    pub droplets: Vec<Droplet>,
+    pub volumes: Vec<Volume>,
+    pub floating_ips: Vec<FloatingIp>,
+    pub load_balancers: Vec<LoadBalancer>,
+    pub domains: Vec<Domain>,
+    /// The records of each domain in the spec which exists, by domain name.
+    pub domain_records: HashMap<String, Vec<DomainRecord>>,
+}
+
+impl State {
+    /// Fetch the current state using the existing `list()` requests.
+    pub fn fetch(client: &DigitalOcean, spec: &Spec) -> Result<Self, Error> {
+        let domains = Domain::list().execute(client)?;
+        let mut domain_records = HashMap::new();
+        for domain in &spec.domains {
+            let exists = domains.iter().any(|d| d.name() == &domain.name);
+            if exists && domain.records.is_some() {
+                let records = Domain::get(&domain.name).records().execute(client)?;
+                domain_records.insert(domain.name.clone(), records);
+            }
+        }
+
+        Ok(State {
+            tags: Tag::list().execute(client)?,
+            droplets: Droplet::list().execute(client)?,
+            volumes: Volume::list().execute(client)?,
+            floating_ips: FloatingIp::list().execute(client)?,
+            load_balancers: LoadBalancer::list().execute(client)?,
+            domains,
+            domain_records,
+        })
+    }
+}
+
+/// A single change in a [`Plan`](struct.Plan.html).
+#[derive(Debug, Clone)]
+pub enum Change {
+    CreateTag(String),
+    CreateDroplet(DropletSpec),
+    ResizeDroplet {
+        droplet: String,
+        from: String,
+        to: String,
+    },
+    TagDroplet {
+        droplet: String,
+        tag: String,
+    },
+    CreateVolume(VolumeSpec),
+    ResizeVolume {
+        volume: String,
+        region: String,
+        from: usize,
+        to: usize,
+    },
+    DetachVolume {
+        volume: String,
+        region: String,
+        droplet_id: usize,
+    },
+    AttachVolume {
+        volume: String,
+        region: String,
+        droplet: String,
+    },
+    CreateFloatingIp {
+        droplet: String,
+    },
+    AssignFloatingIp {
+        ip: IpAddr,
+        droplet: String,
+    },
+    CreateLoadBalancer(LoadBalancerSpec),
+    UpdateLoadBalancer {
+        id: String,
+        spec: LoadBalancerSpec,
+    },
+    /// Creates the domain, then its records if any are given.
+    CreateDomain(DomainSpec),
+    UpdateDomainRecords(RecordPlan),
+}
+
+impl Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Change::CreateTag(tag) => write!(f, "+ tag {}", tag),
+            Change::CreateDroplet(spec) => write!(
+                f,
+                "+ droplet {} ({}, {}, {})",
+                spec.name, spec.region, spec.size, spec.image
+            ),
+            Change::ResizeDroplet { droplet, from, to } => {
+                write!(f, "~ droplet {} (size {} -> {})", droplet, from, to)
+            }
+            Change::TagDroplet { droplet, tag } => {
+                write!(f, "~ droplet {} (add tag {})", droplet, tag)
+            }
+            Change::CreateVolume(spec) => write!(
+                f,
+                "+ volume {} ({}, {}GiB)",
+                spec.name, spec.region, spec.size_gigabytes
+            ),
+            Change::ResizeVolume {
+                volume, from, to, ..
+            } => {
+                write!(f, "~ volume {} (size {}GiB -> {}GiB)", volume, from, to)
+            }
+            Change::DetachVolume {
+                volume, droplet_id, ..
+            } => {
+                write!(
+                    f,
+                    "~ volume {} (detach from droplet {})",
+                    volume, droplet_id
+                )
+            }
+            Change::AttachVolume {
+                volume, droplet, ..
+            } => {
+                write!(f, "~ volume {} (attach to droplet {})", volume, droplet)
+            }
+            Change::CreateFloatingIp { droplet } => {
+                write!(f, "+ floating ip (assigned to droplet {})", droplet)
+            }
+            Change::AssignFloatingIp { ip, droplet } => {
+                write!(f, "~ floating ip {} (assign to droplet {})", ip, droplet)
+            }
+            Change::CreateLoadBalancer(spec) => {
+                write!(f, "+ load balancer {} ({})", spec.name, spec.region)
+            }
+            Change::UpdateLoadBalancer { spec, .. } => {
+                write!(f, "~ load balancer {} ({})", spec.name, spec.region)
+            }
+            Change::CreateDomain(spec) => {
+                write!(f, "+ domain {}", spec.name)?;
+                for record in spec.records.iter().flatten() {
+                    write!(
+                        f,
+                        "\n    + {} {} {}",
+                        record.name,
+                        record.data.kind(),
+                        record.data.data()
+                    )?;
+                }
+                Ok(())
+            }
+            Change::UpdateDomainRecords(plan) => {
+                write!(f, "~ domain {}", plan.domain())?;
+                for change in plan.changes() {
+                    write!(f, "\n    {}", change)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl Spec {
+    /// Fetch the current state of the account and compute the changes needed
+    /// to match this spec. Nothing is changed until the plan is applied.
+    pub fn plan(&self, client: &DigitalOcean) -> Result<Plan, Error> {
+        let state = State::fetch(client, self)?;
+        Ok(Plan::new(self, &state))
+    }
+}
+
+/// The changes needed to bring an account in line with a
+/// [`Spec`](struct.Spec.html), in the order they will be applied.
+#[derive(Debug, Clone)]
+pub struct Plan {
+    changes: Vec<Change>,
+    droplet_ids: HashMap<String, usize>,
+    // Volume names are only unique within a region.
+    volume_ids: HashMap<(String, String), String>,
+    poll_interval: Duration,
+    timeout: Duration,
+}
+
+impl Plan {
+    /// Compute the changes needed to turn `state` into `spec`.
+    ///
+    /// Changes are ordered so that dependencies are satisfied: tags, then
+    /// Droplets, volumes (created before they are attached), floating IPs,
+    /// Load Balancers and finally domains.
+    pub fn new(spec: &Spec, state: &State) -> Self {
+        let mut changes = Vec::new();
+
+        let droplet_ids = state
+            .droplets
+            .iter()
+            .map(|droplet| (droplet.name().clone(), *droplet.id()))
+            .collect::<HashMap<_, _>>();
+        let volume_ids = state
+            .volumes
+            .iter()
+            .map(|volume| {
+                let key = (volume.name().clone(), volume.region().slug().clone());
+                (key, volume.id().clone())
+            })
+            .collect::<HashMap<_, _>>();
+
+        // Tags must exist before anything is tagged with them.
+        let mut tags = spec.tags.clone();
+        for droplet in &spec.droplets {
+            tags.extend(droplet.tags.iter().cloned());
+        }
+        let mut seen = Vec::new();
+        for tag in tags {
+            if !seen.contains(&tag) && !state.tags.iter().any(|t| t.name() == &tag) {
+                changes.push(Change::CreateTag(tag.clone()));
+            }
+            seen.push(tag);
+        }
+
+        for spec in &spec.droplets {
+            match state.droplets.iter().find(|d| d.name() == &spec.name) {
+                None => changes.push(Change::CreateDroplet(spec.clone())),
+                Some(droplet) => {
+                    if droplet.size_slug() != &spec.size {
+                        changes.push(Change::ResizeDroplet {
+                            droplet: spec.name.clone(),
+                            from: droplet.size_slug().clone(),
+                            to: spec.size.clone(),
+                        });
+                    }
+                    for tag in &spec.tags {
+                        if !droplet.tags().contains(tag) {
+                            changes.push(Change::TagDroplet {
+                                droplet: spec.name.clone(),
+                                tag: tag.clone(),
+                            });
+                        }
+                    }
+                }
+            }
+        }
+
+        for spec in &spec.volumes {
+            let current = state
+                .volumes
+                .iter()
+                .find(|v| v.name() == &spec.name && v.region().slug() == &spec.region);
+            let attached_to = match current {
+                None => {
+                    changes.push(Change::CreateVolume(spec.clone()));
+                    None
+                }
+                Some(volume) => {
+                    let size = *volume.size_gigabytes() as usize;
+                    if size < spec.size_gigabytes {
+                        changes.push(Change::ResizeVolume {
+                            volume: spec.name.clone(),
+                            region: spec.region.clone(),
+                            from: size,
+                            to: spec.size_gigabytes,
+                        });
+                    }
+                    volume.droplet_ids().first().cloned()
+                }
+            };
+            if let Some(ref droplet) = spec.droplet {
+                let desired = droplet_ids.get(droplet).cloned();
+                if attached_to.is_none() || attached_to != desired {
+                    if let Some(droplet_id) = attached_to {
+                        changes.push(Change::DetachVolume {
+                            volume: spec.name.clone(),
+                            region: spec.region.clone(),
+                            droplet_id,
+                        });
+                    }
+                    changes.push(Change::AttachVolume {
+                        volume: spec.name.clone(),
+                        region: spec.region.clone(),
+                        droplet: droplet.clone(),
+                    });
+                }
+            }
+        }
+
+        for spec in &spec.floating_ips {
+            let assigned = |ip: &FloatingIp| {
+                ip.droplet()
+                    .as_ref()
+                    .map_or(false, |d| d.name() == &spec.droplet)
+            };
+            match spec.ip {
+                Some(ip) => {
+                    let current = state.floating_ips.iter().find(|f| *f.ip() == ip);
+                    if !current.map_or(false, assigned) {
+                        changes.push(Change::AssignFloatingIp {
+                            ip,
+                            droplet: spec.droplet.clone(),
+                        });
+                    }
+                }
+                None => {
+                    if !state.floating_ips.iter().any(assigned) {
+                        changes.push(Change::CreateFloatingIp {
+                            droplet: spec.droplet.clone(),
+                        });
+                    }
+                }
+            }
+        }
+
+        for spec in &spec.load_balancers {
+            match state.load_balancers.iter().find(|l| l.name() == &spec.name) {
+                None => changes.push(Change::CreateLoadBalancer(spec.clone())),
+                Some(current) => {
+                    if !load_balancer_matches(current, spec, &state.droplets) {
+                        changes.push(Change::UpdateLoadBalancer {
+                            id: current.id().clone(),
+                            spec: spec.clone(),
+                        });
+                    }
+                }
+            }
+        }
+
+        for spec in &spec.domains {
+            if !state.domains.iter().any(|d| d.name() == &spec.name) {
+                changes.push(Change::CreateDomain(spec.clone()));
+            } else if let Some(ref records) = spec.records {
+                let current = state
+                    .domain_records
+                    .get(&spec.name)
+                    .cloned()
+                    .unwrap_or_default();
+                let plan = RecordPlan::new(&spec.name, current, records.clone());
+                if !plan.is_empty() {
+                    changes.push(Change::UpdateDomainRecords(plan));
+                }
+            }
+        }
+
+        Plan {
+            changes,
+            droplet_ids,
+            volume_ids,
+            poll_interval: DEFAULT_POLL_INTERVAL,
+            timeout: DEFAULT_TIMEOUT,
+        }
+    }
+
+    /// The changes in the order they will be applied.
+    pub fn changes(&self) -> &[Change] {
+        &self.changes
+    }
+
+    /// Whether the account already matches the spec.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// How often to check on Actions and new resources while applying. The
+    /// default is 5 seconds.
+    pub fn poll_interval(mut self, val: Duration) -> Self {
+        self.poll_interval = val;
+        self
+    }
+
+    /// How long to wait for any single Action or resource while applying.
+    /// The default is 10 minutes.
+    pub fn timeout(mut self, val: Duration) -> Self {
+        self.timeout = val;
+        self
+    }
+
+    /// Apply the changes in order, waiting for each to complete, and stopping
+    /// at the first error. Changes before the error will already have been
+    /// made.
+    pub fn apply(&self, client: &DigitalOcean) -> Result<(), Error> {
+        let mut droplet_ids = self.droplet_ids.clone();
+        let mut volume_ids = self.volume_ids.clone();
+        for change in &self.changes {
+            info!("Applying: {}", change);
+            match change {
+                Change::CreateTag(tag) => {
+                    Tag::create(tag).execute(client)?;
+                }
+                Change::CreateDroplet(spec) => {
+                    let mut req =
+                        Droplet::create(&spec.name, &spec.region, &spec.size, &spec.image)
+                            .ssh_keys(spec.ssh_keys.clone())
+                            .tags(spec.tags.clone())
+                            .backups(spec.backups)
+                            .ipv6(spec.ipv6)
+                            .monitoring(spec.monitoring);
+                    if let Some(ref user_data) = spec.user_data {
+                        req = req.user_data(user_data);
+                    }
+                    if let Some(ref vpc_uuid) = spec.vpc_uuid {
+                        req = req.vpc_uuid(vpc_uuid);
+                    }
+                    let droplet = req.execute(client)?;
+                    droplet_ids.insert(spec.name.clone(), *droplet.id());
+                    let readiness = Readiness::new()
+                        .ipv6(spec.ipv6)
+                        .poll_interval(self.poll_interval)
+                        .timeout(self.timeout);
+                    droplet.wait_until_ready(client, &readiness)?;
+                }
+                Change::ResizeDroplet { droplet, to, .. } => {
+                    let id = droplet_id(&droplet_ids, droplet)?;
+                    let powered_on = Droplet::get(id).execute(client)?.status() == "active";
+                    if powered_on {
+                        self.wait_for_action(client, Droplet::get(id).shutdown())?;
+                    }
+                    self.wait_for_action(client, Droplet::get(id).resize(to, false))?;
+                    if powered_on {
+                        self.wait_for_action(client, Droplet::get(id).power(true))?;
+                    }
+                }
+                Change::TagDroplet { droplet, tag } => {
+                    let id = droplet_id(&droplet_ids, droplet)?.to_string();
+                    Tag::get(tag)
+                        .add_resources(vec![(id.as_str(), "droplet")])
+                        .execute(client)?;
+                }
+                Change::CreateVolume(spec) => {
+                    let mut req =
+                        Volume::create(&spec.name, spec.size_gigabytes).region(&spec.region);
+                    if let Some(ref description) = spec.description {
+                        req = req.description(description);
+                    }
+                    let volume = req.execute(client)?;
+                    let key = (spec.name.clone(), spec.region.clone());
+                    volume_ids.insert(key, volume.id().clone());
+                }
+                Change::ResizeVolume {
+                    volume, region, to, ..
+                } => {
+                    let id = volume_id(&volume_ids, volume, region)?;
+                    self.wait_for_action(client, Volume::get(id).resize(*to))?;
+                }
+                Change::DetachVolume {
+                    volume,
+                    region,
+                    droplet_id,
+                } => {
+                    let id = volume_id(&volume_ids, volume, region)?;
+                    self.wait_for_action(client, Volume::get(id).detach(*droplet_id))?;
+                }
+                Change::AttachVolume {
+                    volume,
+                    region,
+                    droplet,
+                } => {
+                    let id = volume_id(&volume_ids, volume, region)?;
+                    let droplet = droplet_id(&droplet_ids, droplet)?;
+                    self.wait_for_action(client, Volume::get(id).attach(droplet))?;
+                }
+                Change::CreateFloatingIp { droplet } => {
+                    let id = droplet_id(&droplet_ids, droplet)?;
+                    let ip = *FloatingIp::for_droplet(id).execute(client)?.ip();
+                    self.wait_for(&format!("floating ip {}", ip), || {
+                        Ok(FloatingIp::get(ip).execute(client)?.droplet().is_some())
+                    })?;
+                }
+                Change::AssignFloatingIp { ip, droplet } => {
+                    let id = droplet_id(&droplet_ids, droplet)?;
+                    self.wait_for_action(client, FloatingIp::get(*ip).assign(id))?;
+                }
+                Change::CreateLoadBalancer(spec) => {
+                    let droplets = spec
+                        .droplets
+                        .iter()
+                        .map(|name| droplet_id(&droplet_ids, name))
+                        .collect::<Result<Vec<_>, _>>()?;
+                    let mut req = LoadBalancer::create(&spec.name, &spec.region)
+                        .redirect_http_to_https(spec.redirect_http_to_https);
+                    for rule in &spec.forwarding_rules {
+                        req = req.forwarding_rule(rule.clone());
+                    }
+                    if let Some(ref algorithm) = spec.algorithm {
+                        req = req.algorithm(algorithm.clone());
+                    }
+                    if let Some(ref check) = spec.health_check {
+                        req = req.health_check(
+                            check.protocol.clone(),
+                            check.port,
+                            Some(check.path.as_str()),
+                            Some(check.check_interval_seconds),
+                            Some(check.response_timeout_seconds),
+                            Some(check.unhealthy_threshold),
+                            Some(check.healthy_threshold),
+                        );
+                    }
+                    req = match spec.tag {
+                        Some(ref tag) => req.tag(tag),
+                        None => req.droplets(droplets),
+                    };
+                    req.execute(client)?;
+                }
+                Change::UpdateLoadBalancer { id, spec } => {
+                    let droplets = spec
+                        .droplets
+                        .iter()
+                        .map(|name| droplet_id(&droplet_ids, name))
+                        .collect::<Result<Vec<_>, _>>()?;
+                    let mut req = LoadBalancer::update(id)
+                        .name(&spec.name)
+                        .region(&spec.region)
+                        .redirect_http_to_https(spec.redirect_http_to_https);
+                    for rule in &spec.forwarding_rules {
+                        req = req.forwarding_rule(rule.clone());
+                    }
+                    if let Some(ref algorithm) = spec.algorithm {
+                        req = req.algorithm(algorithm.clone());
+                    }
+                    if let Some(ref check) = spec.health_check {
+                        req = req.health_check(
+                            check.protocol.clone(),
+                            check.port,
+                            Some(check.path.as_str()),
+                            Some(check.check_interval_seconds),
+                            Some(check.response_timeout_seconds),
+                            Some(check.unhealthy_threshold),
+                            Some(check.healthy_threshold),
+                        );
+                    }
+                    req = match spec.tag {
+                        Some(ref tag) => req.tag(tag),
+                        None => req.droplets(droplets),
+                    };
+                    req.execute(client)?;
+                }
+                Change::CreateDomain(spec) => {
+                    match spec.ip_address {
+                        Some(ip_address) => {
+                            Domain::create(&spec.name, ip_address).execute(client)?
+                        }
+                        None => Domain::create_without_ip(&spec.name).execute(client)?,
+                    };
+                    if let Some(ref records) = spec.records {
+                        Domain::get(&spec.name)
+                            .records()
+                            .plan(client, records.clone())?
+                            .apply(client)?;
+                    }
+                }
+                Change::UpdateDomainRecords(plan) => plan.apply(client)?,
+            }
+        }
+        Ok(())
+    }
+
+    // Polls until `done` returns true, or the timeout elapses.
+    fn wait_for<F>(&self, what: &str, mut done: F) -> Result<(), Error>
+    where
+        F: FnMut() -> Result<bool, Error>,
+    {
+        let start = Instant::now();
+        while !done()? {
+            if start.elapsed() > self.timeout {
+                return Err(Error::Timeout(what.to_string()));
+            }
+            thread::sleep(self.poll_interval);
+        }
+        Ok(())
+    }
+
+    // Executes a request which starts an action, then waits for it to finish.
+    fn wait_for_action<R>(&self, client: &DigitalOcean, request: R) -> Result<(), Error>
+    where
+        R: Executable<Action>,
+    {
+        request
+            .execute(client)?
+            .wait(client, self.poll_interval, self.timeout)?;
+        Ok(())
+    }
+}
+
+impl Display for Plan {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for change in &self.changes {
+            writeln!(f, "{}", change)?;
+        }
+        Ok(())
+    }
+}
+
+fn droplet_id(ids: &HashMap<String, usize>, name: &str) -> Result<usize, Error> {
+    ids.get(name).cloned().ok_or(Error::NotFound)
+}
+
+fn volume_id(
+    ids: &HashMap<(String, String), String>,
+    name: &str,
+    region: &str,
+) -> Result<String, Error> {
+    ids.get(&(name.to_string(), region.to_string()))
+        .cloned()
+        .ok_or(Error::NotFound)
+}
+
+// Whether a Load Balancer already has the attributes of the spec. Attributes
+// which the spec leaves unset are not compared.
+fn load_balancer_matches(
+    current: &LoadBalancer,
+    spec: &LoadBalancerSpec,
+    droplets: &[Droplet],
+) -> bool {
+    let certificate = |id: &Option<String>| id.clone().filter(|id| !id.is_empty());
+    let same_rules = current.forwarding_rules().len() == spec.forwarding_rules.len()
+        && current
+            .forwarding_rules()
+            .iter()
+            .zip(&spec.forwarding_rules)
+            .all(|(a, b)| {
+                a.entry_protocol == b.entry_protocol
+                    && a.entry_port == b.entry_port
+                    && a.target_protocol == b.target_protocol
+                    && a.target_port == b.target_port
+                    && a.tls_passthrough == b.tls_passthrough
+                    && certificate(&a.certificate_id) == certificate(&b.certificate_id)
+            });
+    let same_health_check = spec.health_check.as_ref().map_or(true, |b| {
+        let a = current.health_check();
+        a.protocol == b.protocol
+            && a.port == b.port
+            && a.path == b.path
+            && a.check_interval_seconds == b.check_interval_seconds
+            && a.response_timeout_seconds == b.response_timeout_seconds
+            && a.unhealthy_threshold == b.unhealthy_threshold
+            && a.healthy_threshold == b.healthy_threshold
+    });
+    let same_targets = match spec.tag {
+        Some(ref tag) => current.tag() == tag,
+        None => {
+            let mut names = current
+                .droplet_ids()
+                .iter()
+                .filter_map(|id| droplets.iter().find(|d| d.id() == id))
+                .map(|d| d.name().clone())
+                .collect::<Vec<_>>();
+            let mut desired = spec.droplets.clone();
+            names.sort();
+            desired.sort();
+            names == desired
+        }
+    };
+
+    same_rules
+        && same_health_check
+        && same_targets
+        && spec
+            .algorithm
+            .as_ref()
+            .map_or(true, |a| current.algorithm() == a)
+        && *current.redirect_http_to_https() == spec.redirect_http_to_https
+}
@@ -0,0 +1,163 @@
+//! Spans and metrics around requests, with the `tracing` and `metrics`
+//! features. Without them, everything here does nothing.
+//!
+//! Each execution opens a `digitalocean.request` span with `method`,
+//! `resource`, `path` (the URL path with IDs replaced by `{id}`), `status`
+//! and `page` fields, and an `error` field should it fail. Requests are not
+//! retried, so there is no retry count: an execution which fails is not sent
+//! again.
+//!
+//! Recorded metrics, labelled by `method` and `resource`:
+//!
+//! * `digitalocean_requests_total`: a counter, also labelled by `status`.
+//! * `digitalocean_request_duration_seconds`: a histogram of each HTTP exchange.
+//! * `digitalocean_errors_total`: a counter, also labelled by error `kind`.
+
+use crate::error::Error;
+use std::time::Duration;
+use url::Url;
+
+/// A single execution of a request, which may span several pages.
+pub(crate) struct Execution {
+    #[cfg(feature = "tracing")]
+    span: tracing::span::EnteredSpan,
+    #[cfg(feature = "metrics")]
+    method: &'static str,
+    #[cfg(feature = "metrics")]
+    resource: String,
+}
+
+impl Execution {
+    /// Runs `f` within the execution of a request, recording its outcome.
+    pub(crate) fn run<T, F>(method: &'static str, url: &Url, f: F) -> Result<T, Error>
+    where
+        F: FnOnce(&Execution) -> Result<T, Error>,
+    {
+        let execution = Execution::start(method, url);
+        let result = f(&execution);
+        if let Err(ref error) = result {
+            execution.failed(error);
+        }
+        result
+    }
+
+    #[allow(unused_variables)]
+    fn start(method: &'static str, url: &Url) -> Self {
+        Execution {
+            #[cfg(feature = "tracing")]
+            span: tracing::info_span!(
+                "digitalocean.request",
+                method,
+                resource = resource(url).as_str(),
+                path = path_template(url).as_str(),
+                status = tracing::field::Empty,
+                page = 1,
+                error = tracing::field::Empty,
+            )
+            .entered(),
+            #[cfg(feature = "metrics")]
+            method,
+            #[cfg(feature = "metrics")]
+            resource: resource(url),
+        }
+    }
+
+    /// Records which page of a list is being fetched, counting from 1.
+    #[allow(unused_variables)]
+    pub(crate) fn page(&self, page: usize) {
+        #[cfg(feature = "tracing")]
+        self.span.record("page", page);
+    }
+
+    /// Records a response received within the execution, and how long it
+    /// took to receive.
+    #[allow(unused_variables)]
+    pub(crate) fn response(&self, status: u16, elapsed: Duration) {
+        #[cfg(feature = "tracing")]
+        self.span.record("status", status);
+        #[cfg(feature = "metrics")]
+        {
+            metrics::counter!(
+                "digitalocean_requests_total",
+                "method" => self.method,
+                "resource" => self.resource.clone(),
+                "status" => status.to_string(),
+            )
+            .increment(1);
+            metrics::histogram!(
+                "digitalocean_request_duration_seconds",
+                "method" => self.method,
+                "resource" => self.resource.clone(),
+            )
+            .record(elapsed.as_secs_f64());
+        }
+    }
+
+    #[allow(unused_variables)]
+    fn failed(&self, error: &Error) {
+        #[cfg(feature = "tracing")]
+        self.span.record("error", tracing::field::display(error));
+        #[cfg(feature = "metrics")]
+        metrics::counter!(
+            "digitalocean_errors_total",
+            "method" => self.method,
+            "resource" => self.resource.clone(),
+            "kind" => error_kind(error),
+        )
+        .increment(1);
+    }
+}
+
+// The type of resource a URL refers to, ex: `droplets`.
+#[cfg(any(feature = "tracing", feature = "metrics"))]
+fn resource(url: &Url) -> String {
+    url.path_segments()
+        .and_then(|mut segments| segments.find(|segment| !segment.is_empty() && *segment != "v2"))
+        .unwrap_or("")
+        .to_string()
+}
+
+// The path of a URL with IDs, names of domains and IP addresses replaced, so
+// that requests for different resources of the same type can be grouped.
+#[cfg(feature = "tracing")]
+fn path_template(url: &Url) -> String {
+    url.path()
+        .split('/')
+        .map(|segment| {
+            let is_number = !segment.is_empty() && segment.chars().all(|c| c.is_ascii_digit());
+            let is_uuid =
+                segment.len() == 36 && segment.chars().all(|c| c.is_ascii_hexdigit() || c == '-');
+            if is_number || is_uuid || segment.contains('.') || segment.contains(':') {
+                "{id}"
+            } else {
+                segment
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+#[cfg(feature = "metrics")]
+fn error_kind(error: &Error) -> &'static str {
+    match *error {
+        Error::NotFound => "not_found",
+        Error::Unauthorized => "unauthorized",
+        Error::UnexpectedStatus(_) => "unexpected_status",
+        Error::UnprocessableEntity(_) => "unprocessable_entity",
+        Error::ReqwestError(_) => "http",
+        Error::JsonError(_) => "json",
+        Error::Cassette(_) => "cassette",
+        Error::Cache(_) => "cache",
+        Error::DryRun => "dry_run",
+        Error::InvalidRequest(_) => "invalid_request",
+        Error::InvalidDomainRecord(_) => "invalid_domain_record",
+        Error::InvalidZoneFile(_) => "invalid_zone_file",
+        Error::ActionErrored(_) => "action_errored",
+        Error::Timeout(_) => "timeout",
+        Error::Incompatible(_) => "incompatible",
+        Error::Cancelled => "cancelled",
+        Error::OAuth(_) => "oauth",
+        Error::InvalidConfig(_) => "invalid_config",
+        Error::InvalidCertificate(_) => "invalid_certificate",
+    }
+}
@@ -0,0 +1,122 @@
+//! Observing and altering the requests a client makes.
+//!
+//! Each [`Interceptor`](trait.Interceptor.html) given to a client sees every
+//! request just before it is sent, and the response it receives along with
+//! how long it took. Interceptors may modify either, answer a request
+//! themselves instead of sending it, or fail it with an error.
+//!
+//! Interceptors run in the order they were added before a request, and in
+//! reverse order after it, so the first interceptor added sees the final
+//! response. Requests which are journaled in dry-run mode or replayed from a
+//! cassette pass through interceptors too.
+//!
+//! ```rust,no_run
+//! # extern crate digitalocean;
+//! use digitalocean::error::Error;
+//! use digitalocean::interceptor::{Interceptor, OutgoingRequest, Response};
+//! use digitalocean::prelude::*;
+//! use std::time::Duration;
+//!
+//! struct Audit;
+//!
+//! impl Interceptor for Audit {
+//!     fn on_request(&self, request: &mut OutgoingRequest) -> Result<Option<Response>, Error> {
+//!         request.set_header("x-request-id", "1234");
+//!         Ok(None)
+//!     }
+//!
+//!     fn on_response(
+//!         &self,
+//!         request: &OutgoingRequest,
+//!         response: &mut Response,
+//!         elapsed: Duration,
+//!     ) -> Result<(), Error> {
+//!         if !request.is_read() {
+//!             println!("{} {} {} in {:?}", request.method, request.url, response.status, elapsed);
+//!         }
+//!         Ok(())
+//!     }
+//! }
+//!
+//! # fn main() -> Result<(), Error> {
+//! let client = DigitalOcean::new("token")?.interceptor(Audit);
+//! # Ok(())
+//! # }
+//! ```
+
+pub use crate::client::Response;
+use crate::error::Error;
+use reqwest::Method;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::time::Duration;
+use url::Url;
+
+/// A request about to be sent.
+#[derive(Debug, Clone)]
+pub struct OutgoingRequest {
+    pub method: Method,
+    pub url: Url,
+    /// Headers sent in addition to authorization and content type.
+    pub headers: BTreeMap<String, String>,
+    pub body: Option<Value>,
+    /// Notes interceptors may leave for each other, or for their own
+    /// `on_response`. These are not sent.
+    pub annotations: BTreeMap<String, String>,
+}
+
+impl OutgoingRequest {
+    pub(crate) fn new(method: Method, url: Url, body: Option<Value>) -> Self {
+        OutgoingRequest {
+            method,
+            url,
+            headers: BTreeMap::new(),
+            body,
+            annotations: BTreeMap::new(),
+        }
+    }
+
+    /// Whether the request only reads, rather than mutating anything.
+    pub fn is_read(&self) -> bool {
+        self.method == Method::GET
+    }
+
+    pub fn set_header<K, V>(&mut self, name: K, value: V)
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        self.headers.insert(name.into(), value.into());
+    }
+
+    pub fn annotate<K, V>(&mut self, key: K, value: V)
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        self.annotations.insert(key.into(), value.into());
+    }
+}
+
+/// A hook into every request a client makes.
+///
+/// Both methods do nothing by default.
+pub trait Interceptor: Send + Sync {
+    /// Called before a request is sent. Returning a response sends nothing,
+    /// and skips any interceptors added after this one.
+    fn on_request(&self, request: &mut OutgoingRequest) -> Result<Option<Response>, Error> {
+        let _ = request;
+        Ok(None)
+    }
+
+    /// Called once a response is received, with the time taken to receive it.
+    fn on_response(
+        &self,
+        request: &OutgoingRequest,
+        response: &mut Response,
+        elapsed: Duration,
+    ) -> Result<(), Error> {
+        let _ = (request, response, elapsed);
+        Ok(())
+    }
+}
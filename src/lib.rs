@@ -65,7 +65,7 @@ The crate is founded on a few design considerations:
 * Use static dispatch as much as possible.
 * Only the bare minimum amount of information should be carried around.
 * Allow for easy construction of separate clients (`hyper`, etc.)
-* No caching (yet). (DigitalOcean does not have [ETags](https://en.wikipedia.org/wiki/HTTP_ETag))
+* Caching is opt-in and time based. (DigitalOcean does not have [ETags](https://en.wikipedia.org/wiki/HTTP_ETag))
 
 ## Debugging
 
@@ -75,10 +75,136 @@ This crate uses the [`log`](https://doc.rust-lang.org/log/log/index.html) crate.
 RUST_LOG=digitalocean=debug cargo run
 ```
 
+With the `tracing` feature each request is executed within a `digitalocean.request` span recording
+its method, resource type, path (with IDs replaced by `{id}`), status code, page and any error.
+Requests are never retried, so no retry count is recorded. With the `metrics` feature request
+counts, latencies and errors are recorded through the [`metrics`](https://docs.rs/metrics) facade
+as `digitalocean_requests_total`, `digitalocean_request_duration_seconds` and
+`digitalocean_errors_total`.
+
+## Unknown Fields
+
+DigitalOcean regularly adds new fields to its API. Any field a value (`Droplet`, `Image`, etc.)
+does not know about is kept in its `extra()` map rather than being dropped.
+
+To instead fail whenever an unknown field is returned (for example in CI, to catch schema drift
+early) enable the `strict` feature:
+
+```toml
+[dependencies]
+digitalocean = { version = "*", features = ["strict"] }
+```
+
+## Recording and Replaying
+
+To test code which uses the client without reaching the API, record its interactions once with
+`Cassette::recording(path)` and replay them in CI with `Cassette::replaying(path)`:
+
+```rust,ignore
+let client = DigitalOcean::new(token)?.cassette(Cassette::replaying("tests/cassettes/list.json")?);
+```
+
+Tokens are scrubbed from recordings, and replayed requests must match the recorded ones exactly.
+
+## Ensuring Resources Exist
+
+Scripts which may be re-run can call `.ensure(&client)` instead of `.execute(&client)` on the
+create requests of Droplets, volumes, tags and SSH keys. An existing resource with the same name
+(and region, where relevant) is returned as `Ensured::Found` if its settings match, and is
+otherwise created and returned as `Ensured::Created`:
+
+```rust,ignore
+let droplet = Droplet::create("web-1", "nyc3", "s-1vcpu-1gb", "ubuntu-20-04-x64").ensure(&client)?;
+```
+
+## Waiting for Droplets
+
+A new Droplet has no addresses until it becomes `active`. `droplet.wait_until_ready(&client,
+&Readiness::new())` polls until it is active with a public IPv4 address, then returns it so that
+`public_ipv4()`, `private_ipv4()` and `ipv6()` can be used to connect. It also waits for a private
+IPv4 address only with `Readiness::private_ipv4(true)`, for an IPv6 address only with
+`Readiness::ipv6(true)`, and for SSH to accept connections only with `Readiness::ssh()`.
+
+## Caching
+
+Regions, sizes, distribution images and the account rarely change. A client given a `Cache` with
+`.cache(...)` answers `Region::list()`, `Size::list()`, `Image::distributions()` and `Account::get()`
+from it until a per-resource time-to-live passes, optionally keeping it in a file between runs.
+
+## Dry Runs
+
+A client created with `.dry_run()` still sends `Get` and `List` requests, but collects `Create`,
+`Update` and `Delete` requests in a journal (`client.journal()`) instead of sending them, so a job
+can be checked before it is run for real.
+
+## Interceptors
+
+To add headers, audit mutations or measure API calls, give the client an `Interceptor` with
+`.interceptor(...)`. Interceptors see each request (method, URL, headers and body) before it is
+sent, and its response (status, headers, body and timing) once received. They may modify either,
+answer a request themselves, or fail it.
+
+## Batches
+
+To run many requests of the same type at once, such as resizing every Droplet in a tag, collect
+them into a `Batch`. It executes them on a bounded number of threads and returns their results in
+order, optionally stopping at the first error. Give the client a `RateLimit` to keep all threads
+within DigitalOcean's rate limits:
+
+```rust,ignore
+let client = DigitalOcean::new(token)?.rate_limit(RateLimit::per_minute(250));
+let results = Batch::new(requests).concurrency(8).stop_on_error().execute(&client);
+```
+
+## OAuth
+
+To act on behalf of users, the `oauth` module builds authorization URLs, exchanges codes for
+tokens and refreshes them. A client created with `DigitalOcean::with_token_provider` asks its
+`TokenProvider` for a token before each request, so `RefreshingToken` renews expired tokens
+transparently.
+
+## Contexts
+
+With the `config` feature, clients can be created from named contexts (a token, and optionally an
+API endpoint, default region and default SSH keys) kept in a TOML config file laid out like
+`doctl`'s, at `~/.config/digitalocean/config.toml`:
+
+```toml
+context = "staging"
+
+[auth-contexts]
+production = "..."
+
+[auth-contexts.staging]
+access-token = "..."
+region = "nyc3"
+```
+
+`DigitalOcean::from_env()` uses the context named by `DIGITALOCEAN_CONTEXT` (or the file's
+`context`), with `DIGITALOCEAN_TOKEN` overriding its token. See the `config` module for details.
+
+## Command-Line Tool
+
+A `digitalocean` binary exposing common resources as subcommands is built with the `cli` feature:
+
+```bash
+cargo install digitalocean --features cli
+export DIGITALOCEAN_TOKEN=...
+digitalocean droplet list
+digitalocean --context production --output yaml domain records example.com
+digitalocean droplet action snapshot 3164444 nightly --wait
+```
+
+Output may be a `table` (the default), `json` or `yaml`. Commands which start an action accept
+`--wait` to poll until the action has completed.
+
 ## Development Status
 
 This crate is in a prototype state.
 
+The minimum supported Rust version is 1.63. The optional `tracing` and `metrics` features need the
+newer Rust versions their dependencies require (1.65 and 1.71.1).
+
 Not all endpoints have been fully end-to-end tested on the production DigitalOcean API. It's very
 likely that some endpoints will have parsing errors due to unexpected values returned from the API.
 
@@ -97,16 +223,34 @@ extern crate serde_derive;
 extern crate serde_json;
 
 pub mod api;
+pub mod batch;
+pub mod cache;
+pub mod cassette;
 mod client;
+pub mod dry_run;
+#[cfg(feature = "config")]
+pub mod config;
 pub mod error;
+pub mod infrastructure;
+mod instrument;
+pub mod interceptor;
 pub mod method;
+pub mod oauth;
 pub mod prelude;
+pub mod rate_limit;
 pub mod request;
 
 use crate::api::HasResponse;
+use crate::cache::Cache;
+use crate::cassette::Cassette;
+use crate::dry_run::Journal;
+use crate::interceptor::Interceptor;
 use crate::method::Method;
+use crate::oauth::TokenProvider;
+use crate::rate_limit::RateLimit;
 use crate::request::{Executable, Request};
 use crate::error::Error;
+use std::sync::Arc;
 use url::Url;
 
 const STATIC_URL_ERROR: &str = "Staticly constructed DigitalOcean URL is malformed.";
@@ -119,7 +263,13 @@ lazy_static! {
 #[derive(Clone)]
 pub struct DigitalOcean {
     client: client::Client,
-    token: String,
+    token: Arc<dyn TokenProvider>,
+    api_url: Option<Url>,
+    cassette: Option<Arc<Cassette>>,
+    cache: Option<Arc<Cache>>,
+    journal: Option<Arc<Journal>>,
+    rate_limit: Option<Arc<RateLimit>>,
+    interceptors: Vec<Arc<dyn Interceptor>>,
 }
 
 impl DigitalOcean {
@@ -128,10 +278,112 @@ impl DigitalOcean {
         info!("Created.");
         Ok(DigitalOcean {
             client: client::Client::new(),
-            token: token.into(),
+            token: Arc::new(token.into()),
+            api_url: None,
+            cassette: None,
+            cache: None,
+            journal: None,
+            rate_limit: None,
+            interceptors: Vec::new(),
         })
     }
 
+    /// Create a DigitalOcean client which asks `provider` for a token before
+    /// each request, for example to refresh OAuth tokens.
+    pub fn with_token_provider<P>(provider: P) -> Self
+    where
+        P: TokenProvider + 'static,
+    {
+        info!("Created.");
+        DigitalOcean {
+            client: client::Client::new(),
+            token: Arc::new(provider),
+            api_url: None,
+            cassette: None,
+            cache: None,
+            journal: None,
+            rate_limit: None,
+            interceptors: Vec::new(),
+        }
+    }
+
+    /// Send requests to a different API endpoint, such as a proxy, instead of
+    /// `https://api.digitalocean.com/v2`.
+    pub fn api_url(mut self, url: Url) -> Self {
+        self.api_url = Some(url);
+        self
+    }
+
+    /// Record interactions to, or replay them from, a cassette. See the
+    /// [`cassette`](cassette/index.html) module.
+    pub fn cassette(mut self, cassette: Cassette) -> Self {
+        self.cassette = Some(Arc::new(cassette));
+        self
+    }
+
+    /// The cassette interactions are recorded to or replayed from, if any.
+    /// Clones of a client share its cassette, so a replaying test can check
+    /// `is_finished()` once it is done.
+    pub fn active_cassette(&self) -> Option<&Cassette> {
+        self.cassette.as_deref()
+    }
+
+    /// Answer requests for rarely changing resources from a cache. See the
+    /// [`cache`](cache/index.html) module.
+    pub fn cache(mut self, cache: Cache) -> Self {
+        self.cache = Some(Arc::new(cache));
+        self
+    }
+
+    /// Journal `Create`, `Update` and `Delete` requests instead of sending
+    /// them. See the [`dry_run`](dry_run/index.html) module.
+    pub fn dry_run(mut self) -> Self {
+        self.journal = Some(Arc::new(Journal::default()));
+        self
+    }
+
+    /// Wait before each request so as to stay within `limit`. Clones of a
+    /// client share its limit. See the [`rate_limit`](rate_limit/index.html)
+    /// module.
+    pub fn rate_limit(mut self, limit: RateLimit) -> Self {
+        self.rate_limit = Some(Arc::new(limit));
+        self
+    }
+
+    /// Pass every request and response through `interceptor`, after any
+    /// interceptors already added. See the
+    /// [`interceptor`](interceptor/index.html) module.
+    pub fn interceptor<I>(mut self, interceptor: I) -> Self
+    where
+        I: Interceptor + 'static,
+    {
+        self.interceptors.push(Arc::new(interceptor));
+        self
+    }
+
+    /// The requests which were not sent, if in dry-run mode. Clones of a
+    /// client share its journal.
+    pub fn journal(&self) -> Option<&Journal> {
+        self.journal.as_deref()
+    }
+
+    // Requests are built against `ROOT_URL`, so are moved onto the configured
+    // API endpoint just before they are sent.
+    pub(crate) fn rebase(&self, url: &Url) -> Url {
+        let root = ROOT_URL.as_str();
+        match self.api_url {
+            Some(ref api_url) if url.as_str().starts_with(root) => {
+                let rebased = format!(
+                    "{}{}",
+                    api_url.as_str().trim_end_matches('/'),
+                    &url.as_str()[root.len()..]
+                );
+                Url::parse(&rebased).unwrap_or_else(|_| url.clone())
+            }
+            _ => url.clone(),
+        }
+    }
+
     pub fn execute<A, V>(&self, request: Request<A, V>) -> Result<V, Error>
     where
         A: Method,
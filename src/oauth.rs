@@ -0,0 +1,405 @@
+//! Acting on behalf of users through DigitalOcean's OAuth flow.
+//!
+//! An [`OAuthApp`](struct.OAuthApp.html) builds the URL users are sent to
+//! in order to authorize an application, then exchanges the code they return
+//! with for a [`Token`](struct.Token.html). Tokens expire, so a client
+//! created with a [`RefreshingToken`](struct.RefreshingToken.html) refreshes
+//! them as needed before each request.
+//!
+//! ```rust,no_run
+//! # extern crate digitalocean;
+//! use digitalocean::oauth::{OAuthApp, RefreshingToken, Scope};
+//! use digitalocean::DigitalOcean;
+//!
+//! # fn main() -> Result<(), digitalocean::error::Error> {
+//! let app = OAuthApp::new("client_id", "client_secret", "https://example.com/callback");
+//!
+//! // Send the user here, and check `state` when they return.
+//! let url = app.authorize_url(&[Scope::Read, Scope::Write], "state");
+//!
+//! // Once they return with a code...
+//! let token = app.exchange_code("code")?;
+//! let client = DigitalOcean::with_token_provider(RefreshingToken::new(app, token));
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/oauth-api/)
+
+use crate::error::Error;
+use crate::STATIC_URL_ERROR;
+use chrono::{DateTime, Duration, Utc};
+use getset::Getters;
+use lazy_static::lazy_static;
+use log::info;
+use reqwest::Client;
+use std::fmt::{self, Display};
+use std::sync::{Arc, Mutex, MutexGuard};
+use url::Url;
+
+lazy_static! {
+    static ref OAUTH_URL: Url =
+        Url::parse("https://cloud.digitalocean.com/v1/oauth/").expect(STATIC_URL_ERROR);
+}
+
+/// Tokens are refreshed this long before they expire, so that they do not
+/// expire while a request is in flight.
+const EXPIRY_MARGIN_SECONDS: i64 = 60;
+
+/// Supplies the bearer token of each request a client makes.
+///
+/// The client calls `token()` before every request, so implementations may
+/// refresh or rotate tokens transparently. A plain `String` is a provider of
+/// itself.
+pub trait TokenProvider: Send + Sync {
+    fn token(&self) -> Result<String, Error>;
+}
+
+impl TokenProvider for String {
+    fn token(&self) -> Result<String, Error> {
+        Ok(self.clone())
+    }
+}
+
+/// The access an OAuth token grants.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Scope {
+    /// Read-only access to the account.
+    Read,
+    /// Full access to the account.
+    Write,
+    /// Any other scope, ex: `droplet:read`.
+    Custom(String),
+}
+
+impl Display for Scope {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Scope::Read => write!(f, "read"),
+            Scope::Write => write!(f, "write"),
+            Scope::Custom(scope) => write!(f, "{}", scope),
+        }
+    }
+}
+
+impl<'a> From<&'a str> for Scope {
+    fn from(scope: &'a str) -> Self {
+        match scope {
+            "read" => Scope::Read,
+            "write" => Scope::Write,
+            scope => Scope::Custom(scope.to_string()),
+        }
+    }
+}
+
+/// The user or team a token acts on behalf of.
+#[derive(Deserialize, Serialize, Debug, Clone, Getters)]
+#[get = "pub"]
+pub struct TokenInfo {
+    name: String,
+    email: String,
+    uuid: String,
+}
+
+/// An OAuth access token along with what is needed to refresh it.
+///
+/// Tokens can be serialized in order to be stored between runs.
+#[derive(Deserialize, Serialize, Clone, Getters)]
+#[get = "pub"]
+pub struct Token {
+    /// The bearer token used to authenticate requests.
+    access_token: String,
+    /// Used to obtain a new access token once this one expires.
+    refresh_token: Option<String>,
+    /// When the access token expires, if it does.
+    expires_at: Option<DateTime<Utc>>,
+    /// The scopes the token was granted, separated by spaces.
+    #[serde(default)]
+    scope: String,
+    /// Who the token acts on behalf of.
+    #[serde(default)]
+    info: Option<TokenInfo>,
+}
+
+// The response of the token endpoint.
+#[derive(Deserialize, Debug)]
+struct TokenResponse {
+    access_token: String,
+    refresh_token: Option<String>,
+    expires_in: Option<i64>,
+    #[serde(default)]
+    scope: String,
+    info: Option<TokenInfo>,
+}
+
+#[derive(Deserialize, Debug)]
+struct ErrorResponse {
+    error: String,
+    error_description: Option<String>,
+}
+
+impl Token {
+    /// Restore a token which was stored elsewhere.
+    pub fn new<S>(
+        access_token: S,
+        refresh_token: Option<S>,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> Self
+    where
+        S: Into<String>,
+    {
+        Token {
+            access_token: access_token.into(),
+            refresh_token: refresh_token.map(Into::into),
+            expires_at,
+            scope: String::new(),
+            info: None,
+        }
+    }
+
+    /// The scopes the token was granted.
+    pub fn scopes(&self) -> Vec<Scope> {
+        self.scope.split_whitespace().map(Scope::from).collect()
+    }
+
+    /// Whether the token has expired, or is about to.
+    pub fn is_expired(&self) -> bool {
+        self.expires_at.map_or(false, |expires_at| {
+            expires_at - Duration::seconds(EXPIRY_MARGIN_SECONDS) <= Utc::now()
+        })
+    }
+}
+
+// The access and refresh tokens are redacted so that they are never logged.
+impl fmt::Debug for Token {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let redacted = |token: &str| if token.is_empty() { "" } else { "[redacted]" };
+        f.debug_struct("Token")
+            .field("access_token", &redacted(&self.access_token))
+            .field(
+                "refresh_token",
+                &self.refresh_token.as_deref().map(redacted),
+            )
+            .field("expires_at", &self.expires_at)
+            .field("scope", &self.scope)
+            .field("info", &self.info)
+            .finish()
+    }
+}
+
+/// An application registered with DigitalOcean's OAuth API.
+#[derive(Clone, Getters)]
+pub struct OAuthApp {
+    #[get = "pub"]
+    client_id: String,
+    client_secret: String,
+    #[get = "pub"]
+    redirect_uri: String,
+    // The base of the OAuth endpoints.
+    oauth_url: Url,
+}
+
+// The client secret is left out so that it is never logged.
+impl fmt::Debug for OAuthApp {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("OAuthApp")
+            .field("client_id", &self.client_id)
+            .field("redirect_uri", &self.redirect_uri)
+            .field("oauth_url", &self.oauth_url)
+            .finish()
+    }
+}
+
+impl OAuthApp {
+    pub fn new<S>(client_id: S, client_secret: S, redirect_uri: S) -> Self
+    where
+        S: Into<String>,
+    {
+        OAuthApp {
+            client_id: client_id.into(),
+            client_secret: client_secret.into(),
+            redirect_uri: redirect_uri.into(),
+            oauth_url: OAUTH_URL.clone(),
+        }
+    }
+
+    /// Send OAuth requests to a different endpoint, such as a proxy.
+    pub fn oauth_url(mut self, url: Url) -> Self {
+        self.oauth_url = url;
+        self
+    }
+
+    /// The URL to send users to in order to authorize the application. They
+    /// return to the redirect URI with `code` and `state` query parameters.
+    ///
+    /// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/oauth-api/#authorize)
+    pub fn authorize_url<S>(&self, scopes: &[Scope], state: S) -> Url
+    where
+        S: AsRef<str>,
+    {
+        let scope = scopes
+            .iter()
+            .map(Scope::to_string)
+            .collect::<Vec<_>>()
+            .join(" ");
+        let mut url = self.endpoint("authorize");
+        url.query_pairs_mut()
+            .append_pair("client_id", &self.client_id)
+            .append_pair("redirect_uri", &self.redirect_uri)
+            .append_pair("response_type", "code")
+            .append_pair("scope", &scope)
+            .append_pair("state", state.as_ref());
+        url
+    }
+
+    /// Exchange the code a user returned with for a token.
+    ///
+    /// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/oauth-api/#exchange)
+    pub fn exchange_code<S>(&self, code: S) -> Result<Token, Error>
+    where
+        S: AsRef<str>,
+    {
+        let mut url = self.endpoint("token");
+        url.query_pairs_mut()
+            .append_pair("grant_type", "authorization_code")
+            .append_pair("code", code.as_ref())
+            .append_pair("client_id", &self.client_id)
+            .append_pair("client_secret", &self.client_secret)
+            .append_pair("redirect_uri", &self.redirect_uri);
+        self.request_token(url)
+    }
+
+    /// Obtain a new token with a refresh token. Refresh tokens may only be
+    /// used once, so the new token's refresh token must be kept instead.
+    ///
+    /// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/oauth-api/#refresh)
+    pub fn refresh<S>(&self, refresh_token: S) -> Result<Token, Error>
+    where
+        S: AsRef<str>,
+    {
+        let mut url = self.endpoint("token");
+        url.query_pairs_mut()
+            .append_pair("grant_type", "refresh_token")
+            .append_pair("refresh_token", refresh_token.as_ref());
+        self.request_token(url)
+    }
+
+    /// Revoke a token, either an access or a refresh token.
+    ///
+    /// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/oauth-api/#revoke)
+    pub fn revoke(&self, token: &Token) -> Result<(), Error> {
+        let mut url = self.endpoint("revoke");
+        url.query_pairs_mut()
+            .append_pair("token", &token.access_token);
+        info!("POST {:?}", url.path());
+        let mut response = Client::new()
+            .post(url)
+            .bearer_auth(&token.access_token)
+            .send()?;
+        check(&mut response)
+    }
+
+    fn endpoint(&self, name: &str) -> Url {
+        self.oauth_url.join(name).expect(STATIC_URL_ERROR)
+    }
+
+    fn request_token(&self, url: Url) -> Result<Token, Error> {
+        // The URL contains secrets so only its path is logged.
+        info!("POST {:?}", url.path());
+        let mut response = Client::new().post(url).send()?;
+        check(&mut response)?;
+        let response: TokenResponse = response.json()?;
+
+        Ok(Token {
+            access_token: response.access_token,
+            refresh_token: response.refresh_token,
+            expires_at: response
+                .expires_in
+                .map(|seconds| Utc::now() + Duration::seconds(seconds)),
+            scope: response.scope,
+            info: response.info,
+        })
+    }
+}
+
+// Turns an unsuccessful response into the error it describes.
+fn check(response: &mut reqwest::Response) -> Result<(), Error> {
+    info!("Response status: {:?}", response.status());
+    if response.status().is_success() {
+        return Ok(());
+    }
+    match response.json::<ErrorResponse>() {
+        Ok(ErrorResponse {
+            error,
+            error_description: Some(description),
+        }) => Err(Error::OAuth(format!("{}: {}", error, description))),
+        Ok(ErrorResponse { error, .. }) => Err(Error::OAuth(error)),
+        Err(_) => Err(Error::UnexpectedStatus(response.status())),
+    }
+}
+
+type RefreshCallback = dyn Fn(&Token) + Send + Sync;
+
+/// A token provider which refreshes its token before it expires.
+///
+/// Clones share the same token, so a refresh by one is seen by all.
+#[derive(Clone)]
+pub struct RefreshingToken {
+    app: OAuthApp,
+    token: Arc<Mutex<Token>>,
+    on_refresh: Option<Arc<RefreshCallback>>,
+}
+
+impl RefreshingToken {
+    pub fn new(app: OAuthApp, token: Token) -> Self {
+        RefreshingToken {
+            app,
+            token: Arc::new(Mutex::new(token)),
+            on_refresh: None,
+        }
+    }
+
+    /// Called with each new token, for example to store its refresh token.
+    pub fn on_refresh<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&Token) + Send + Sync + 'static,
+    {
+        self.on_refresh = Some(Arc::new(callback));
+        self
+    }
+
+    /// The current token.
+    pub fn current(&self) -> Token {
+        self.lock().clone()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Token> {
+        // A panic while refreshing leaves the previous token in place.
+        self.token.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl TokenProvider for RefreshingToken {
+    fn token(&self) -> Result<String, Error> {
+        let mut token = self.lock();
+        if token.is_expired() {
+            let refresh_token = token.refresh_token.clone().ok_or_else(|| {
+                Error::OAuth(String::from("token expired and has no refresh token"))
+            })?;
+            info!("Refreshing token.");
+            *token = self.app.refresh(refresh_token)?;
+            if let Some(ref callback) = self.on_refresh {
+                callback(&token);
+            }
+        }
+        Ok(token.access_token.clone())
+    }
+}
+
+impl fmt::Debug for RefreshingToken {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("RefreshingToken")
+            .field("app", &self.app)
+            .finish()
+    }
+}
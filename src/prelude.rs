@@ -1,8 +1,9 @@
 //! Wildcard glob this module to have access to all commonly used items.
 
 pub use crate::api::{
-    Account, Action, Certificate, CustomImage, Domain, DomainRecord, Droplet, FloatingIp, Image,
-    LoadBalancer, Region, Size, Snapshot, SshKey, Tag, Volume,
+    Account, Action, AutoscalePool, Certificate, CustomImage, Domain, DomainRecord, Droplet,
+    FloatingIp, Image, LoadBalancer, OneClick, Region, ReservedIp, ReservedIpv6, Size, Snapshot,
+    SshKey, Tag, Volume,
 };
 pub use crate::request::Executable;
 pub use crate::request::Request;
@@ -0,0 +1,94 @@
+//! Spacing out requests to stay within DigitalOcean's rate limits.
+//!
+//! DigitalOcean allows each token a limited number of requests per minute and
+//! per hour. A client given a [`RateLimit`](struct.RateLimit.html) waits
+//! before each request so as not to exceed it, and pauses until the limit
+//! resets whenever the API reports that none remain. Clones of the client,
+//! such as those used by a [`Batch`](../batch/struct.Batch.html), share its
+//! budget.
+//!
+//! ```rust,no_run
+//! # extern crate digitalocean;
+//! use digitalocean::prelude::*;
+//! use digitalocean::rate_limit::RateLimit;
+//!
+//! # fn main() -> Result<(), digitalocean::error::Error> {
+//! let client = DigitalOcean::new("token")?.rate_limit(RateLimit::per_minute(250));
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#section/Introduction/Rate-Limit)
+
+use log::info;
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+const REMAINING_HEADER: &str = "ratelimit-remaining";
+const RESET_HEADER: &str = "ratelimit-reset";
+
+/// A budget of requests shared by a client and its clones.
+#[derive(Debug)]
+pub struct RateLimit {
+    interval: Duration,
+    // When the next request may be sent.
+    next: Mutex<Instant>,
+}
+
+impl RateLimit {
+    /// Send at most one request per `interval`.
+    pub fn new(interval: Duration) -> Self {
+        RateLimit {
+            interval,
+            next: Mutex::new(Instant::now()),
+        }
+    }
+
+    /// Send at most `requests` requests per minute.
+    pub fn per_minute(requests: u32) -> Self {
+        RateLimit::new(Duration::from_secs(60) / requests.max(1))
+    }
+
+    /// The time allowed between requests.
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    // Blocks until a request may be sent, reserving its slot.
+    pub(crate) fn acquire(&self) {
+        let now = Instant::now();
+        let at = {
+            let mut next = self.next.lock().unwrap_or_else(|e| e.into_inner());
+            let at = (*next).max(now);
+            *next = at + self.interval;
+            at
+        };
+        if at > now {
+            thread::sleep(at - now);
+        }
+    }
+
+    // Pauses all requests until the limit resets if the API reports that no
+    // requests remain.
+    pub(crate) fn observe(&self, headers: &BTreeMap<String, String>) {
+        let remaining = headers
+            .get(REMAINING_HEADER)
+            .and_then(|value| value.parse::<u64>().ok());
+        let reset = headers
+            .get(RESET_HEADER)
+            .and_then(|value| value.parse::<u64>().ok());
+        if let (Some(0), Some(reset)) = (remaining, reset) {
+            let now = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default();
+            let wait = Duration::from_secs(reset)
+                .checked_sub(now)
+                .unwrap_or_default();
+            info!("Rate limit exhausted, pausing for {:?}.", wait);
+            let mut next = self.next.lock().unwrap_or_else(|e| e.into_inner());
+            *next = (*next).max(Instant::now() + wait);
+        }
+    }
+}
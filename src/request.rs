@@ -3,9 +3,9 @@
 //!
 
 use crate::api::{HasPagination, HasResponse};
+use crate::error::Error;
 use crate::method::{Create, Delete, Get, List, Method, Update};
 use crate::DigitalOcean;
-use crate::error::Error;
 use getset::{Getters, MutGetters, Setters};
 use serde_json::Value;
 use std::marker::PhantomData;
@@ -16,6 +16,8 @@ use url_serde;
 pub type AccountRequest<M, V> = Request<M, V>;
 /// A type alias with [`Request<_, Action>`](struct.Request.html) specific functions.
 pub type ActionRequest<M, V> = Request<M, V>;
+/// A type alias with [`Request<_, AutoscalePool>`](struct.Request.html) specific functions.
+pub type AutoscalePoolRequest<M, V> = Request<M, V>;
 /// A type alias with [`Request<_, Certificate>`](struct.Request.html) specific functions.
 pub type CertificateRequest<M, V> = Request<M, V>;
 /// A type alias with [`Request<_, DomainRecord>`](struct.Request.html) specific functions.
@@ -38,8 +40,18 @@ pub type ImageRequest<M, V> = Request<M, V>;
 pub type CustomImageRequest<M, V> = Request<M, V>;
 /// A type alias with [`Request<_, LoadBalancer>`](struct.Request.html) specific functions.
 pub type LoadBalancerRequest<M, V> = Request<M, V>;
+/// A type alias with [`Request<_, OneClick>`](struct.Request.html) specific functions.
+pub type OneClickRequest<M, V> = Request<M, V>;
 /// A type alias with [`Request<_, Region>`](struct.Request.html) specific functions.
 pub type RegionRequest<M, V> = Request<M, V>;
+/// A type alias with [`Request<_, ReservedIpAction>`](struct.Request.html) specific functions.
+pub type ReservedIpActionRequest<M, V> = Request<M, V>;
+/// A type alias with [`Request<_, ReservedIp>`](struct.Request.html) specific functions.
+pub type ReservedIpRequest<M, V> = Request<M, V>;
+/// A type alias with [`Request<_, ReservedIpv6Action>`](struct.Request.html) specific functions.
+pub type ReservedIpv6ActionRequest<M, V> = Request<M, V>;
+/// A type alias with [`Request<_, ReservedIpv6>`](struct.Request.html) specific functions.
+pub type ReservedIpv6Request<M, V> = Request<M, V>;
 /// A type alias with [`Request<_, Size>`](struct.Request.html) specific functions.
 pub type SizeRequest<M, V> = Request<M, V>;
 /// A type alias with [`Request<_, Snapshot>`](struct.Request.html) specific functions.
@@ -101,6 +113,131 @@ where
     }
 }
 
+impl<A, V> Request<A, V>
+where
+    A: Method,
+{
+    /// Render the request as a `curl` command which can be run to reproduce
+    /// it.
+    ///
+    /// If `token` is `None` the command reads the token from the
+    /// `DIGITALOCEAN_TOKEN` environment variable instead, so it can be shared
+    /// without the token.
+    ///
+    /// *Note:* `List` requests are rendered as their first page, without the
+    /// `per_page` parameter the client adds.
+    pub fn to_curl(&self, token: Option<&str>) -> String {
+        let mut command = format!("curl -X {} {}", A::HTTP_METHOD, quote(self.url.as_str()));
+        match token {
+            Some(token) => {
+                command += &format!(" -H {}", quote(&format!("Authorization: Bearer {}", token)))
+            }
+            None => command += " -H \"Authorization: Bearer $DIGITALOCEAN_TOKEN\"",
+        }
+        if !self.body.is_null() {
+            command += &format!(
+                " -H {} -d {}",
+                quote("Content-Type: application/json"),
+                quote(&self.body.to_string())
+            );
+        }
+        command
+    }
+
+    /// Render the request as raw HTTP/1.1, as it would be sent.
+    ///
+    /// If `token` is `None` the token is redacted.
+    pub fn to_http(&self, token: Option<&str>) -> String {
+        let mut path = self.url.path().to_string();
+        if let Some(query) = self.url.query() {
+            path += "?";
+            path += query;
+        }
+        let mut http = format!(
+            "{} {} HTTP/1.1\r\nHost: {}\r\nAuthorization: Bearer {}\r\n",
+            A::HTTP_METHOD,
+            path,
+            self.url.host_str().unwrap_or_default(),
+            token.unwrap_or(REDACTED)
+        );
+        if self.body.is_null() {
+            http += "\r\n";
+        } else {
+            let body = self.body.to_string();
+            http += &format!(
+                "Content-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+                body.len(),
+                body
+            );
+        }
+        http
+    }
+
+    /// Parse a raw HTTP/1.1 request, such as one from
+    /// [`to_http()`](#method.to_http), back into a request.
+    ///
+    /// Its method must match `A`. Headers other than `Host` are ignored, and
+    /// the URL is assumed to use HTTPS.
+    pub fn from_http(raw: &str) -> Result<Self, Error> {
+        let invalid = |reason: &str| Error::InvalidRequest(reason.to_string());
+        let (head, body) = match raw.find("\r\n\r\n") {
+            Some(end) => (&raw[..end], &raw[end + 4..]),
+            None => match raw.find("\n\n") {
+                Some(end) => (&raw[..end], &raw[end + 2..]),
+                None => (raw, ""),
+            },
+        };
+        let mut lines = head.lines().map(str::trim_end);
+
+        let request_line = lines.next().ok_or_else(|| invalid("empty request"))?;
+        let mut parts = request_line.split_whitespace();
+        let (method, target) = match (parts.next(), parts.next()) {
+            (Some(method), Some(target)) => (method, target),
+            _ => return Err(invalid("malformed request line")),
+        };
+        if method != A::HTTP_METHOD {
+            return Err(Error::InvalidRequest(format!(
+                "expected a {} request, got {}",
+                A::HTTP_METHOD,
+                method
+            )));
+        }
+
+        let host = lines
+            .filter_map(|line| {
+                let mut header = line.splitn(2, ':');
+                match (header.next(), header.next()) {
+                    (Some(name), Some(value)) if name.trim().eq_ignore_ascii_case("host") => {
+                        Some(value.trim())
+                    }
+                    _ => None,
+                }
+            })
+            .next();
+        let url = match Url::parse(target) {
+            Ok(url) => url,
+            Err(_) => {
+                let host = host.ok_or_else(|| invalid("missing Host header"))?;
+                Url::parse(&format!("https://{}{}", host, target))
+                    .map_err(|e| Error::InvalidRequest(e.to_string()))?
+            }
+        };
+
+        let mut request = Request::new(url);
+        if !body.trim().is_empty() {
+            request.body = serde_json::from_str(body)?;
+        }
+        Ok(request)
+    }
+}
+
+const REDACTED: &str = "<REDACTED>";
+
+// Quotes a value for a POSIX shell.
+fn quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
+
 impl<V> Request<List, V> {
     /// Impose a limit on the number of values which may be retrieved from a request.
     pub fn limit(mut self, limit: Option<usize>) -> Self {
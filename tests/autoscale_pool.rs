@@ -0,0 +1,182 @@
+extern crate digitalocean;
+#[macro_use]
+extern crate log;
+#[macro_use]
+extern crate serde_json;
+extern crate url;
+extern crate url_serde;
+
+mod utils;
+
+use serde_json::Value;
+
+use digitalocean::api::autoscale_pool_fields::{AutoscaleConfig, DropletTemplate};
+use digitalocean::api::{AutoscalePool, AutoscalePoolHistoryEvent, AutoscalePoolMember};
+use digitalocean::method::{Create, Delete, Get, List, Update};
+use digitalocean::request::Request;
+
+use crate::utils::before;
+
+#[test]
+fn list_produces_correct_request() {
+    before();
+
+    let correct_url = "https://api.digitalocean.com/v2/droplets/autoscale";
+
+    let req: Request<List, Vec<AutoscalePool>> = AutoscalePool::list();
+    info!("{:#?}", req);
+
+    assert_eq!(req.url().as_str(), correct_url);
+    assert_eq!(*req.body(), Value::Null);
+}
+
+#[test]
+fn get_produces_correct_request() {
+    before();
+
+    let pool_id = "0d3db13e-a604-4944-9827-7ec2642d32ac";
+    let correct_url = format!(
+        "https://api.digitalocean.com/v2/droplets/autoscale/{}",
+        pool_id
+    );
+
+    let req: Request<Get, AutoscalePool> = AutoscalePool::get(pool_id);
+    info!("{:#?}", req);
+
+    assert_eq!(req.url().as_str(), correct_url);
+    assert_eq!(*req.body(), Value::Null);
+}
+
+#[test]
+fn create_produces_correct_request() {
+    before();
+
+    let correct_url = "https://api.digitalocean.com/v2/droplets/autoscale";
+    let (name, region, size, image) = ("web", "tor1", "s-1vcpu-1gb", "ubuntu-20-04-x64");
+    let (ssh_keys, tags) = (vec![123, 456], vec![String::from("web")]);
+
+    let config = AutoscaleConfig::dynamic(2, 10)
+        .target_cpu_utilization(0.6)
+        .cooldown_minutes(5);
+    let vpc_uuid = "760e09ef-dc84-11e8-981e-3cfdfeaae000";
+    let template = DropletTemplate::new(region, size, image)
+        .ssh_keys(ssh_keys.clone())
+        .tags(tags.clone())
+        .ipv6(true)
+        .vpc_uuid(vpc_uuid)
+        .with_droplet_agent(true);
+
+    let req: Request<Create, AutoscalePool> = AutoscalePool::create(name, config, template);
+    info!("{:#?}", req);
+
+    assert_eq!(req.url().as_str(), correct_url);
+    assert_eq!(
+        *req.body(),
+        json!({
+            "name": name,
+            "config": {
+                "min_instances": 2,
+                "max_instances": 10,
+                "target_cpu_utilization": 0.6,
+                "cooldown_minutes": 5,
+            },
+            "droplet_template": {
+                "region": region,
+                "size": size,
+                "image": image,
+                "ssh_keys": ["123", "456"],
+                "tags": tags,
+                "ipv6": true,
+                "vpc_uuid": vpc_uuid,
+                "with_droplet_agent": true,
+            },
+        })
+    );
+}
+
+#[test]
+fn update_produces_correct_request() {
+    before();
+
+    let pool_id = "0d3db13e-a604-4944-9827-7ec2642d32ac";
+    let correct_url = format!(
+        "https://api.digitalocean.com/v2/droplets/autoscale/{}",
+        pool_id
+    );
+    let (name, region, size, image) = ("web", "tor1", "s-1vcpu-1gb", "ubuntu-20-04-x64");
+
+    let config = AutoscaleConfig::fixed(3);
+    let template = DropletTemplate::new(region, size, image).ssh_keys(vec!["test"]);
+
+    let req: Request<Update, AutoscalePool> =
+        AutoscalePool::update(pool_id, name, config, template);
+    info!("{:#?}", req);
+
+    assert_eq!(req.url().as_str(), correct_url);
+    assert_eq!(
+        *req.body(),
+        json!({
+            "name": name,
+            "config": {
+                "target_number_instances": 3,
+            },
+            "droplet_template": {
+                "region": region,
+                "size": size,
+                "image": image,
+                "ssh_keys": ["test"],
+            },
+        })
+    );
+}
+
+#[test]
+fn delete_produces_correct_request() {
+    before();
+
+    let pool_id = "0d3db13e-a604-4944-9827-7ec2642d32ac";
+    let correct_url = format!(
+        "https://api.digitalocean.com/v2/droplets/autoscale/{}",
+        pool_id
+    );
+
+    let req: Request<Delete, ()> = AutoscalePool::delete(pool_id);
+    info!("{:#?}", req);
+
+    assert_eq!(req.url().as_str(), correct_url);
+    assert_eq!(*req.body(), Value::Null);
+}
+
+#[test]
+fn members_produces_correct_request() {
+    before();
+
+    let pool_id = "0d3db13e-a604-4944-9827-7ec2642d32ac";
+    let correct_url = format!(
+        "https://api.digitalocean.com/v2/droplets/autoscale/{}/members",
+        pool_id
+    );
+
+    let req: Request<List, Vec<AutoscalePoolMember>> = AutoscalePool::get(pool_id).members();
+    info!("{:#?}", req);
+
+    assert_eq!(req.url().as_str(), correct_url);
+    assert_eq!(*req.body(), Value::Null);
+}
+
+#[test]
+fn history_produces_correct_request() {
+    before();
+
+    let pool_id = "0d3db13e-a604-4944-9827-7ec2642d32ac";
+    let correct_url = format!(
+        "https://api.digitalocean.com/v2/droplets/autoscale/{}/history",
+        pool_id
+    );
+
+    let req: Request<List, Vec<AutoscalePoolHistoryEvent>> = AutoscalePool::get(pool_id).history();
+    info!("{:#?}", req);
+
+    assert_eq!(req.url().as_str(), correct_url);
+    assert_eq!(*req.body(), Value::Null);
+}
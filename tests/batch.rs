@@ -0,0 +1,80 @@
+extern crate digitalocean;
+#[macro_use]
+extern crate log;
+#[macro_use]
+extern crate serde_json;
+
+mod utils;
+
+use digitalocean::api::Droplet;
+use digitalocean::batch::Batch;
+use digitalocean::cassette::Cassette;
+use digitalocean::error::Error;
+use digitalocean::prelude::*;
+
+use crate::utils::before;
+
+#[test]
+fn batch_executes_every_request() {
+    before();
+
+    let client = DigitalOcean::new("unused").unwrap().dry_run();
+    let ids = (1..=20).collect::<Vec<usize>>();
+
+    let results = Batch::new(ids.iter().map(|&id| Droplet::delete(id)))
+        .concurrency(5)
+        .execute(&client);
+    info!("{:#?}", results);
+
+    assert_eq!(results.len(), ids.len());
+    assert!(results.iter().all(Result::is_ok));
+
+    let mut urls = client
+        .journal()
+        .unwrap()
+        .entries()
+        .into_iter()
+        .map(|entry| entry.url.to_string())
+        .collect::<Vec<_>>();
+    urls.sort();
+    let mut expected = ids
+        .iter()
+        .map(|id| format!("https://api.digitalocean.com/v2/droplets/{}", id))
+        .collect::<Vec<_>>();
+    expected.sort();
+    assert_eq!(urls, expected);
+}
+
+#[test]
+fn batch_stops_on_error() {
+    before();
+
+    let response = |id: usize, status: u16| {
+        json!({
+            "request": {
+                "method": "DELETE",
+                "url": format!("https://api.digitalocean.com/v2/droplets/{}", id),
+            },
+            "response": { "status": status },
+        })
+    };
+    let cassette = Cassette::from_interactions(
+        serde_json::from_value(json!([response(1, 204), response(2, 404)])).unwrap(),
+    );
+    let client = DigitalOcean::new("unused").unwrap().cassette(cassette);
+
+    let results = Batch::new(vec![
+        Droplet::delete(1),
+        Droplet::delete(2),
+        Droplet::delete(3),
+    ])
+    .concurrency(1)
+    .stop_on_error()
+    .execute(&client);
+    info!("{:#?}", results);
+
+    match &results[..] {
+        [Ok(()), Err(Error::UnexpectedStatus(_)), Err(Error::Cancelled)] => (),
+        results => panic!("Unexpected results: {:?}", results),
+    }
+}
@@ -0,0 +1,103 @@
+extern crate digitalocean;
+#[macro_use]
+extern crate log;
+#[macro_use]
+extern crate serde_json;
+extern crate uuid;
+
+mod utils;
+
+use digitalocean::api::{Account, Region, SshKey};
+use digitalocean::cache::{Cache, CachedResource};
+use digitalocean::error::Error;
+use digitalocean::prelude::*;
+use serde_json::Value;
+use std::fs;
+use std::time::Duration;
+
+use crate::utils::{before, interaction, replay};
+
+const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+fn regions() -> Value {
+    interaction(
+        "GET",
+        "/regions?per_page=200",
+        Value::Null,
+        200,
+        json!({ "regions": [], "links": {}, "meta": { "total": 0 } }),
+    )
+}
+
+fn account() -> Value {
+    interaction(
+        "GET",
+        "/account",
+        Value::Null,
+        200,
+        json!({
+            "account": {
+                "droplet_limit": 25,
+                "floating_ip_limit": 3,
+                "email": "sammy@digitalocean.com",
+                "uuid": "b6fr89dbf6d9156cace5f3c78dc9851d957381ef",
+                "email_verified": true,
+                "status": "active",
+                "status_message": "",
+            }
+        }),
+    )
+}
+
+#[test]
+fn cache_serves_until_invalidated() {
+    before();
+
+    let client = replay(vec![
+        regions(),
+        account(),
+        interaction("DELETE", "/account/keys/1", Value::Null, 204, Value::Null),
+        account(),
+    ])
+    .cache(Cache::new(DAY));
+
+    // Each is only requested once.
+    for _ in 0..2 {
+        let regions = Region::list().execute(&client).unwrap();
+        info!("{:#?}", regions);
+        let account = Account::get().execute(&client).unwrap();
+        assert_eq!(account.email(), "sammy@digitalocean.com");
+    }
+
+    // Changing the account requests it again.
+    SshKey::delete(1).execute(&client).unwrap();
+    Account::get().execute(&client).unwrap();
+    Region::list().execute(&client).unwrap();
+}
+
+#[test]
+fn cache_persists_between_clients() {
+    before();
+
+    let path =
+        std::env::temp_dir().join(format!("digitalocean-cache-{}.json", uuid::Uuid::new_v4()));
+
+    let client = replay(vec![regions()]).cache(Cache::persistent(&path, DAY).unwrap());
+    Region::list().execute(&client).unwrap();
+
+    // Nothing is left to replay, so only the cache can answer.
+    let client = replay(vec![]).cache(Cache::persistent(&path, DAY).unwrap());
+    Region::list().execute(&client).unwrap();
+
+    let client = replay(vec![]).cache(
+        Cache::persistent(&path, DAY)
+            .unwrap()
+            .ttl(CachedResource::Regions, Duration::from_secs(0)),
+    );
+    match Region::list().execute(&client) {
+        Err(Error::Cassette(_)) => (),
+        result => panic!("Expected the cache to be bypassed, got {:?}", result),
+    }
+
+    fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,153 @@
+extern crate digitalocean;
+#[macro_use]
+extern crate log;
+#[macro_use]
+extern crate serde_json;
+
+mod utils;
+
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpListener;
+use std::thread;
+
+use digitalocean::api::{Action, Droplet};
+use digitalocean::cassette::{Cassette, Interaction};
+use digitalocean::error::Error;
+use digitalocean::prelude::*;
+use url::Url;
+
+use crate::utils::before;
+
+fn action(id: usize, status: &str) -> String {
+    json!({
+        "action": {
+            "id": id,
+            "status": status,
+            "type": "reboot",
+            "started_at": "2020-07-21T18:37:44Z",
+            "completed_at": null,
+            "resource_id": 3164444,
+            "resource_type": "droplet",
+            "region_slug": "nyc3",
+        }
+    })
+    .to_string()
+}
+
+fn interactions() -> Vec<Interaction> {
+    serde_json::from_value(json!([
+        {
+            "request": {
+                "method": "GET",
+                "url": "https://api.digitalocean.com/v2/actions/1",
+            },
+            "response": { "status": 200, "body": action(1, "in-progress") },
+        },
+        {
+            "request": {
+                "method": "POST",
+                "url": "https://api.digitalocean.com/v2/droplets/3164444/actions",
+                "body": { "type": "reboot" },
+            },
+            "response": { "status": 201, "body": action(2, "in-progress") },
+        },
+    ]))
+    .unwrap()
+}
+
+#[test]
+fn replay_serves_recorded_responses() {
+    before();
+
+    let cassette = Cassette::from_interactions(interactions());
+    let client = DigitalOcean::new("unused").unwrap().cassette(cassette);
+
+    let action = Action::get(1).execute(&client).unwrap();
+    info!("{:#?}", action);
+    assert_eq!(*action.id(), 1);
+    assert!(!client.active_cassette().unwrap().is_finished());
+
+    let action = Droplet::get(3164444).reboot().execute(&client).unwrap();
+    assert_eq!(*action.id(), 2);
+    assert_eq!(*action.kind(), "reboot");
+
+    // Every interaction has been used up.
+    assert!(client.active_cassette().unwrap().is_finished());
+    match Action::get(1).execute(&client) {
+        Err(Error::Cassette(_)) => (),
+        result => panic!("Expected a cassette error, got {:?}", result),
+    }
+}
+
+#[test]
+fn replay_rejects_unexpected_requests() {
+    before();
+
+    let cassette = Cassette::from_interactions(interactions());
+    let client = DigitalOcean::new("unused").unwrap().cassette(cassette);
+
+    match Action::get(2).execute(&client) {
+        Err(Error::Cassette(_)) => (),
+        result => panic!("Expected a cassette error, got {:?}", result),
+    }
+}
+
+#[test]
+fn record_scrubs_the_token() {
+    before();
+
+    // A server which answers a single request with an action mentioning the token.
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let server = thread::spawn(move || {
+        let (stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(stream);
+        let mut authorization = String::new();
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            if line.to_ascii_lowercase().starts_with("authorization") {
+                authorization = line.trim().to_string();
+            }
+            if line == "\r\n" {
+                break;
+            }
+        }
+        let body = action(1, "completed").replace("nyc3", "secret-token");
+        let mut stream = reader.into_inner();
+        write!(
+            stream,
+            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            body.len(),
+            body
+        )
+        .unwrap();
+        authorization
+    });
+
+    let path = std::env::temp_dir().join(format!("digitalocean-cassette-{}.json", port));
+    let api_url = Url::parse(&format!("http://127.0.0.1:{}/v2", port)).unwrap();
+    let client = DigitalOcean::new("secret-token")
+        .unwrap()
+        .api_url(api_url)
+        .cassette(Cassette::recording(&path));
+
+    let action = Action::get(1).execute(&client).unwrap();
+    assert_eq!(*action.status(), "completed");
+    assert_eq!(*action.kind(), "reboot");
+    assert_eq!(server.join().unwrap(), "authorization: Bearer secret-token");
+
+    let recorded = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_file(&path).ok();
+    info!("{}", recorded);
+
+    assert!(!recorded.contains("secret-token"));
+    let interactions: Vec<Interaction> = serde_json::from_str(&recorded).unwrap();
+    assert_eq!(interactions.len(), 1);
+    assert_eq!(interactions[0].request.method, "GET");
+    assert_eq!(
+        interactions[0].request.url,
+        format!("http://127.0.0.1:{}/v2/actions/1", port)
+    );
+    assert!(interactions[0].response.body.contains("<TOKEN>"));
+}
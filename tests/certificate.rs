@@ -58,6 +58,51 @@ fn create_produces_correct_request() {
     );
 }
 
+#[test]
+fn lets_encrypt_produces_correct_request() {
+    before();
+
+    let correct_url = "https://api.digitalocean.com/v2/certificates";
+    let name = "web-cert-01";
+    let dns_names = vec!["www.example.com", "example.com"];
+
+    let req: Request<Create, Certificate> = Certificate::lets_encrypt(name, dns_names.clone());
+    info!("{:#?}", req);
+
+    assert_eq!(req.url().as_str(), correct_url);
+    assert_eq!(
+        *req.body(),
+        json!({
+            "name": name,
+            "type": "lets_encrypt",
+            "dns_names": dns_names,
+        })
+    );
+}
+
+#[test]
+fn lets_encrypt_payload_deserializes() {
+    before();
+
+    let payload = json!({
+        "id": "ba9b9c18-6c59-46c2-99df-70da170a42ba",
+        "name": "web-cert-01",
+        "not_after": "2018-06-07T17:44:12Z",
+        "sha1_fingerprint": "479c82b5c63cb6d3e6fac4624d58a33b267e166c",
+        "created_at": "2018-03-09T18:44:11Z",
+        "dns_names": ["www.example.com", "example.com"],
+        "state": "pending",
+        "type": "lets_encrypt",
+    });
+
+    let certificate: Certificate = serde_json::from_value(payload).unwrap();
+    info!("{:#?}", certificate);
+
+    assert_eq!(certificate.kind(), "lets_encrypt");
+    assert_eq!(certificate.state(), "pending");
+    assert_eq!(certificate.dns_names().len(), 2);
+}
+
 #[test]
 fn get_produces_correct_request() {
     before();
@@ -91,3 +136,61 @@ fn delete_produces_correct_request() {
     assert_eq!(req.url().as_str(), correct_url);
     assert_eq!(*req.body(), Value::Null);
 }
+
+#[cfg(feature = "certificate-validation")]
+mod validation {
+    use digitalocean::api::Certificate;
+    use digitalocean::error::Error;
+
+    use crate::utils::before;
+
+    const LEAF: &str = include_str!("fixtures/certificate/leaf.pem");
+    const LEAF_KEY: &str = include_str!("fixtures/certificate/leaf.key");
+    const OTHER_KEY: &str = include_str!("fixtures/certificate/other.key");
+    const CHAIN: &str = include_str!("fixtures/certificate/chain.pem");
+    const INTERMEDIATE: &str = include_str!("fixtures/certificate/intermediate.pem");
+    const ROOT: &str = include_str!("fixtures/certificate/root.pem");
+
+    #[test]
+    fn validate_reports_expiry_and_dns_names() {
+        before();
+
+        let leaf = Certificate::create("test", LEAF_KEY, LEAF)
+            .certificate_chain(CHAIN)
+            .validate()
+            .unwrap();
+        info!("{:#?}", leaf);
+
+        assert_eq!(leaf.not_after().to_rfc3339(), "2126-09-24T23:09:39+00:00");
+        assert_eq!(*leaf.dns_names(), vec!["example.com", "www.example.com"]);
+    }
+
+    #[test]
+    fn validate_rejects_mismatched_key() {
+        before();
+
+        let result = Certificate::create("test", OTHER_KEY, LEAF).validate();
+        info!("{:#?}", result);
+
+        match result {
+            Err(Error::InvalidCertificate(message)) => assert!(message.contains("private_key")),
+            _ => panic!("Expected the private key to be rejected."),
+        }
+    }
+
+    #[test]
+    fn validate_rejects_misordered_chain() {
+        before();
+
+        let chain = format!("{}{}", ROOT, INTERMEDIATE);
+        let result = Certificate::create("test", LEAF_KEY, LEAF)
+            .certificate_chain(chain.as_str())
+            .validate();
+        info!("{:#?}", result);
+
+        match result {
+            Err(Error::InvalidCertificate(message)) => assert!(message.contains("certificate 0")),
+            _ => panic!("Expected the certificate chain to be rejected."),
+        }
+    }
+}
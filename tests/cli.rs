@@ -0,0 +1,180 @@
+#![cfg(feature = "cli")]
+
+extern crate digitalocean;
+#[macro_use]
+extern crate log;
+#[macro_use]
+extern crate serde_json;
+
+mod utils;
+
+use std::fs;
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpListener;
+use std::process::{Command, Output};
+use std::thread;
+
+use serde_json::Value;
+
+use crate::utils::{before, droplet, list};
+
+/// Runs the command-line tool with `args`, against a local server which
+/// answers each request with the next of `responses`. Returns the output of
+/// the tool and the request line of each request it made.
+fn run(args: &[&str], responses: Vec<Value>) -> (Output, Vec<String>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let server = thread::spawn(move || {
+        let mut requests = Vec::new();
+        for response in responses {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut request = String::new();
+            reader.read_line(&mut request).unwrap();
+            requests.push(request.trim().to_string());
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line == "\r\n" {
+                    break;
+                }
+            }
+            let body = response.to_string();
+            let mut stream = reader.into_inner();
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            )
+            .unwrap();
+        }
+        requests
+    });
+
+    let config = std::env::temp_dir().join(format!("digitalocean-cli-{}.toml", port));
+    fs::write(
+        &config,
+        format!("api-url = \"http://127.0.0.1:{}/v2\"\n", port),
+    )
+    .unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_digitalocean"))
+        .args(args)
+        .env("DIGITALOCEAN_CONFIG", &config)
+        .env("DIGITALOCEAN_TOKEN", "unused")
+        .env_remove("DIGITALOCEAN_CONTEXT")
+        .output()
+        .unwrap();
+    info!("{:#?}", output);
+    fs::remove_file(&config).ok();
+
+    let requests = if output.status.success() {
+        server.join().unwrap()
+    } else {
+        Vec::new()
+    };
+    (output, requests)
+}
+
+fn droplets() -> Value {
+    list(
+        "droplets",
+        vec![
+            droplet(1, "web-1", "s-1vcpu-1gb"),
+            droplet(2, "web-2", "s-2vcpu-2gb"),
+        ],
+    )
+}
+
+#[test]
+fn droplets_print_as_a_table() {
+    before();
+
+    let (output, requests) = run(&["droplet", "list"], vec![droplets()]);
+    assert!(output.status.success());
+    assert_eq!(requests, ["GET /v2/droplets?per_page=200 HTTP/1.1"]);
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let lines = stdout.lines().collect::<Vec<_>>();
+    assert_eq!(
+        lines,
+        [
+            "ID  NAME   PUBLIC IPV4  REGION  SIZE         STATUS  TAGS",
+            "1   web-1               nyc3    s-1vcpu-1gb  active",
+            "2   web-2               nyc3    s-2vcpu-2gb  active",
+        ]
+    );
+}
+
+#[test]
+fn droplets_print_as_json() {
+    before();
+
+    let (output, _) = run(&["--output", "json", "droplet", "list"], vec![droplets()]);
+    assert!(output.status.success());
+
+    let printed: Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(printed.as_array().unwrap().len(), 2);
+    assert_eq!(printed[1]["name"], "web-2");
+    assert_eq!(printed[1]["size_slug"], "s-2vcpu-2gb");
+}
+
+#[test]
+fn droplets_print_as_yaml() {
+    before();
+
+    let (output, _) = run(
+        &["-o", "yaml", "droplet", "get", "1"],
+        vec![json!({
+            "droplet": droplet(1, "web-1", "s-1vcpu-1gb"),
+        })],
+    );
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.lines().any(|line| line == "- id: 1"));
+    assert!(stdout.lines().any(|line| line == "  name: web-1"));
+}
+
+#[test]
+fn tag_filters_the_droplets_listed() {
+    before();
+
+    let (output, requests) = run(&["droplet", "list", "--tag", "web"], vec![droplets()]);
+    assert!(output.status.success());
+    assert_eq!(
+        requests,
+        ["GET /v2/droplets?tag_name=web&per_page=200 HTTP/1.1"]
+    );
+}
+
+#[test]
+fn unknown_output_formats_are_rejected() {
+    before();
+
+    let (output, _) = run(&["--output", "xml", "droplet", "list"], vec![]);
+    assert!(!output.status.success());
+
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("unknown output format \"xml\""));
+}
+
+#[test]
+fn missing_arguments_are_rejected() {
+    before();
+
+    let (output, _) = run(&["droplet", "create", "web-1", "--image", "ubuntu"], vec![]);
+    assert!(!output.status.success());
+
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("--size"));
+}
+
+#[test]
+fn ids_must_be_numbers() {
+    before();
+
+    let (output, _) = run(&["droplet", "get", "web-1"], vec![]);
+    assert!(!output.status.success());
+}
@@ -0,0 +1,124 @@
+#![cfg(feature = "config")]
+
+extern crate digitalocean;
+#[macro_use]
+extern crate log;
+
+mod utils;
+
+use digitalocean::config::{Config, Context};
+use digitalocean::error::Error;
+
+use crate::utils::before;
+
+const CONFIG: &str = r#"
+context = "staging"
+access-token = "default-token"
+region = "nyc3"
+ssh-keys = ["1234"]
+
+[auth-contexts]
+production = "production-token"
+
+[auth-contexts.staging]
+access-token = "staging-token"
+api-url = "https://api.staging.example.com/v2"
+region = "ams3"
+"#;
+
+#[test]
+fn config_resolves_named_contexts() {
+    before();
+
+    let config: Config = CONFIG.parse().unwrap();
+    info!("{:#?}", config);
+
+    assert_eq!(config.names(), vec!["default", "production", "staging"]);
+
+    // The configured context is used when none is chosen.
+    let staging = config.get(None).unwrap();
+    assert_eq!(staging.name(), "staging");
+    assert_eq!(staging.token(), "staging-token");
+    assert_eq!(
+        staging.api_url().as_ref().map(|url| url.as_str()),
+        Some("https://api.staging.example.com/v2")
+    );
+    assert_eq!(staging.region().as_ref().map(String::as_str), Some("ams3"));
+    assert_eq!(*staging.ssh_keys(), vec!["1234"]);
+
+    // A doctl style context is just a token, with defaults from the top level.
+    let production = config.get(Some("production")).unwrap();
+    assert_eq!(production.token(), "production-token");
+    assert_eq!(*production.api_url(), None);
+    assert_eq!(
+        production.region().as_ref().map(String::as_str),
+        Some("nyc3")
+    );
+
+    let default = config.get(Some("default")).unwrap();
+    assert_eq!(default.token(), "default-token");
+}
+
+#[test]
+fn tokens_are_redacted_from_debug_output() {
+    before();
+
+    let config: Config = CONFIG.parse().unwrap();
+    let context = config.get(None).unwrap();
+    info!("{:#?}", context);
+
+    let debug = format!("{:?} {:?}", config, context);
+    assert!(!debug.contains("-token"));
+    assert!(debug.contains("<redacted>"));
+    assert!(debug.contains("ams3"));
+}
+
+#[test]
+fn config_rejects_unknown_contexts() {
+    before();
+
+    let config: Config = CONFIG.parse().unwrap();
+
+    match config.get(Some("development")) {
+        Err(Error::InvalidConfig(_)) => (),
+        result => panic!("Expected an error, got {:?}", result),
+    }
+    match "context = [".parse::<Config>() {
+        Err(Error::InvalidConfig(_)) => (),
+        result => panic!("Expected an error, got {:?}", result),
+    }
+}
+
+#[test]
+fn environment_token_fills_in_a_missing_token() {
+    before();
+
+    let path =
+        std::env::temp_dir().join(format!("digitalocean-config-{}.toml", std::process::id()));
+    std::fs::write(
+        &path,
+        "context = \"staging\"\n\n[auth-contexts.staging]\nregion = \"ams3\"\n",
+    )
+    .unwrap();
+    std::env::set_var("DIGITALOCEAN_CONFIG", &path);
+    std::env::set_var("DIGITALOCEAN_TOKEN", "environment-token");
+    std::env::remove_var("DIGITALOCEAN_CONTEXT");
+
+    let context = Context::from_env();
+    std::fs::remove_file(&path).ok();
+    let context = context.unwrap();
+    info!("{:#?}", context);
+
+    assert_eq!(context.name(), "staging");
+    assert_eq!(context.token(), "environment-token");
+    assert_eq!(context.region().as_ref().map(String::as_str), Some("ams3"));
+
+    // Without a token from either, the context cannot be used.
+    let config: Config = "[auth-contexts.staging]\nregion = \"ams3\"\n"
+        .parse()
+        .unwrap();
+    match config.get(Some("staging")) {
+        Err(Error::InvalidConfig(_)) => (),
+        result => panic!("Expected an error, got {:?}", result),
+    }
+}
@@ -52,6 +52,25 @@ fn create_produces_correct_request() {
     );
 }
 
+#[test]
+fn create_without_ip_produces_correct_request() {
+    before();
+
+    let domain = "example.com";
+    let correct_url = "https://api.digitalocean.com/v2/domains";
+
+    let req: Request<Create, Domain> = Domain::create_without_ip(domain);
+    info!("{:#?}", req);
+
+    assert_eq!(req.url().as_str(), correct_url);
+    assert_eq!(
+        *req.body(),
+        json!({
+            "name": domain,
+        })
+    );
+}
+
 #[test]
 fn get_produces_correct_request() {
     before();
@@ -10,7 +10,9 @@ mod utils;
 
 use serde_json::Value;
 
+use digitalocean::api::domain_record_fields::RecordData;
 use digitalocean::api::{Domain, DomainRecord};
+use digitalocean::error::Error;
 use digitalocean::method::{Create, Delete, Get, List, Update};
 use digitalocean::request::Request;
 
@@ -30,6 +32,26 @@ fn list_produces_correct_request() {
     assert_eq!(*req.body(), Value::Null);
 }
 
+#[test]
+fn list_by_name_and_kind_produces_correct_request() {
+    before();
+
+    let domain = "example.com";
+    let correct_url = format!(
+        "https://api.digitalocean.com/v2/domains/{}/records?name=www.example.com&type=A",
+        domain
+    );
+
+    let req: Request<List, Vec<DomainRecord>> = Domain::get(domain)
+        .records()
+        .name("www.example.com")
+        .kind("A");
+    info!("{:#?}", req);
+
+    assert_eq!(req.url().as_str(), correct_url);
+    assert_eq!(*req.body(), Value::Null);
+}
+
 #[test]
 fn create_produces_correct_request() {
     before();
@@ -122,3 +144,128 @@ fn delete_produces_correct_request() {
     assert_eq!(req.url().as_str(), correct_url);
     assert_eq!(*req.body(), Value::Null);
 }
+
+#[test]
+fn create_record_produces_correct_request() {
+    before();
+
+    let domain = "example.com";
+    let correct_url = format!("https://api.digitalocean.com/v2/domains/{}/records", domain);
+    let data = RecordData::SRV {
+        priority: 10,
+        weight: 5,
+        port: 5060,
+        target: String::from("sip.example.com."),
+    };
+
+    let req: Request<Create, DomainRecord> = Domain::get(domain)
+        .records()
+        .create_record("_sip._tcp", data)
+        .unwrap()
+        .ttl(1800);
+    info!("{:#?}", req);
+
+    assert_eq!(req.url().as_str(), correct_url);
+    assert_eq!(
+        *req.body(),
+        json!({
+            "type": "SRV",
+            "name": "_sip._tcp",
+            "data": "sip.example.com.",
+            "priority": 10,
+            "weight": 5,
+            "port": 5060,
+            "ttl": 1800,
+        })
+    );
+}
+
+#[test]
+fn update_record_data_produces_correct_request() {
+    before();
+
+    let domain = "example.com";
+    let record_id = 123;
+    let data = RecordData::CAA {
+        flags: 1,
+        tag: String::from("issue"),
+        value: String::from("letsencrypt.org"),
+    };
+
+    let req: Request<Update, DomainRecord> = Domain::get(domain)
+        .records()
+        .update(record_id)
+        .name("@")
+        .record_data(data)
+        .unwrap();
+    info!("{:#?}", req);
+
+    assert_eq!(
+        *req.body(),
+        json!({
+            "type": "CAA",
+            "name": "@",
+            "data": "letsencrypt.org",
+            "flags": 1,
+            "tag": "issue",
+        })
+    );
+}
+
+#[test]
+fn invalid_record_data_is_rejected() {
+    before();
+
+    let invalid = vec![
+        RecordData::CNAME(String::from("not a host")),
+        RecordData::MX {
+            priority: 70000,
+            host: String::from("mail.example.com."),
+        },
+        RecordData::TXT(String::new()),
+        RecordData::CAA {
+            flags: 0,
+            tag: String::from("bogus"),
+            value: String::from("letsencrypt.org"),
+        },
+    ];
+
+    for data in invalid {
+        let result = Domain::get("example.com")
+            .records()
+            .create_record("www", data);
+        info!("{:#?}", result);
+
+        match result {
+            Err(Error::InvalidDomainRecord(_)) => (),
+            _ => panic!("Expected the record data to be rejected."),
+        }
+    }
+}
+
+#[test]
+fn record_data_from_domain_record() {
+    before();
+
+    let record: DomainRecord = serde_json::from_value(json!({
+        "id": 28448433,
+        "type": "MX",
+        "name": "@",
+        "data": "mail.example.com",
+        "priority": 10,
+        "port": null,
+        "ttl": 1800,
+        "weight": null,
+        "flags": null,
+        "tag": null,
+    }))
+    .unwrap();
+
+    assert_eq!(
+        record.record_data(),
+        Some(RecordData::MX {
+            priority: 10,
+            host: String::from("mail.example.com"),
+        })
+    );
+}
@@ -0,0 +1,121 @@
+extern crate digitalocean;
+#[macro_use]
+extern crate log;
+#[macro_use]
+extern crate serde_json;
+extern crate url;
+extern crate url_serde;
+
+mod utils;
+
+use serde_json::Value;
+
+use digitalocean::api::domain_record_fields::RecordData;
+use digitalocean::api::{DesiredRecord, DomainRecord, RecordChange, RecordPlan};
+
+use crate::utils::before;
+
+fn record(id: usize, kind: &str, name: &str, data: &str, priority: Value) -> DomainRecord {
+    serde_json::from_value(json!({
+        "id": id,
+        "type": kind,
+        "name": name,
+        "data": data,
+        "priority": priority,
+        "port": null,
+        "ttl": 1800,
+        "weight": null,
+    }))
+    .unwrap()
+}
+
+fn current() -> Vec<DomainRecord> {
+    vec![
+        record(1, "SOA", "@", "1800", Value::Null),
+        record(2, "NS", "@", "ns1.digitalocean.com", Value::Null),
+        record(3, "A", "@", "192.0.2.1", Value::Null),
+        record(4, "MX", "@", "mail.example.com", json!(10)),
+        record(5, "A", "www", "192.0.2.1", Value::Null),
+        record(6, "A", "old", "192.0.2.9", Value::Null),
+    ]
+}
+
+#[test]
+fn plan_with_matching_records_is_empty() {
+    before();
+
+    let desired = vec![
+        DesiredRecord::new("@", RecordData::A("192.0.2.1".parse().unwrap())),
+        DesiredRecord::new(
+            "example.com.",
+            RecordData::MX {
+                priority: 10,
+                host: String::from("mail.example.com."),
+            },
+        )
+        .ttl(1800),
+        DesiredRecord::new("www", RecordData::A("192.0.2.1".parse().unwrap())),
+        DesiredRecord::new(
+            "old.example.com.",
+            RecordData::A("192.0.2.9".parse().unwrap()),
+        ),
+    ];
+
+    let plan = RecordPlan::new("example.com", current(), desired);
+    info!("{:#?}", plan);
+
+    assert!(plan.is_empty());
+}
+
+#[test]
+fn plan_produces_correct_changes() {
+    before();
+
+    let desired = vec![
+        DesiredRecord::new("@", RecordData::A("192.0.2.1".parse().unwrap())).ttl(300),
+        DesiredRecord::new(
+            "@",
+            RecordData::MX {
+                priority: 20,
+                host: String::from("mail.example.com."),
+            },
+        ),
+        DesiredRecord::new("www", RecordData::CNAME(String::from("@"))),
+        DesiredRecord::new("api", RecordData::A("192.0.2.2".parse().unwrap())),
+    ];
+
+    let plan = RecordPlan::new("example.com", current(), desired);
+    info!("{:#?}", plan);
+
+    let summary = plan
+        .changes()
+        .iter()
+        .map(|change| match change {
+            RecordChange::Create(desired) => format!("create {}", desired.name),
+            RecordChange::Update { current, .. } => format!("update {}", current.id()),
+            RecordChange::Delete(current) => format!("delete {}", current.id()),
+        })
+        .collect::<Vec<_>>();
+
+    // The A record for `www` conflicts with the new CNAME so is deleted first.
+    assert_eq!(
+        summary,
+        vec![
+            "delete 5",
+            "update 3",
+            "update 4",
+            "create www",
+            "create api",
+            "delete 6",
+        ]
+    );
+    assert_eq!(
+        plan.to_string(),
+        "- www A 192.0.2.1\n\
+         ~ @ A 192.0.2.1 (ttl 1800 -> 300)\n\
+         ~ @ MX mail.example.com. (priority 20)\n\
+         + www CNAME @\n\
+         + api A 192.0.2.2\n\
+         - old A 192.0.2.9\n"
+    );
+}
@@ -9,7 +9,11 @@ extern crate url_serde;
 mod utils;
 
 use serde_json::Value;
+use std::net::Ipv4Addr;
 
+use digitalocean::api::droplet_fields::{
+    BackupPolicy, NetworkKind, NetworkV4, NetworkV6, Networks,
+};
 use digitalocean::api::{Droplet, Snapshot};
 use digitalocean::method::{Create, Delete, Get, List};
 use digitalocean::request::Request;
@@ -45,6 +49,41 @@ fn create_produces_correct_request() {
     );
 }
 
+#[test]
+fn create_with_vpc_and_backup_policy_produces_correct_request() {
+    before();
+
+    let correct_url = "https://api.digitalocean.com/v2/droplets";
+    let (name, region, size, image) = ("bear", "tor1", "5gb", "ubuntu-14-04-x64");
+    let vpc_uuid = "760e09ef-dc84-11e8-981e-3cfdfeaae000";
+
+    let req: Request<Create, Droplet> = Droplet::create(name, region, size, image)
+        .vpc_uuid(vpc_uuid)
+        .with_droplet_agent(true)
+        .backups(true)
+        .backup_policy(BackupPolicy::weekly("SUN").hour(8));
+    info!("{:#?}", req);
+
+    assert_eq!(req.url().as_str(), correct_url);
+    assert_eq!(
+        *req.body(),
+        json!({
+            "name": name,
+            "region": region,
+            "size": size,
+            "image": image,
+            "vpc_uuid": vpc_uuid,
+            "with_droplet_agent": true,
+            "backups": true,
+            "backup_policy": {
+                "plan": "weekly",
+                "weekday": "SUN",
+                "hour": 8,
+            },
+        })
+    );
+}
+
 #[test]
 fn create_many_produces_correct_request() {
     before();
@@ -102,6 +141,19 @@ fn list_produces_correct_request() {
     assert_eq!(*req.body(), Value::Null);
 }
 
+#[test]
+fn list_by_name_produces_correct_request() {
+    before();
+
+    let correct_url = "https://api.digitalocean.com/v2/droplets?name=web-1";
+
+    let req: Request<List, Vec<Droplet>> = Droplet::list_by_name("web-1");
+    info!("{:#?}", req);
+
+    assert_eq!(req.url().as_str(), correct_url);
+    assert_eq!(*req.body(), Value::Null);
+}
+
 #[test]
 fn list_by_tag_produces_correct_request() {
     before();
@@ -213,3 +265,187 @@ fn get_neighbors_produces_correct_request() {
     assert_eq!(req.url().as_str(), correct_url);
     assert_eq!(*req.body(), Value::Null);
 }
+
+#[test]
+fn current_droplet_payload_deserializes() {
+    before();
+
+    let payload = json!({
+        "id": 3164444,
+        "name": "example.com",
+        "memory": 1024,
+        "vcpus": 1,
+        "disk": 25,
+        "locked": false,
+        "status": "active",
+        "kernel": null,
+        "created_at": "2020-07-21T18:37:44Z",
+        "features": ["backups", "private_networking", "ipv6"],
+        "backup_ids": [53893572],
+        "next_backup_window": {
+            "start": "2020-07-30T00:00:00Z",
+            "end": "2020-07-30T23:00:00Z"
+        },
+        "snapshot_ids": [67512819],
+        "image": {
+            "id": 63663980,
+            "name": "20.04 (LTS) x64",
+            "distribution": "Ubuntu",
+            "slug": "ubuntu-20-04-x64",
+            "public": true,
+            "regions": ["nyc3"],
+            "created_at": "2020-05-15T05:47:50Z",
+            "type": "snapshot",
+            "min_disk_size": 20,
+            "size_gigabytes": 2.36,
+            "description": "",
+            "tags": [],
+            "status": "available",
+            "error_message": ""
+        },
+        "volume_ids": [],
+        "size": {
+            "slug": "s-1vcpu-1gb",
+            "memory": 1024,
+            "vcpus": 1,
+            "disk": 25,
+            "transfer": 1.0,
+            "price_monthly": 5.0,
+            "price_hourly": 0.00743999984115362,
+            "regions": ["nyc3"],
+            "available": true,
+            "description": "Basic"
+        },
+        "size_slug": "s-1vcpu-1gb",
+        "networks": {
+            "v4": [
+                {
+                    "ip_address": "10.128.192.124",
+                    "netmask": "255.255.0.0",
+                    "gateway": "nil",
+                    "type": "private"
+                },
+                {
+                    "ip_address": "192.241.165.154",
+                    "netmask": "255.255.255.0",
+                    "gateway": "192.241.165.1",
+                    "type": "public"
+                }
+            ],
+            "v6": [
+                {
+                    "ip_address": "2604:a880:0:1010::18a:a001",
+                    "netmask": 64,
+                    "gateway": "2604:a880:0:1010::1",
+                    "type": "public"
+                }
+            ]
+        },
+        "region": {
+            "name": "New York 3",
+            "slug": "nyc3",
+            "features": ["private_networking", "backups", "ipv6"],
+            "available": true,
+            "sizes": ["s-1vcpu-1gb"]
+        },
+        "tags": ["web", "env:prod"],
+        "vpc_uuid": "760e09ef-dc84-11e8-981e-3cfdfeaae000",
+        "disk_info": [
+            {
+                "type": "local",
+                "size": {
+                    "amount": 25,
+                    "unit": "gib"
+                }
+            }
+        ]
+    });
+
+    let droplet: Droplet = serde_json::from_value(payload).unwrap();
+    info!("{:#?}", droplet);
+
+    assert_eq!(
+        droplet.vpc_uuid().as_ref().map(String::as_str),
+        Some("760e09ef-dc84-11e8-981e-3cfdfeaae000")
+    );
+    assert_eq!(droplet.disk_info()[0].size.amount, 25);
+    assert_eq!(droplet.networks().v4[0].gateway, None);
+    assert!(droplet.networks().v4[1].gateway.is_some());
+    assert_eq!(droplet.networks().v4[0].kind, NetworkKind::Private);
+    assert_eq!(*droplet.backup_ids(), vec![53893572]);
+}
+
+#[test]
+fn networks_report_addresses_cidrs_and_gateways() {
+    before();
+
+    let networks = Networks {
+        v4: vec![
+            NetworkV4 {
+                gateway: None,
+                ip_address: Ipv4Addr::new(10, 108, 0, 2),
+                netmask: Ipv4Addr::new(255, 255, 240, 0),
+                kind: NetworkKind::Private,
+            },
+            NetworkV4 {
+                gateway: Some(Ipv4Addr::new(192, 241, 165, 1)),
+                ip_address: Ipv4Addr::new(192, 241, 165, 154),
+                netmask: Ipv4Addr::new(255, 255, 255, 0),
+                kind: NetworkKind::Public,
+            },
+            NetworkV4 {
+                gateway: Some(Ipv4Addr::new(192, 241, 166, 1)),
+                ip_address: Ipv4Addr::new(192, 241, 166, 7),
+                netmask: Ipv4Addr::new(255, 255, 255, 0),
+                kind: NetworkKind::Public,
+            },
+        ],
+        v6: vec![NetworkV6 {
+            gateway: "2604:a880:0:1010::1".parse().ok(),
+            ip_address: "2604:a880:0:1010::18a:a001".parse().unwrap(),
+            netmask: 64,
+            kind: NetworkKind::Public,
+        }],
+    };
+    info!("{:#?}", networks);
+
+    // The first network of each kind is the primary one.
+    assert_eq!(
+        networks.public_ipv4(),
+        Some(Ipv4Addr::new(192, 241, 165, 154))
+    );
+    assert_eq!(networks.private_ipv4(), Some(Ipv4Addr::new(10, 108, 0, 2)));
+    assert_eq!(networks.ipv6(), "2604:a880:0:1010::18a:a001".parse().ok());
+    assert_eq!(networks.v4[0].prefix_len(), 20);
+    assert_eq!(
+        networks
+            .primary_v4(&NetworkKind::Private)
+            .map(NetworkV4::cidr)
+            .as_deref(),
+        Some("10.108.0.0/20")
+    );
+    assert_eq!(
+        networks
+            .primary_v4(&NetworkKind::Public)
+            .map(NetworkV4::cidr)
+            .as_deref(),
+        Some("192.241.165.0/24")
+    );
+    assert_eq!(
+        networks
+            .primary_v6(&NetworkKind::Public)
+            .map(NetworkV6::cidr)
+            .as_deref(),
+        Some("2604:a880:0:1010::/64")
+    );
+    assert!(networks.primary_v6(&NetworkKind::Private).is_none());
+    assert_eq!(networks.gateway_v4(&NetworkKind::Private), None);
+    assert_eq!(
+        networks.gateway_v4(&NetworkKind::Public),
+        Some(Ipv4Addr::new(192, 241, 165, 1))
+    );
+    assert_eq!(
+        networks.gateway_v6(&NetworkKind::Public),
+        "2604:a880:0:1010::1".parse().ok()
+    );
+}
@@ -0,0 +1,78 @@
+extern crate digitalocean;
+#[macro_use]
+extern crate log;
+#[macro_use]
+extern crate serde_json;
+
+mod utils;
+
+use digitalocean::api::{Action, Droplet, Tag};
+use digitalocean::cassette::Cassette;
+use digitalocean::error::Error;
+use digitalocean::prelude::*;
+
+use crate::utils::before;
+
+#[test]
+fn dry_run_journals_mutations() {
+    before();
+
+    // Reads are still sent, here to a cassette.
+    let cassette = Cassette::from_interactions(
+        serde_json::from_value(json!([{
+            "request": {
+                "method": "GET",
+                "url": "https://api.digitalocean.com/v2/actions/1",
+            },
+            "response": {
+                "status": 200,
+                "body": json!({
+                    "action": {
+                        "id": 1,
+                        "status": "completed",
+                        "started_at": "2020-07-21T18:37:44Z",
+                        "completed_at": null,
+                        "resource_id": 3164444,
+                        "resource_type": "droplet",
+                        "region_slug": "nyc3",
+                        "type": "reboot",
+                    }
+                })
+                .to_string(),
+            },
+        }]))
+        .unwrap(),
+    );
+    let client = DigitalOcean::new("unused")
+        .unwrap()
+        .cassette(cassette)
+        .dry_run();
+
+    let action = Action::get(1).execute(&client).unwrap();
+    assert_eq!(*action.status(), "completed");
+
+    Droplet::delete(3164444).execute(&client).unwrap();
+    Tag::get("web")
+        .add_resources(vec![("3164444", "droplet")])
+        .execute(&client)
+        .unwrap();
+    match Droplet::get(3164444).reboot().execute(&client) {
+        Err(Error::DryRun) => (),
+        result => panic!("Expected a dry run error, got {:?}", result),
+    }
+
+    let journal = client.journal().unwrap();
+    info!("{}", journal);
+
+    assert_eq!(
+        journal.to_string(),
+        "DELETE https://api.digitalocean.com/v2/droplets/3164444\n\
+         POST https://api.digitalocean.com/v2/tags/web/resources \
+         {\"resources\":[{\"resource_id\":\"3164444\",\"resource_type\":\"droplet\"}]}\n\
+         POST https://api.digitalocean.com/v2/droplets/3164444/actions {\"type\":\"reboot\"}\n"
+    );
+    assert_eq!(journal.entries()[2].body, json!({ "type": "reboot" }));
+
+    journal.clear();
+    assert!(journal.is_empty());
+}
@@ -0,0 +1,236 @@
+extern crate digitalocean;
+#[macro_use]
+extern crate log;
+#[macro_use]
+extern crate serde_json;
+
+mod utils;
+
+use digitalocean::api::{Droplet, Ensured, SshKey, Tag, Volume};
+use digitalocean::error::Error;
+use serde_json::Value;
+
+use crate::utils::{before, droplet, interaction, list, replay, volume};
+
+fn ssh_key(public_key: &str) -> Value {
+    json!({
+        "id": 512189,
+        "fingerprint": "3b:16:bf:e4:8b:00:8b:b8:59:8c:a9:d3:f0:19:45:fa",
+        "public_key": public_key,
+        "name": "deploy",
+    })
+}
+
+#[test]
+fn ensure_droplet_finds_or_creates() {
+    before();
+
+    let create = || Droplet::create("web-1", "nyc3", "s-1vcpu-1gb", "ubuntu-20-04-x64");
+    let client = replay(vec![
+        interaction(
+            "GET",
+            "/droplets?name=web-1&per_page=200",
+            Value::Null,
+            200,
+            list("droplets", vec![droplet(1, "web-1", "s-1vcpu-1gb")]),
+        ),
+        interaction(
+            "GET",
+            "/droplets?name=web-1&per_page=200",
+            Value::Null,
+            200,
+            list("droplets", vec![droplet(1, "web-1", "s-2vcpu-2gb")]),
+        ),
+        interaction(
+            "GET",
+            "/droplets?name=web-1&per_page=200",
+            Value::Null,
+            200,
+            list("droplets", vec![]),
+        ),
+        interaction(
+            "POST",
+            "/droplets",
+            create().body().clone(),
+            202,
+            json!({ "droplet": droplet(1, "web-1", "s-1vcpu-1gb") }),
+        ),
+    ]);
+
+    let found = create().ensure(&client).unwrap();
+    info!("{:#?}", found);
+    assert!(!found.was_created());
+
+    match create().ensure(&client) {
+        Err(Error::Incompatible(_)) => (),
+        result => panic!("Expected an incompatible droplet, got {:?}", result),
+    }
+
+    let created = create().ensure(&client).unwrap();
+    assert!(created.was_created());
+    assert_eq!(created.value().name(), "web-1");
+}
+
+#[test]
+fn ensure_volume_finds_or_creates() {
+    before();
+
+    let create = || Volume::create("data", 10).region("nyc3");
+    let client = replay(vec![
+        interaction(
+            "GET",
+            "/volumes?name=data&region=nyc3&per_page=200",
+            Value::Null,
+            200,
+            list("volumes", vec![volume("data", 10)]),
+        ),
+        interaction(
+            "GET",
+            "/volumes?name=data&region=nyc3&per_page=200",
+            Value::Null,
+            200,
+            list("volumes", vec![volume("data", 20)]),
+        ),
+        interaction(
+            "GET",
+            "/volumes?name=data&region=nyc3&per_page=200",
+            Value::Null,
+            200,
+            list("volumes", vec![]),
+        ),
+        interaction(
+            "POST",
+            "/volumes",
+            create().body().clone(),
+            201,
+            json!({ "volume": volume("data", 10) }),
+        ),
+    ]);
+
+    assert!(!create().ensure(&client).unwrap().was_created());
+    match create().ensure(&client) {
+        Err(Error::Incompatible(_)) => (),
+        result => panic!("Expected an incompatible volume, got {:?}", result),
+    }
+    assert!(create().ensure(&client).unwrap().was_created());
+}
+
+#[test]
+fn ensure_droplet_accepts_a_retired_image_slug() {
+    before();
+
+    let mut existing = droplet(1, "web-1", "s-1vcpu-1gb");
+    existing["image"]["slug"] = Value::Null;
+    let other_region = {
+        let mut other = droplet(2, "web-1", "s-1vcpu-1gb");
+        other["region"]["slug"] = json!("ams3");
+        other
+    };
+    let client = replay(vec![interaction(
+        "GET",
+        "/droplets?name=web-1&per_page=200",
+        Value::Null,
+        200,
+        list("droplets", vec![other_region, existing]),
+    )]);
+
+    let found = Droplet::create("web-1", "nyc3", "s-1vcpu-1gb", "ubuntu-20-04-x64")
+        .ensure(&client)
+        .unwrap();
+    info!("{:#?}", found);
+    assert!(!found.was_created());
+    assert_eq!(*found.value().id(), 1);
+}
+
+#[test]
+fn ensure_volume_checks_filesystem_type() {
+    before();
+
+    let mut formatted = volume("data", 10);
+    formatted["filesystem_type"] = json!("ext4");
+    let client = replay(vec![
+        interaction(
+            "GET",
+            "/volumes?name=data&per_page=200",
+            Value::Null,
+            200,
+            list("volumes", vec![formatted.clone()]),
+        ),
+        interaction(
+            "GET",
+            "/volumes?name=data&per_page=200",
+            Value::Null,
+            200,
+            list("volumes", vec![formatted]),
+        ),
+    ]);
+
+    let found = Volume::create("data", 10)
+        .filesystem_type("ext4")
+        .ensure(&client)
+        .unwrap();
+    assert_eq!(found.value().filesystem_type().as_deref(), Some("ext4"));
+    match Volume::create("data", 10)
+        .filesystem_type("xfs")
+        .ensure(&client)
+    {
+        Err(Error::Incompatible(_)) => (),
+        result => panic!("Expected an incompatible volume, got {:?}", result),
+    }
+}
+
+#[test]
+fn ensure_tag_finds_or_creates() {
+    before();
+
+    let tag = json!({ "tag": { "name": "web", "resources": {} } });
+    let client = replay(vec![
+        interaction("GET", "/tags/web", Value::Null, 200, tag.clone()),
+        interaction(
+            "GET",
+            "/tags/web",
+            Value::Null,
+            404,
+            json!({ "id": "not_found", "message": "tag not found" }),
+        ),
+        interaction("POST", "/tags", json!({ "name": "web" }), 201, tag),
+    ]);
+
+    match Tag::create("web").ensure(&client).unwrap() {
+        Ensured::Found(tag) => assert_eq!(tag.name(), "web"),
+        result => panic!("Expected an existing tag, got {:?}", result),
+    }
+    match Tag::create("web").ensure(&client).unwrap() {
+        Ensured::Created(tag) => assert_eq!(tag.name(), "web"),
+        result => panic!("Expected a new tag, got {:?}", result),
+    }
+}
+
+#[test]
+fn ensure_ssh_key_checks_public_key() {
+    before();
+
+    let client = replay(vec![
+        interaction(
+            "GET",
+            "/account/keys?per_page=200",
+            Value::Null,
+            200,
+            list("ssh_keys", vec![ssh_key("ssh-rsa AAAA deploy@example.com")]),
+        ),
+        interaction(
+            "GET",
+            "/account/keys?per_page=200",
+            Value::Null,
+            200,
+            list("ssh_keys", vec![ssh_key("ssh-rsa BBBB deploy@example.com")]),
+        ),
+    ]);
+    let create = || SshKey::create("deploy", "ssh-rsa AAAA deploy@example.com\n");
+
+    assert!(!create().ensure(&client).unwrap().was_created());
+    match create().ensure(&client) {
+        Err(Error::Incompatible(_)) => (),
+        result => panic!("Expected an incompatible SSH key, got {:?}", result),
+    }
+}
@@ -76,3 +76,85 @@ fn delete_produces_correct_request() {
     assert_eq!(req.url().as_str(), correct_url);
     assert_eq!(*req.body(), Value::Null);
 }
+
+#[test]
+fn current_image_payload_deserializes() {
+    before();
+
+    let payload = json!({
+        "id": 63663980,
+        "name": "20.04 (LTS) x64",
+        "type": "base",
+        "distribution": "Ubuntu",
+        "slug": "ubuntu-20-04-x64",
+        "public": true,
+        "regions": ["nyc3"],
+        "min_disk_size": 20,
+        "size_gigabytes": 2.36,
+        "created_at": "2020-05-15T05:47:50Z",
+        "status": "available",
+        "tags": ["base-image"],
+        "description": "",
+        "error_message": "",
+    });
+
+    let image: Image = serde_json::from_value(payload).unwrap();
+    info!("{:#?}", image);
+
+    assert_eq!(image.status().as_ref().map(String::as_str), Some("available"));
+    assert_eq!(*image.tags(), vec!["base-image".to_string()]);
+    assert!(image.extra().is_empty());
+}
+
+#[test]
+#[cfg(not(feature = "strict"))]
+fn unknown_fields_are_preserved() {
+    before();
+
+    let payload = json!({
+        "id": 63663980,
+        "name": "20.04 (LTS) x64",
+        "type": "base",
+        "distribution": "Ubuntu",
+        "slug": "ubuntu-20-04-x64",
+        "public": true,
+        "regions": ["nyc3"],
+        "min_disk_size": 20,
+        "size_gigabytes": 2.36,
+        "created_at": "2020-05-15T05:47:50Z",
+        "architecture": "x86_64",
+        "labels": ["base-image"],
+    });
+
+    let image: Image = serde_json::from_value(payload).unwrap();
+    info!("{:#?}", image);
+
+    assert_eq!(image.extra()["architecture"], json!("x86_64"));
+    assert_eq!(image.extra()["labels"], json!(["base-image"]));
+    assert_eq!(image.extra().len(), 2);
+}
+
+#[test]
+#[cfg(feature = "strict")]
+fn unknown_fields_are_rejected() {
+    before();
+
+    let payload = json!({
+        "id": 63663980,
+        "name": "20.04 (LTS) x64",
+        "type": "base",
+        "distribution": "Ubuntu",
+        "slug": "ubuntu-20-04-x64",
+        "public": true,
+        "regions": ["nyc3"],
+        "min_disk_size": 20,
+        "size_gigabytes": 2.36,
+        "created_at": "2020-05-15T05:47:50Z",
+        "architecture": "x86_64",
+    });
+
+    let err = serde_json::from_value::<Image>(payload).unwrap_err();
+    info!("{}", err);
+
+    assert!(err.to_string().contains("architecture"));
+}
@@ -0,0 +1,425 @@
+extern crate digitalocean;
+#[macro_use]
+extern crate log;
+#[macro_use]
+extern crate serde_json;
+extern crate url;
+extern crate url_serde;
+
+mod utils;
+
+use serde_json::Value;
+use std::time::Duration;
+
+use digitalocean::infrastructure::{Change, Plan, Spec, State};
+
+use crate::utils::{action, before, droplet, interaction, region, replay, volume};
+
+fn state() -> State {
+    let mut web = droplet(1, "web-1", "s-1vcpu-1gb");
+    web["tags"] = json!(["web"]);
+    let mut data = volume("data", 10);
+    data["droplet_ids"] = json!([1]);
+
+    State {
+        tags: serde_json::from_value(json!([
+            { "name": "web", "resources": {} },
+        ]))
+        .unwrap(),
+        droplets: serde_json::from_value(json!([web, droplet(2, "db-1", "s-1vcpu-1gb")])).unwrap(),
+        volumes: serde_json::from_value(json!([data])).unwrap(),
+        floating_ips: serde_json::from_value(json!([{
+            "ip": "45.55.96.47",
+            "region": region(),
+            "droplet": web,
+        }]))
+        .unwrap(),
+        domains: serde_json::from_value(json!([
+            { "name": "example.com", "ttl": 1800, "zone_file": null },
+        ]))
+        .unwrap(),
+        domain_records: vec![(
+            String::from("example.com"),
+            serde_json::from_value(json!([{
+                "id": 3,
+                "type": "A",
+                "name": "@",
+                "data": "192.0.2.1",
+                "priority": null,
+                "port": null,
+                "ttl": 1800,
+                "weight": null,
+            }]))
+            .unwrap(),
+        )]
+        .into_iter()
+        .collect(),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn plan_with_matching_spec_is_empty() {
+    before();
+
+    let spec: Spec = serde_json::from_value(json!({
+        "tags": ["web"],
+        "droplets": [{
+            "name": "web-1",
+            "region": "nyc3",
+            "size": "s-1vcpu-1gb",
+            "image": "ubuntu-20-04-x64",
+            "tags": ["web"],
+        }],
+        "volumes": [{
+            "name": "data",
+            "region": "nyc3",
+            "size_gigabytes": 10,
+            "droplet": "web-1",
+        }],
+        "floating_ips": [{ "droplet": "web-1" }],
+        "domains": [{
+            "name": "example.com",
+            "records": [{ "name": "@", "data": { "type": "A", "data": "192.0.2.1" } }],
+        }],
+    }))
+    .unwrap();
+
+    let plan = Plan::new(&spec, &state());
+    info!("{:#?}", plan);
+
+    assert!(plan.is_empty());
+}
+
+#[test]
+fn plan_orders_changes_by_dependency() {
+    before();
+
+    let spec: Spec = serde_json::from_value(json!({
+        "droplets": [
+            {
+                "name": "web-1",
+                "region": "nyc3",
+                "size": "s-2vcpu-2gb",
+                "image": "ubuntu-20-04-x64",
+                "tags": ["web", "prod"],
+            },
+            {
+                "name": "web-2",
+                "region": "nyc3",
+                "size": "s-1vcpu-1gb",
+                "image": "ubuntu-20-04-x64",
+                "tags": ["web"],
+            },
+        ],
+        "volumes": [
+            { "name": "data", "region": "nyc3", "size_gigabytes": 20, "droplet": "db-1" },
+            { "name": "logs", "region": "nyc3", "size_gigabytes": 5, "droplet": "web-2" },
+        ],
+        "floating_ips": [{ "droplet": "web-2" }],
+        "load_balancers": [{
+            "name": "lb",
+            "region": "nyc3",
+            "forwarding_rules": [{
+                "entry_protocol": "http",
+                "entry_port": 80,
+                "target_protocol": "http",
+                "target_port": 80,
+            }],
+            "droplets": ["web-1", "web-2"],
+        }],
+        "domains": [
+            {
+                "name": "example.com",
+                "records": [
+                    { "name": "@", "data": { "type": "A", "data": "192.0.2.1" } },
+                    { "name": "www", "data": { "type": "CNAME", "data": "@" } },
+                ],
+            },
+            { "name": "example.org", "ip_address": "192.0.2.2" },
+        ],
+    }))
+    .unwrap();
+
+    let plan = Plan::new(&spec, &state());
+    info!("{:#?}", plan);
+
+    match plan.changes()[1] {
+        Change::ResizeDroplet {
+            ref from, ref to, ..
+        } => {
+            assert_eq!(from, "s-1vcpu-1gb");
+            assert_eq!(to, "s-2vcpu-2gb");
+        }
+        ref change => panic!("Unexpected change {:?}", change),
+    }
+    assert_eq!(
+        plan.to_string(),
+        "+ tag prod\n\
+         ~ droplet web-1 (size s-1vcpu-1gb -> s-2vcpu-2gb)\n\
+         ~ droplet web-1 (add tag prod)\n\
+         + droplet web-2 (nyc3, s-1vcpu-1gb, ubuntu-20-04-x64)\n\
+         ~ volume data (size 10GiB -> 20GiB)\n\
+         ~ volume data (detach from droplet 1)\n\
+         ~ volume data (attach to droplet db-1)\n\
+         + volume logs (nyc3, 5GiB)\n\
+         ~ volume logs (attach to droplet web-2)\n\
+         + floating ip (assigned to droplet web-2)\n\
+         + load balancer lb (nyc3)\n\
+         ~ domain example.com\n    \
+         + www CNAME @\n\
+         + domain example.org\n"
+    );
+}
+
+#[test]
+fn apply_creates_resources_and_waits_for_droplets() {
+    before();
+
+    let spec: Spec = serde_json::from_value(json!({
+        "droplets": [{
+            "name": "web-1",
+            "region": "nyc3",
+            "size": "s-1vcpu-1gb",
+            "image": "ubuntu-20-04-x64",
+            "tags": ["web"],
+        }],
+        "domains": [{ "name": "example.org" }],
+    }))
+    .unwrap();
+    let plan = Plan::new(&spec, &State::default()).poll_interval(Duration::from_millis(10));
+    info!("{}", plan);
+
+    let mut created = droplet(1, "web-1", "s-1vcpu-1gb");
+    created["status"] = json!("new");
+    let mut active = droplet(1, "web-1", "s-1vcpu-1gb");
+    active["networks"] = json!({
+        "v4": [{
+            "ip_address": "192.0.2.10",
+            "netmask": "255.255.255.0",
+            "gateway": "192.0.2.1",
+            "type": "public",
+        }],
+        "v6": [],
+    });
+    let client = replay(vec![
+        interaction(
+            "POST",
+            "/tags",
+            json!({ "name": "web" }),
+            201,
+            json!({ "tag": { "name": "web", "resources": {} } }),
+        ),
+        interaction(
+            "POST",
+            "/droplets",
+            json!({
+                "name": "web-1",
+                "region": "nyc3",
+                "size": "s-1vcpu-1gb",
+                "image": "ubuntu-20-04-x64",
+                "ssh_keys": [],
+                "tags": ["web"],
+                "backups": false,
+                "ipv6": false,
+                "monitoring": false,
+            }),
+            202,
+            json!({ "droplet": created }),
+        ),
+        // The Droplet is polled until it is active with an address.
+        interaction(
+            "GET",
+            "/droplets/1",
+            Value::Null,
+            200,
+            json!({ "droplet": created }),
+        ),
+        interaction(
+            "GET",
+            "/droplets/1",
+            Value::Null,
+            200,
+            json!({ "droplet": active }),
+        ),
+        interaction(
+            "POST",
+            "/domains",
+            json!({ "name": "example.org" }),
+            201,
+            json!({ "domain": { "name": "example.org", "ttl": 1800, "zone_file": null } }),
+        ),
+    ]);
+
+    plan.apply(&client).unwrap();
+    assert!(client.active_cassette().unwrap().is_finished());
+}
+
+#[test]
+fn apply_updates_resources_and_waits_for_actions() {
+    before();
+
+    let spec: Spec = serde_json::from_value(json!({
+        "droplets": [{
+            "name": "web-1",
+            "region": "nyc3",
+            "size": "s-2vcpu-2gb",
+            "image": "ubuntu-20-04-x64",
+            "tags": ["web", "prod"],
+        }],
+        "volumes": [{ "name": "data", "region": "nyc3", "size_gigabytes": 20 }],
+    }))
+    .unwrap();
+    let plan = Plan::new(&spec, &state()).poll_interval(Duration::from_millis(10));
+    info!("{}", plan);
+
+    let volume_actions = "/volumes/506f78a4-e098-11e5-ad9f-000f53306ae1/actions";
+    let client = replay(vec![
+        interaction(
+            "POST",
+            "/tags",
+            json!({ "name": "prod" }),
+            201,
+            json!({ "tag": { "name": "prod", "resources": {} } }),
+        ),
+        // Resizing powers the Droplet off and back on again.
+        interaction(
+            "GET",
+            "/droplets/1",
+            Value::Null,
+            200,
+            json!({ "droplet": droplet(1, "web-1", "s-1vcpu-1gb") }),
+        ),
+        interaction(
+            "POST",
+            "/droplets/1/actions",
+            json!({ "type": "shutdown" }),
+            201,
+            action(10, "shutdown", "in-progress"),
+        ),
+        interaction(
+            "GET",
+            "/actions/10",
+            Value::Null,
+            200,
+            action(10, "shutdown", "completed"),
+        ),
+        interaction(
+            "POST",
+            "/droplets/1/actions",
+            json!({ "type": "resize", "size": "s-2vcpu-2gb", "disk": false }),
+            201,
+            action(11, "resize", "completed"),
+        ),
+        interaction(
+            "POST",
+            "/droplets/1/actions",
+            json!({ "type": "power_on" }),
+            201,
+            action(12, "power_on", "completed"),
+        ),
+        interaction(
+            "POST",
+            "/tags/prod/resources",
+            json!({ "resources": [{ "resource_id": "1", "resource_type": "droplet" }] }),
+            204,
+            Value::Null,
+        ),
+        interaction(
+            "POST",
+            volume_actions,
+            json!({ "type": "resize", "size_gigabytes": 20 }),
+            201,
+            action(13, "resize", "completed"),
+        ),
+    ]);
+
+    plan.apply(&client).unwrap();
+    assert!(client.active_cassette().unwrap().is_finished());
+}
+
+#[test]
+fn apply_deletes_records_and_detaches_volumes() {
+    before();
+
+    let spec: Spec = serde_json::from_value(json!({
+        "volumes": [{ "name": "data", "region": "nyc3", "size_gigabytes": 10, "droplet": "db-1" }],
+        "domains": [{ "name": "example.com", "records": [] }],
+    }))
+    .unwrap();
+    let plan = Plan::new(&spec, &state()).poll_interval(Duration::from_millis(10));
+    info!("{}", plan);
+
+    let volume_actions = "/volumes/506f78a4-e098-11e5-ad9f-000f53306ae1/actions";
+    let client = replay(vec![
+        interaction(
+            "POST",
+            volume_actions,
+            json!({ "type": "detach", "droplet_id": 1 }),
+            201,
+            action(20, "detach", "completed"),
+        ),
+        interaction(
+            "POST",
+            volume_actions,
+            json!({ "type": "attach", "droplet_id": 2 }),
+            201,
+            action(21, "attach", "completed"),
+        ),
+        interaction(
+            "DELETE",
+            "/domains/example.com/records/3",
+            Value::Null,
+            204,
+            Value::Null,
+        ),
+    ]);
+
+    plan.apply(&client).unwrap();
+    assert!(client.active_cassette().unwrap().is_finished());
+}
+
+#[test]
+fn apply_tells_volumes_with_the_same_name_apart_by_region() {
+    before();
+
+    let mut nyc3 = volume("data", 10);
+    nyc3["id"] = json!("nyc3-volume");
+    let mut ams3 = volume("data", 10);
+    ams3["id"] = json!("ams3-volume");
+    ams3["region"]["slug"] = json!("ams3");
+    let state = State {
+        droplets: serde_json::from_value(json!([droplet(2, "db-1", "s-1vcpu-1gb")])).unwrap(),
+        volumes: serde_json::from_value(json!([nyc3, ams3])).unwrap(),
+        ..Default::default()
+    };
+    let spec: Spec = serde_json::from_value(json!({
+        "volumes": [
+            { "name": "data", "region": "nyc3", "size_gigabytes": 20, "droplet": "db-1" },
+            { "name": "data", "region": "ams3", "size_gigabytes": 10 },
+        ],
+    }))
+    .unwrap();
+    let plan = Plan::new(&spec, &state).poll_interval(Duration::from_millis(10));
+    info!("{}", plan);
+
+    let client = replay(vec![
+        interaction(
+            "POST",
+            "/volumes/nyc3-volume/actions",
+            json!({ "type": "resize", "size_gigabytes": 20 }),
+            201,
+            action(30, "resize", "completed"),
+        ),
+        interaction(
+            "POST",
+            "/volumes/nyc3-volume/actions",
+            json!({ "type": "attach", "droplet_id": 2 }),
+            201,
+            action(31, "attach", "completed"),
+        ),
+    ]);
+
+    plan.apply(&client).unwrap();
+    assert!(client.active_cassette().unwrap().is_finished());
+}
@@ -0,0 +1,293 @@
+#![cfg(any(feature = "metrics", feature = "tracing"))]
+
+extern crate digitalocean;
+#[macro_use]
+extern crate log;
+#[macro_use]
+extern crate serde_json;
+#[cfg(feature = "metrics")]
+extern crate metrics;
+#[cfg(feature = "tracing")]
+extern crate tracing;
+
+mod utils;
+
+use digitalocean::api::{Action, Droplet};
+#[cfg(feature = "metrics")]
+use digitalocean::cassette::Cassette;
+use digitalocean::prelude::*;
+#[cfg(feature = "metrics")]
+use metrics::{
+    Counter, CounterFn, Gauge, Histogram, HistogramFn, Key, KeyName, Metadata, Recorder,
+    SharedString, Unit,
+};
+#[cfg(feature = "tracing")]
+use serde_json::Value;
+#[cfg(feature = "tracing")]
+use std::collections::BTreeMap;
+#[cfg(feature = "tracing")]
+use std::fmt;
+use std::sync::{Arc, Mutex};
+#[cfg(feature = "tracing")]
+use tracing::field::{Field, Visit};
+#[cfg(feature = "tracing")]
+use tracing::span::{Attributes, Id, Record};
+#[cfg(feature = "tracing")]
+use tracing::Subscriber;
+
+use crate::utils::before;
+#[cfg(feature = "tracing")]
+use crate::utils::{action, interaction, list, region, replay, API_URL};
+
+// Records each metric as `name{label=value,...}`.
+#[cfg(feature = "metrics")]
+#[derive(Default)]
+struct Events(Arc<Mutex<Vec<String>>>);
+
+#[cfg(feature = "metrics")]
+struct Event {
+    key: String,
+    events: Arc<Mutex<Vec<String>>>,
+}
+
+#[cfg(feature = "metrics")]
+impl CounterFn for Event {
+    fn increment(&self, value: u64) {
+        self.events
+            .lock()
+            .unwrap()
+            .push(format!("{} +{}", self.key, value));
+    }
+
+    fn absolute(&self, _: u64) {}
+}
+
+#[cfg(feature = "metrics")]
+impl HistogramFn for Event {
+    fn record(&self, _: f64) {
+        self.events.lock().unwrap().push(self.key.clone());
+    }
+}
+
+#[cfg(feature = "metrics")]
+impl Events {
+    fn event(&self, key: &Key) -> Arc<Event> {
+        let labels = key
+            .labels()
+            .map(|label| format!("{}={}", label.key(), label.value()))
+            .collect::<Vec<_>>();
+        Arc::new(Event {
+            key: format!("{}{{{}}}", key.name(), labels.join(",")),
+            events: self.0.clone(),
+        })
+    }
+}
+
+#[cfg(feature = "metrics")]
+impl Recorder for Events {
+    fn describe_counter(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+    fn describe_gauge(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+    fn describe_histogram(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+    fn register_counter(&self, key: &Key, _: &Metadata<'_>) -> Counter {
+        Counter::from_arc(self.event(key))
+    }
+
+    fn register_gauge(&self, _: &Key, _: &Metadata<'_>) -> Gauge {
+        Gauge::noop()
+    }
+
+    fn register_histogram(&self, key: &Key, _: &Metadata<'_>) -> Histogram {
+        Histogram::from_arc(self.event(key))
+    }
+}
+
+// Records the fields of each span, in the order the spans were opened.
+#[cfg(feature = "tracing")]
+#[derive(Default)]
+struct Spans(Arc<Mutex<Vec<BTreeMap<String, String>>>>);
+
+#[cfg(feature = "tracing")]
+struct Fields<'a>(&'a mut BTreeMap<String, String>);
+
+#[cfg(feature = "tracing")]
+impl Visit for Fields<'_> {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_string(), value.to_string());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.0
+            .insert(field.name().to_string(), format!("{:?}", value));
+    }
+}
+
+#[cfg(feature = "tracing")]
+impl Subscriber for Spans {
+    fn enabled(&self, _: &tracing::Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, span: &Attributes<'_>) -> Id {
+        let mut spans = self.0.lock().unwrap();
+        let mut fields = BTreeMap::new();
+        fields.insert("name".to_string(), span.metadata().name().to_string());
+        span.record(&mut Fields(&mut fields));
+        spans.push(fields);
+        Id::from_u64(spans.len() as u64)
+    }
+
+    fn record(&self, span: &Id, values: &Record<'_>) {
+        let mut spans = self.0.lock().unwrap();
+        values.record(&mut Fields(&mut spans[span.into_u64() as usize - 1]));
+    }
+
+    fn record_follows_from(&self, _: &Id, _: &Id) {}
+    fn event(&self, _: &tracing::Event<'_>) {}
+    fn enter(&self, _: &Id) {}
+    fn exit(&self, _: &Id) {}
+}
+
+#[test]
+#[cfg(feature = "metrics")]
+fn requests_and_errors_are_measured() {
+    before();
+
+    let cassette = Cassette::from_interactions(
+        serde_json::from_value(json!([
+            {
+                "request": {
+                    "method": "GET",
+                    "url": "https://api.digitalocean.com/v2/actions/1",
+                },
+                "response": {
+                    "status": 200,
+                    "body": json!({
+                        "action": {
+                            "id": 1,
+                            "status": "completed",
+                            "type": "reboot",
+                            "started_at": "2020-07-21T18:37:44Z",
+                            "completed_at": null,
+                            "resource_id": 3164444,
+                            "resource_type": "droplet",
+                            "region_slug": "nyc3",
+                        }
+                    })
+                    .to_string(),
+                },
+            },
+            {
+                "request": {
+                    "method": "GET",
+                    "url": "https://api.digitalocean.com/v2/droplets/2",
+                },
+                "response": { "status": 404 },
+            },
+        ]))
+        .unwrap(),
+    );
+    let client = DigitalOcean::new("unused").unwrap().cassette(cassette);
+    let recorder = Events::default();
+    let events = recorder.0.clone();
+
+    metrics::with_local_recorder(&recorder, || {
+        Action::get(1).execute(&client).unwrap();
+        Droplet::get(2).execute(&client).unwrap_err();
+    });
+    let events = events.lock().unwrap();
+    info!("{:#?}", events);
+
+    assert_eq!(
+        *events,
+        vec![
+            "digitalocean_requests_total{method=GET,resource=actions,status=200} +1",
+            "digitalocean_request_duration_seconds{method=GET,resource=actions}",
+            "digitalocean_requests_total{method=GET,resource=droplets,status=404} +1",
+            "digitalocean_request_duration_seconds{method=GET,resource=droplets}",
+            "digitalocean_errors_total{method=GET,resource=droplets,kind=not_found} +1",
+        ]
+    );
+}
+
+#[test]
+#[cfg(feature = "tracing")]
+fn spans_record_status_page_and_error() {
+    before();
+
+    let mut first_page = list("regions", vec![region()]);
+    first_page["links"] = json!({
+        "pages": { "next": format!("{}/regions?page=2&per_page=200", API_URL) },
+    });
+    let client = replay(vec![
+        interaction(
+            "GET",
+            "/actions/1",
+            Value::Null,
+            200,
+            action(1, "reboot", "completed"),
+        ),
+        interaction(
+            "GET",
+            "/droplets/2",
+            Value::Null,
+            404,
+            json!({ "id": "not_found", "message": "The resource you were accessing could not be found." }),
+        ),
+        interaction("GET", "/regions?per_page=200", Value::Null, 200, first_page),
+        interaction(
+            "GET",
+            "/regions?page=2&per_page=200",
+            Value::Null,
+            200,
+            list("regions", vec![region()]),
+        ),
+    ]);
+    let subscriber = Spans::default();
+    let spans = subscriber.0.clone();
+
+    tracing::subscriber::with_default(subscriber, || {
+        Action::get(1).execute(&client).unwrap();
+        Droplet::get(2).execute(&client).unwrap_err();
+        Region::list().execute(&client).unwrap();
+    });
+    let spans = spans.lock().unwrap();
+    info!("{:#?}", spans);
+
+    let span = |fields: &[(&str, &str)]| {
+        fields
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect::<BTreeMap<_, _>>()
+    };
+    assert_eq!(
+        *spans,
+        vec![
+            span(&[
+                ("name", "digitalocean.request"),
+                ("method", "GET"),
+                ("resource", "actions"),
+                ("path", "/v2/actions/{id}"),
+                ("page", "1"),
+                ("status", "200"),
+            ]),
+            span(&[
+                ("name", "digitalocean.request"),
+                ("method", "GET"),
+                ("resource", "droplets"),
+                ("path", "/v2/droplets/{id}"),
+                ("page", "1"),
+                ("status", "404"),
+                ("error", "Not Found"),
+            ]),
+            span(&[
+                ("name", "digitalocean.request"),
+                ("method", "GET"),
+                ("resource", "regions"),
+                ("path", "/v2/regions"),
+                ("page", "2"),
+                ("status", "200"),
+            ]),
+        ]
+    );
+}
@@ -0,0 +1,157 @@
+extern crate digitalocean;
+#[macro_use]
+extern crate log;
+#[macro_use]
+extern crate serde_json;
+extern crate reqwest;
+extern crate url;
+
+mod utils;
+
+use digitalocean::api::{Action, Droplet};
+use digitalocean::cassette::Cassette;
+use digitalocean::error::Error;
+use digitalocean::interceptor::{Interceptor, OutgoingRequest, Response};
+use digitalocean::prelude::*;
+use reqwest::StatusCode;
+use serde_json::Value;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use url::Url;
+
+use crate::utils::before;
+
+fn action(id: usize) -> Value {
+    json!({
+        "action": {
+            "id": id,
+            "status": "completed",
+            "started_at": "2020-07-21T18:37:44Z",
+            "completed_at": null,
+            "resource_id": 3164444,
+            "resource_type": "droplet",
+            "region_slug": "nyc3",
+            "type": "reboot",
+        }
+    })
+}
+
+// Records the order hooks are called in.
+struct Trace {
+    name: &'static str,
+    calls: Arc<Mutex<Vec<String>>>,
+}
+
+impl Interceptor for Trace {
+    fn on_request(&self, request: &mut OutgoingRequest) -> Result<Option<Response>, Error> {
+        self.calls
+            .lock()
+            .unwrap()
+            .push(format!("{} request {}", self.name, request.url.path()));
+        request.annotate(self.name, "seen");
+        Ok(None)
+    }
+
+    fn on_response(
+        &self,
+        request: &OutgoingRequest,
+        response: &mut Response,
+        _: Duration,
+    ) -> Result<(), Error> {
+        assert_eq!(request.annotations[self.name], "seen");
+        self.calls.lock().unwrap().push(format!(
+            "{} response {}",
+            self.name,
+            response.status.as_u16()
+        ));
+        Ok(())
+    }
+}
+
+// Answers every request for action 1 itself.
+struct Stub;
+
+impl Interceptor for Stub {
+    fn on_request(&self, request: &mut OutgoingRequest) -> Result<Option<Response>, Error> {
+        if request.url.path().ends_with("/actions/1") {
+            Ok(Some(Response::new(StatusCode::OK, action(1).to_string())))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+// Moves requests for action 2 onto action 3, and refuses deletions.
+struct Rewrite;
+
+impl Interceptor for Rewrite {
+    fn on_request(&self, request: &mut OutgoingRequest) -> Result<Option<Response>, Error> {
+        if !request.is_read() {
+            return Err(Error::InvalidRequest(String::from("read only")));
+        }
+        if request.url.path().ends_with("/actions/2") {
+            request.url = Url::parse("https://api.digitalocean.com/v2/actions/3").unwrap();
+        }
+        request.set_header("x-request-id", "1234");
+        Ok(None)
+    }
+}
+
+#[test]
+fn interceptors_see_requests_and_responses_in_order() {
+    before();
+
+    let calls = Arc::new(Mutex::new(Vec::new()));
+    let cassette = Cassette::from_interactions(
+        serde_json::from_value(json!([{
+            "request": {
+                "method": "GET",
+                "url": "https://api.digitalocean.com/v2/actions/3",
+            },
+            "response": { "status": 200, "body": action(3).to_string() },
+        }]))
+        .unwrap(),
+    );
+    let client = DigitalOcean::new("unused")
+        .unwrap()
+        .cassette(cassette)
+        .interceptor(Trace {
+            name: "outer",
+            calls: calls.clone(),
+        })
+        .interceptor(Stub)
+        .interceptor(Trace {
+            name: "inner",
+            calls: calls.clone(),
+        })
+        .interceptor(Rewrite);
+
+    // Short-circuited, so never reaches the inner interceptors or the cassette.
+    let stubbed = Action::get(1).execute(&client).unwrap();
+    info!("{:#?}", stubbed);
+    assert_eq!(*stubbed.id(), 1);
+
+    // Rewritten, so replayed from the cassette as action 3.
+    let rewritten = Action::get(2).execute(&client).unwrap();
+    info!("{:#?}", rewritten);
+    assert_eq!(*rewritten.id(), 3);
+
+    match Droplet::delete(3164444).execute(&client) {
+        Err(Error::InvalidRequest(_)) => (),
+        result => panic!("Expected the deletion to be refused, got {:?}", result),
+    }
+
+    assert_eq!(
+        *calls.lock().unwrap(),
+        vec![
+            "outer request /v2/actions/1",
+            "outer response 200",
+            "outer request /v2/actions/2",
+            "inner request /v2/actions/2",
+            "inner response 200",
+            "outer response 200",
+            "outer request /v2/droplets/3164444",
+            "inner request /v2/droplets/3164444",
+        ]
+    );
+}
@@ -10,11 +10,14 @@ mod utils;
 
 use serde_json::Value;
 
+use digitalocean::api::load_balancer_fields::{
+    Algorithm, ForwardingRule, HealthCheckProtocol, Protocol,
+};
 use digitalocean::api::LoadBalancer;
 use digitalocean::method::{Create, Delete, Get, List, Update};
-use digitalocean::request::Request;
+use digitalocean::request::{Executable, Request};
 
-use crate::utils::before;
+use crate::utils::{before, interaction, replay};
 
 #[test]
 fn list_produces_correct_request() {
@@ -51,14 +54,21 @@ fn create_produces_correct_request() {
     before();
 
     let correct_url = "https://api.digitalocean.com/v2/load_balancers";
-    let (name, region, algo) = ("test", "tor1", "least_connections");
-    let rule_1 = ("tcp", 22, "tcp", 22);
-    let rule_2 = ("http", 443, "http", 443, None, false);
+    let (name, region, algo) = ("test", "tor1", Algorithm::LeastConnections);
+    let rule_1 = (Protocol::Tcp, 22, Protocol::Tcp, 22);
+    let rule_2 = (
+        Protocol::Https,
+        443,
+        Protocol::Http,
+        80,
+        Some("cert-id"),
+        false,
+    );
 
     let req: Request<Create, LoadBalancer> = LoadBalancer::create(name, region)
         .algorithm(algo)
-        .forwarding_rule(rule_1)
-        .forwarding_rule(rule_2);
+        .forwarding_rule(rule_1.clone())
+        .forwarding_rule(rule_2.clone());
     info!("{:#?}", req);
 
     assert_eq!(req.url().as_str(), correct_url);
@@ -67,7 +77,7 @@ fn create_produces_correct_request() {
         json!({
             "name": name,
             "region": region,
-            "algorithm": algo,
+            "algorithm": "least_connections",
             "forwarding_rules": [
                 {
                     "entry_protocol": rule_1.0,
@@ -82,7 +92,7 @@ fn create_produces_correct_request() {
                     "entry_port": rule_2.1,
                     "target_protocol": rule_2.2,
                     "target_port": rule_2.3,
-                    "certificate_id": Value::Null,
+                    "certificate_id": rule_2.4,
                     "tls_passthrough": false,
                 },
             ],
@@ -100,8 +110,7 @@ fn update_produces_correct_request() {
         load_balancer_id
     );
     let (name, region, tag) = ("test", "tor1", "tag");
-    let (e_protocol, e_port, t_protocol, t_port) =
-        (String::from("tcp"), 22, String::from("tcp"), 22);
+    let (e_protocol, e_port, t_protocol, t_port) = (Protocol::Tcp, 22, Protocol::Tcp, 22);
 
     let req: Request<Update, LoadBalancer> = LoadBalancer::update(load_balancer_id)
         .name(name)
@@ -205,10 +214,17 @@ fn add_forwarding_rule_produces_correct_request() {
         "https://api.digitalocean.com/v2/load_balancers/{}/forwarding_rules",
         load_balancer_id
     );
-    let (e_protocol, e_port, t_protocol, t_port) = ("tcp", 22, "tcp", 22);
+    let (e_protocol, e_port, t_protocol, t_port) = (Protocol::Tcp, 22, Protocol::Tcp, 22);
 
-    let req: Request<Create, ()> = LoadBalancer::get(load_balancer_id)
-        .add_forwarding_rules(vec![(e_protocol, e_port, t_protocol, t_port, None, true)]);
+    let req: Request<Create, ()> =
+        LoadBalancer::get(load_balancer_id).add_forwarding_rules(vec![(
+            e_protocol.clone(),
+            e_port,
+            t_protocol.clone(),
+            t_port,
+            None::<String>,
+            true,
+        )]);
     info!("{:#?}", req);
 
     assert_eq!(req.url().as_str(), correct_url);
@@ -238,10 +254,17 @@ fn remove_forwarding_rule_produces_correct_request() {
         "https://api.digitalocean.com/v2/load_balancers/{}/forwarding_rules",
         load_balancer_id
     );
-    let (e_protocol, e_port, t_protocol, t_port) = ("tcp", 22, "tcp", 22);
+    let (e_protocol, e_port, t_protocol, t_port) = (Protocol::Tcp, 22, Protocol::Tcp, 22);
 
-    let req: Request<Delete, ()> = LoadBalancer::get(load_balancer_id)
-        .remove_forwarding_rules(vec![(e_protocol, e_port, t_protocol, t_port, None, false)]);
+    let req: Request<Delete, ()> =
+        LoadBalancer::get(load_balancer_id).remove_forwarding_rules(vec![(
+            e_protocol.clone(),
+            e_port,
+            t_protocol.clone(),
+            t_port,
+            None::<String>,
+            false,
+        )]);
     info!("{:#?}", req);
 
     assert_eq!(req.url().as_str(), correct_url);
@@ -261,3 +284,224 @@ fn remove_forwarding_rule_produces_correct_request() {
         })
     );
 }
+
+#[test]
+fn create_with_all_options_produces_correct_request() {
+    before();
+
+    let correct_url = "https://api.digitalocean.com/v2/load_balancers";
+    let (name, region, vpc) = ("test", "tor1", "c33931f2-a26a-4e61-b85c-4e95a2ec431b");
+    let certificate = String::from("892071a0-bb95-49bc-8021-3afd67a210bf");
+
+    let req: Request<Create, LoadBalancer> = LoadBalancer::create(name, region)
+        .forwarding_rule((
+            Protocol::Http3,
+            443,
+            Protocol::Http,
+            80,
+            Some(certificate.clone()),
+        ))
+        .health_check(
+            HealthCheckProtocol::Https,
+            443,
+            Some("/up"),
+            None,
+            None,
+            None,
+            None,
+        )
+        .size_unit(2)
+        .vpc_uuid(vpc)
+        .enable_proxy_protocol(true)
+        .enable_backend_keepalive(true)
+        .http_idle_timeout_seconds(90)
+        .disable_lets_encrypt_dns_records(true)
+        .firewall(vec!["cidr:1.2.0.0/16"], vec!["ip:1.2.3.4"]);
+    info!("{:#?}", req);
+
+    assert_eq!(req.url().as_str(), correct_url);
+    assert_eq!(
+        *req.body(),
+        json!({
+            "name": name,
+            "region": region,
+            "forwarding_rules": [
+                {
+                    "entry_protocol": "http3",
+                    "entry_port": 443,
+                    "target_protocol": "http",
+                    "target_port": 80,
+                    "certificate_id": certificate,
+                    "tls_passthrough": false,
+                },
+            ],
+            "health_check": {
+                "protocol": "https",
+                "port": 443,
+                "path": "/up",
+            },
+            "size_unit": 2,
+            "vpc_uuid": vpc,
+            "enable_proxy_protocol": true,
+            "enable_backend_keepalive": true,
+            "http_idle_timeout_seconds": 90,
+            "disable_lets_encrypt_dns_records": true,
+            "firewall": {
+                "allow": ["cidr:1.2.0.0/16"],
+                "deny": ["ip:1.2.3.4"],
+            },
+        })
+    );
+}
+
+#[test]
+fn current_load_balancer_payload_deserializes() {
+    before();
+
+    let payload = json!({
+        "id": "4de7ac8b-495b-4884-9a69-1050c6793cd6",
+        "name": "example-lb-01",
+        "ip": "104.131.186.241",
+        "size_unit": 3,
+        "size": "lb-small",
+        "algorithm": "round_robin",
+        "status": "new",
+        "created_at": "2017-02-01T22:22:58Z",
+        "forwarding_rules": [
+            {
+                "entry_protocol": "http2",
+                "entry_port": 443,
+                "target_protocol": "http2",
+                "target_port": 80,
+                "certificate_id": "892071a0-bb95-49bc-8021-3afd67a210bf",
+                "tls_passthrough": false,
+            },
+        ],
+        "health_check": {
+            "protocol": "http",
+            "port": 80,
+            "path": "/",
+            "check_interval_seconds": 10,
+            "response_timeout_seconds": 5,
+            "healthy_threshold": 5,
+            "unhealthy_threshold": 3,
+        },
+        "sticky_sessions": {
+            "type": "none",
+        },
+        "region": {
+            "name": "New York 3",
+            "slug": "nyc3",
+            "sizes": ["s-1vcpu-1gb"],
+            "features": ["private_networking"],
+            "available": true,
+        },
+        "tag": "",
+        "droplet_ids": [3164444, 3164445],
+        "redirect_http_to_https": false,
+        "enable_proxy_protocol": false,
+        "enable_backend_keepalive": true,
+        "http_idle_timeout_seconds": 60,
+        "vpc_uuid": "c33931f2-a26a-4e61-b85c-4e95a2ec431b",
+        "disable_lets_encrypt_dns_records": false,
+        "firewall": {
+            "deny": ["ip:1.2.3.4"],
+            "allow": ["cidr:2.3.4.0/24"],
+        },
+    });
+
+    let load_balancer: LoadBalancer = serde_json::from_value(payload).unwrap();
+    info!("{:#?}", load_balancer);
+
+    assert_eq!(*load_balancer.algorithm(), Algorithm::RoundRobin);
+    assert_eq!(*load_balancer.size_unit(), Some(3));
+    assert!(*load_balancer.enable_backend_keepalive());
+    assert_eq!(
+        load_balancer.forwarding_rules()[0].entry_protocol,
+        Protocol::Http2
+    );
+    assert_eq!(
+        load_balancer.health_check().protocol,
+        HealthCheckProtocol::Http
+    );
+    let firewall = load_balancer.firewall().as_ref().unwrap();
+    assert_eq!(firewall.deny, vec!["ip:1.2.3.4"]);
+}
+
+#[test]
+fn unknown_protocols_and_algorithms_are_kept() {
+    before();
+
+    let rule: ForwardingRule = serde_json::from_value(json!({
+        "entry_protocol": "quic",
+        "entry_port": 443,
+        "target_protocol": "http",
+        "target_port": 80,
+    }))
+    .unwrap();
+    info!("{:#?}", rule);
+
+    assert_eq!(rule.entry_protocol, Protocol::Other("quic".into()));
+    assert_eq!(rule.target_protocol, Protocol::Http);
+    assert_eq!(
+        serde_json::to_value(&rule).unwrap()["entry_protocol"],
+        "quic"
+    );
+
+    let algorithm: Algorithm = serde_json::from_value(json!("weighted")).unwrap();
+    assert_eq!(algorithm, Algorithm::Other("weighted".into()));
+    assert_eq!(algorithm.to_string(), "weighted");
+
+    let protocol: HealthCheckProtocol = serde_json::from_value(json!("grpc")).unwrap();
+    assert_eq!(protocol, HealthCheckProtocol::Other("grpc".into()));
+    assert_eq!(serde_json::to_value(&protocol).unwrap(), "grpc");
+}
+
+#[test]
+fn removals_send_their_bodies() {
+    before();
+
+    let client = replay(vec![
+        interaction(
+            "DELETE",
+            "/load_balancers/123/droplets",
+            json!({ "droplet_ids": [1, 2] }),
+            204,
+            Value::Null,
+        ),
+        interaction(
+            "DELETE",
+            "/load_balancers/123/forwarding_rules",
+            json!({
+                "forwarding_rules": [
+                    {
+                        "entry_protocol": "tcp",
+                        "entry_port": 22,
+                        "target_protocol": "tcp",
+                        "target_port": 22,
+                        "certificate_id": Value::Null,
+                        "tls_passthrough": false,
+                    },
+                ],
+            }),
+            204,
+            Value::Null,
+        ),
+    ]);
+
+    LoadBalancer::get("123")
+        .remove_droplets(vec![1, 2])
+        .execute(&client)
+        .unwrap();
+    LoadBalancer::get("123")
+        .remove_forwarding_rules(vec![(
+            Protocol::Tcp,
+            22,
+            Protocol::Tcp,
+            22,
+            None::<String>,
+            false,
+        )])
+        .execute(&client)
+        .unwrap();
+}
@@ -0,0 +1,158 @@
+extern crate chrono;
+extern crate digitalocean;
+#[macro_use]
+extern crate log;
+#[macro_use]
+extern crate serde_json;
+extern crate url;
+
+mod utils;
+
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpListener;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use chrono::{Duration, Utc};
+use digitalocean::oauth::{OAuthApp, RefreshingToken, Scope, Token, TokenProvider};
+use url::Url;
+
+use crate::utils::before;
+
+#[test]
+fn authorize_url_is_correct() {
+    before();
+
+    let app = OAuthApp::new("client", "secret", "https://example.com/callback");
+    let url = app.authorize_url(
+        &[Scope::Read, Scope::Custom("droplet:create".into())],
+        "xyz",
+    );
+    info!("{:#?}", app);
+
+    assert_eq!(
+        url.as_str(),
+        "https://cloud.digitalocean.com/v1/oauth/authorize\
+         ?client_id=client\
+         &redirect_uri=https%3A%2F%2Fexample.com%2Fcallback\
+         &response_type=code\
+         &scope=read+droplet%3Acreate\
+         &state=xyz"
+    );
+    assert!(!format!("{:?}", app).contains("secret"));
+}
+
+#[test]
+fn token_expiry_and_scopes() {
+    before();
+
+    let token: Token = serde_json::from_value(json!({
+        "access_token": "access",
+        "refresh_token": "refresh",
+        "expires_at": Utc::now() + Duration::days(30),
+        "scope": "read write",
+    }))
+    .unwrap();
+    info!("{:#?}", token);
+
+    assert!(!token.is_expired());
+    assert_eq!(token.scopes(), vec![Scope::Read, Scope::Write]);
+
+    let debug = format!("{:?}", token);
+    assert!(!debug.contains("access\""));
+    assert!(!debug.contains("refresh\""));
+    assert!(debug.contains("read write"));
+
+    // Tokens about to expire are treated as expired so they are refreshed early.
+    let expiring = Token::new(
+        "access",
+        Some("refresh"),
+        Some(Utc::now() + Duration::seconds(10)),
+    );
+    assert!(expiring.is_expired());
+    assert!(!Token::new("access", None, None).is_expired());
+}
+
+#[test]
+fn refreshing_token_provides_current_token() {
+    before();
+
+    let app = OAuthApp::new("client", "secret", "https://example.com/callback");
+    let provider = RefreshingToken::new(app.clone(), Token::new("access", Some("refresh"), None));
+
+    assert_eq!(provider.token().unwrap(), "access");
+
+    // Without a refresh token an expired token cannot be renewed.
+    let expired = Token::new("access", None, Some(Utc::now() - Duration::hours(1)));
+    assert!(RefreshingToken::new(app, expired).token().is_err());
+}
+
+#[test]
+fn refreshing_token_renews_an_expired_token() {
+    before();
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let server = thread::spawn(move || {
+        let (stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(stream);
+        let mut request = String::new();
+        reader.read_line(&mut request).unwrap();
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            if line == "\r\n" {
+                break;
+            }
+        }
+        let body = json!({
+            "access_token": "new-access",
+            "refresh_token": "new-refresh",
+            "expires_in": 2592000,
+            "scope": "read",
+        })
+        .to_string();
+        let mut stream = reader.into_inner();
+        write!(
+            stream,
+            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            body.len(),
+            body
+        )
+        .unwrap();
+        request
+    });
+
+    let oauth_url = Url::parse(&format!("http://127.0.0.1:{}/v1/oauth/", port)).unwrap();
+    let app =
+        OAuthApp::new("client", "secret", "https://example.com/callback").oauth_url(oauth_url);
+    let expired = Token::new(
+        "old-access",
+        Some("old-refresh"),
+        Some(Utc::now() - Duration::hours(1)),
+    );
+    let refreshed = Arc::new(Mutex::new(Vec::new()));
+    let provider = {
+        let refreshed = refreshed.clone();
+        RefreshingToken::new(app, expired).on_refresh(move |token| {
+            refreshed
+                .lock()
+                .unwrap()
+                .push(token.refresh_token().clone());
+        })
+    };
+
+    assert_eq!(provider.token().unwrap(), "new-access");
+    let request = server.join().unwrap();
+    info!("{}", request);
+    assert!(request
+        .starts_with("POST /v1/oauth/token?grant_type=refresh_token&refresh_token=old-refresh "));
+
+    // The new token is kept, so is not refreshed again.
+    assert_eq!(provider.token().unwrap(), "new-access");
+    assert!(!provider.current().is_expired());
+    assert_eq!(
+        *refreshed.lock().unwrap(),
+        vec![Some(String::from("new-refresh"))]
+    );
+}
@@ -0,0 +1,81 @@
+extern crate digitalocean;
+#[macro_use]
+extern crate log;
+#[macro_use]
+extern crate serde_json;
+extern crate url;
+extern crate url_serde;
+
+mod utils;
+
+use serde_json::Value;
+
+use digitalocean::api::{Droplet, OneClick, OneClickInstallation};
+use digitalocean::method::{Create, List};
+use digitalocean::request::Request;
+
+use crate::utils::before;
+
+#[test]
+fn list_produces_correct_request() {
+    before();
+
+    let correct_url = "https://api.digitalocean.com/v2/1-clicks";
+
+    let req: Request<List, Vec<OneClick>> = OneClick::list();
+    info!("{:#?}", req);
+
+    assert_eq!(req.url().as_str(), correct_url);
+    assert_eq!(*req.body(), Value::Null);
+}
+
+#[test]
+fn list_by_kind_produces_correct_request() {
+    before();
+
+    let correct_url = "https://api.digitalocean.com/v2/1-clicks?type=kubernetes";
+
+    let req: Request<List, Vec<OneClick>> = OneClick::list().kubernetes();
+    info!("{:#?}", req);
+
+    assert_eq!(req.url().as_str(), correct_url);
+    assert_eq!(*req.body(), Value::Null);
+}
+
+#[test]
+fn install_kubernetes_produces_correct_request() {
+    before();
+
+    let correct_url = "https://api.digitalocean.com/v2/1-clicks/kubernetes";
+    let cluster = "50a994b6-c303-438f-9495-7e896cfe6b08";
+    let addons = vec!["kube-state-metrics", "loki"];
+
+    let req: Request<Create, OneClickInstallation> =
+        OneClick::install_kubernetes(cluster.to_string(), addons.clone());
+    info!("{:#?}", req);
+
+    assert_eq!(req.url().as_str(), correct_url);
+    assert_eq!(
+        *req.body(),
+        json!({
+            "addon_slugs": addons,
+            "cluster_uuid": cluster,
+        })
+    );
+}
+
+#[test]
+fn usable_as_droplet_image() {
+    before();
+
+    let one_click: OneClick = serde_json::from_value(json!({
+        "slug": "wordpress-20-04",
+        "type": "droplet",
+    }))
+    .unwrap();
+
+    let req: Request<Create, Droplet> = Droplet::create("bear", "tor1", "5gb", &one_click);
+    info!("{:#?}", req);
+
+    assert_eq!(req.body()["image"], json!("wordpress-20-04"));
+}
@@ -0,0 +1,95 @@
+extern crate digitalocean;
+#[macro_use]
+extern crate log;
+#[macro_use]
+extern crate serde_json;
+
+mod utils;
+
+use digitalocean::api::{Droplet, Readiness};
+use digitalocean::error::Error;
+use serde_json::Value;
+use std::net::{Ipv4Addr, TcpListener};
+use std::time::Duration;
+
+use crate::utils::{before, droplet, interaction, replay};
+
+// A Droplet as returned while it is being polled.
+fn poll(status: &str, networks: Value) -> Value {
+    let mut droplet = droplet(1, "web-1", "s-1vcpu-1gb");
+    droplet["status"] = json!(status);
+    droplet["networks"] = networks;
+    interaction(
+        "GET",
+        "/droplets/1",
+        Value::Null,
+        200,
+        json!({ "droplet": droplet }),
+    )
+}
+
+fn created() -> Droplet {
+    let mut droplet = droplet(1, "web-1", "s-1vcpu-1gb");
+    droplet["status"] = json!("new");
+    serde_json::from_value(droplet).unwrap()
+}
+
+fn networks() -> Value {
+    json!({
+        "v4": [
+            {
+                "ip_address": "10.108.0.2",
+                "netmask": "255.255.240.0",
+                "gateway": "nil",
+                "type": "private",
+            },
+            {
+                "ip_address": "127.0.0.1",
+                "netmask": "255.255.240.0",
+                "gateway": "127.0.0.254",
+                "type": "public",
+            },
+        ],
+        "v6": [],
+    })
+}
+
+#[test]
+fn wait_until_ready_polls_for_addresses_and_port() {
+    before();
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+
+    let client = replay(vec![
+        poll("active", json!({ "v4": [], "v6": [] })),
+        poll("active", networks()),
+    ]);
+    let readiness = Readiness::new()
+        .private_ipv4(true)
+        .port(port)
+        .poll_interval(Duration::from_millis(10));
+
+    let droplet = created().wait_until_ready(&client, &readiness).unwrap();
+    info!("{:#?}", droplet);
+
+    assert_eq!(droplet.public_ipv4(), Some(Ipv4Addr::new(127, 0, 0, 1)));
+    assert_eq!(droplet.private_ipv4(), Some(Ipv4Addr::new(10, 108, 0, 2)));
+    assert_eq!(droplet.ipv6(), None);
+}
+
+#[test]
+fn wait_until_ready_times_out() {
+    before();
+
+    let client = replay(vec![poll("active", networks())]);
+    let readiness = Readiness::new()
+        .ipv6(true)
+        .poll_interval(Duration::from_millis(10))
+        .timeout(Duration::from_millis(5));
+
+    match created().wait_until_ready(&client, &readiness) {
+        Err(Error::Timeout(_)) => (),
+        result => panic!("Expected a timeout, got {:?}", result),
+    }
+}
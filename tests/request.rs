@@ -0,0 +1,91 @@
+extern crate digitalocean;
+#[macro_use]
+extern crate log;
+#[macro_use]
+extern crate serde_json;
+extern crate url;
+extern crate url_serde;
+
+mod utils;
+
+use serde_json::Value;
+
+use digitalocean::api::{Droplet, SshKey};
+use digitalocean::error::Error;
+use digitalocean::method::{Create, Delete, Get};
+use digitalocean::request::Request;
+
+use crate::utils::before;
+
+#[test]
+fn to_curl_produces_correct_command() {
+    before();
+
+    let req = SshKey::create("it's me", "ssh-rsa AAAA");
+    info!("{:#?}", req);
+
+    assert_eq!(
+        req.to_curl(None),
+        "curl -X POST 'https://api.digitalocean.com/v2/account/keys' \
+         -H \"Authorization: Bearer $DIGITALOCEAN_TOKEN\" \
+         -H 'Content-Type: application/json' \
+         -d '{\"name\":\"it'\\''s me\",\"public_key\":\"ssh-rsa AAAA\"}'"
+    );
+    assert_eq!(
+        Droplet::delete(123).to_curl(Some("token")),
+        "curl -X DELETE 'https://api.digitalocean.com/v2/droplets/123' \
+         -H 'Authorization: Bearer token'"
+    );
+}
+
+#[test]
+fn to_http_produces_correct_request() {
+    before();
+
+    let req = Droplet::get(123).reboot();
+
+    assert_eq!(
+        req.to_http(None),
+        "POST /v2/droplets/123/actions HTTP/1.1\r\n\
+         Host: api.digitalocean.com\r\n\
+         Authorization: Bearer <REDACTED>\r\n\
+         Content-Type: application/json\r\n\
+         Content-Length: 17\r\n\
+         \r\n\
+         {\"type\":\"reboot\"}"
+    );
+    assert_eq!(
+        Droplet::get(123).to_http(Some("token")),
+        "GET /v2/droplets/123 HTTP/1.1\r\n\
+         Host: api.digitalocean.com\r\n\
+         Authorization: Bearer token\r\n\
+         \r\n"
+    );
+}
+
+#[test]
+fn from_http_round_trips() {
+    before();
+
+    let req = Droplet::get(123).reboot();
+    let parsed: Request<Create, Value> = Request::from_http(&req.to_http(None)).unwrap();
+    info!("{:#?}", parsed);
+
+    assert_eq!(parsed.url(), req.url());
+    assert_eq!(*parsed.body(), json!({ "type": "reboot" }));
+
+    let parsed: Request<Get, Droplet> = Request::from_http(
+        "GET https://api.digitalocean.com/v2/droplets?tag_name=web HTTP/1.1\n\n",
+    )
+    .unwrap();
+    assert_eq!(
+        parsed.url().as_str(),
+        "https://api.digitalocean.com/v2/droplets?tag_name=web"
+    );
+    assert_eq!(*parsed.body(), Value::Null);
+
+    match Request::<Delete, ()>::from_http(&req.to_http(None)) {
+        Err(Error::InvalidRequest(_)) => (),
+        result => panic!("Expected an invalid request, got {:?}", result),
+    }
+}
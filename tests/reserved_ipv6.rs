@@ -0,0 +1,85 @@
+extern crate digitalocean;
+#[macro_use]
+extern crate log;
+#[macro_use]
+extern crate serde_json;
+extern crate url;
+extern crate url_serde;
+
+mod utils;
+
+use serde_json::Value;
+use std::net::Ipv6Addr;
+use std::str::FromStr;
+
+use digitalocean::api::ReservedIpv6;
+use digitalocean::method::{Create, Delete, Get, List};
+use digitalocean::request::Request;
+
+use crate::utils::before;
+
+#[test]
+fn list_produces_correct_request() {
+    before();
+
+    let correct_url = "https://api.digitalocean.com/v2/reserved_ipv6";
+
+    let req: Request<List, Vec<ReservedIpv6>> = ReservedIpv6::list();
+    info!("{:#?}", req);
+
+    assert_eq!(req.url().as_str(), correct_url);
+    assert_eq!(*req.body(), Value::Null);
+}
+
+#[test]
+fn for_region_produces_correct_request() {
+    before();
+
+    let correct_url = "https://api.digitalocean.com/v2/reserved_ipv6";
+    let region_id = "tor1";
+
+    let req: Request<Create, ReservedIpv6> = ReservedIpv6::for_region(region_id);
+    info!("{:#?}", req);
+
+    assert_eq!(req.url().as_str(), correct_url);
+    assert_eq!(
+        *req.body(),
+        json!({
+            "region_slug": region_id,
+        })
+    );
+}
+
+#[test]
+fn get_produces_correct_request() {
+    before();
+
+    let reserved_ipv6 = Ipv6Addr::from_str("2409:40d0:f7:1017:74b4:3a96:105e:4c6e").unwrap();
+    let correct_url = format!(
+        "https://api.digitalocean.com/v2/reserved_ipv6/{}",
+        reserved_ipv6
+    );
+
+    let req: Request<Get, ReservedIpv6> = ReservedIpv6::get(reserved_ipv6);
+    info!("{:#?}", req);
+
+    assert_eq!(req.url().as_str(), correct_url);
+    assert_eq!(*req.body(), Value::Null);
+}
+
+#[test]
+fn delete_produces_correct_request() {
+    before();
+
+    let reserved_ipv6 = Ipv6Addr::from_str("2409:40d0:f7:1017:74b4:3a96:105e:4c6e").unwrap();
+    let correct_url = format!(
+        "https://api.digitalocean.com/v2/reserved_ipv6/{}",
+        reserved_ipv6
+    );
+
+    let req: Request<Delete, ()> = ReservedIpv6::delete(reserved_ipv6);
+    info!("{:#?}", req);
+
+    assert_eq!(req.url().as_str(), correct_url);
+    assert_eq!(*req.body(), Value::Null);
+}
@@ -0,0 +1,64 @@
+extern crate digitalocean;
+#[macro_use]
+extern crate log;
+#[macro_use]
+extern crate serde_json;
+extern crate url;
+extern crate url_serde;
+
+mod utils;
+
+use std::net::Ipv6Addr;
+use std::str::FromStr;
+
+use digitalocean::api::{Action, ReservedIpv6};
+use digitalocean::method::Create;
+use digitalocean::request::Request;
+
+use crate::utils::before;
+
+#[test]
+fn assign_produces_correct_request() {
+    before();
+
+    let reserved_ipv6 = Ipv6Addr::from_str("2409:40d0:f7:1017:74b4:3a96:105e:4c6e").unwrap();
+    let correct_url = format!(
+        "https://api.digitalocean.com/v2/reserved_ipv6/{}/actions",
+        reserved_ipv6
+    );
+    let droplet_id = 123;
+
+    let req: Request<Create, Action> = ReservedIpv6::get(reserved_ipv6).assign(droplet_id);
+    info!("{:#?}", req);
+
+    assert_eq!(req.url().as_str(), correct_url);
+    assert_eq!(
+        *req.body(),
+        json!({
+            "type": "assign",
+            "droplet_id": droplet_id
+        })
+    );
+}
+
+#[test]
+fn unassign_produces_correct_request() {
+    before();
+
+    let reserved_ipv6 = Ipv6Addr::from_str("2409:40d0:f7:1017:74b4:3a96:105e:4c6e").unwrap();
+    let correct_url = format!(
+        "https://api.digitalocean.com/v2/reserved_ipv6/{}/actions",
+        reserved_ipv6
+    );
+
+    let req: Request<Create, Action> = ReservedIpv6::get(reserved_ipv6).unassign();
+    info!("{:#?}", req);
+
+    assert_eq!(req.url().as_str(), correct_url);
+    assert_eq!(
+        *req.body(),
+        json!({
+            "type": "unassign",
+        })
+    );
+}
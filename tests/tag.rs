@@ -12,9 +12,9 @@ use serde_json::Value;
 
 use digitalocean::api::Tag;
 use digitalocean::method::{Create, Delete, Get, List};
-use digitalocean::request::Request;
+use digitalocean::request::{Executable, Request};
 
-use crate::utils::before;
+use crate::utils::{before, interaction, list, replay};
 
 #[test]
 fn list_produces_correct_request() {
@@ -22,7 +22,7 @@ fn list_produces_correct_request() {
 
     let correct_url = "https://api.digitalocean.com/v2/tags";
 
-    let req: Request<List, Tag> = Tag::list();
+    let req: Request<List, Vec<Tag>> = Tag::list();
     info!("{:#?}", req);
 
     assert_eq!(req.url().as_str(), correct_url);
@@ -133,3 +133,64 @@ fn remove_resources_produces_correct_request() {
         })
     );
 }
+
+#[test]
+fn remove_resources_sends_its_body() {
+    before();
+
+    let client = replay(vec![interaction(
+        "DELETE",
+        "/tags/web/resources",
+        json!({ "resources": [{ "resource_id": "1", "resource_type": "droplet" }] }),
+        204,
+        Value::Null,
+    )]);
+
+    Tag::get("web")
+        .remove_resources(vec![("1", "droplet")])
+        .execute(&client)
+        .unwrap();
+}
+
+#[test]
+fn add_resources_accepts_no_content() {
+    before();
+
+    let client = replay(vec![interaction(
+        "POST",
+        "/tags/web/resources",
+        json!({ "resources": [{ "resource_id": "1", "resource_type": "droplet" }] }),
+        204,
+        Value::Null,
+    )]);
+
+    Tag::get("web")
+        .add_resources(vec![("1", "droplet")])
+        .execute(&client)
+        .unwrap();
+}
+
+#[test]
+fn list_returns_every_tag() {
+    before();
+
+    let client = replay(vec![interaction(
+        "GET",
+        "/tags?per_page=200",
+        Value::Null,
+        200,
+        list(
+            "tags",
+            vec![
+                json!({ "name": "web", "resources": {} }),
+                json!({ "name": "db", "resources": {} }),
+            ],
+        ),
+    )]);
+
+    let tags = Tag::list().execute(&client).unwrap();
+    info!("{:#?}", tags);
+
+    assert_eq!(tags.len(), 2);
+    assert_eq!(tags[1].name(), "db");
+}
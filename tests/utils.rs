@@ -1,8 +1,124 @@
+// Each test crate uses only some of these.
+#![allow(dead_code)]
+
 extern crate dotenv;
 extern crate env_logger;
 
+use digitalocean::cassette::{Cassette, Interaction};
+use digitalocean::DigitalOcean;
+use serde_json::{json, Value};
+
+/// The API URL requests in cassettes are made to.
+pub const API_URL: &str = "https://api.digitalocean.com/v2";
+
 pub fn before() {
     // Setup for tests
     dotenv::dotenv().ok();
     env_logger::try_init().ok();
 }
+
+/// An interaction of a cassette, answering a request for `path` (under
+/// `API_URL`) with `response`. A `body` of `Value::Null` means none was sent.
+pub fn interaction(method: &str, path: &str, body: Value, status: u16, response: Value) -> Value {
+    json!({
+        "request": { "method": method, "url": format!("{}{}", API_URL, path), "body": body },
+        "response": { "status": status, "body": response.to_string() },
+    })
+}
+
+/// A client which replays `interactions`, in order.
+pub fn replay(interactions: Vec<Value>) -> DigitalOcean {
+    let interactions: Vec<Interaction> =
+        serde_json::from_value(Value::Array(interactions)).unwrap();
+    DigitalOcean::new("unused")
+        .unwrap()
+        .cassette(Cassette::from_interactions(interactions))
+}
+
+/// A page of a list, holding every item.
+pub fn list(key: &str, items: Vec<Value>) -> Value {
+    json!({ key: items, "links": {}, "meta": { "total": items.len() } })
+}
+
+pub fn region() -> Value {
+    json!({
+        "name": "New York 3",
+        "slug": "nyc3",
+        "features": [],
+        "available": true,
+        "sizes": [],
+    })
+}
+
+/// An active Droplet in `nyc3`, without addresses or tags.
+pub fn droplet(id: usize, name: &str, size: &str) -> Value {
+    json!({
+        "id": id,
+        "name": name,
+        "memory": 1024,
+        "vcpus": 1,
+        "disk": 25,
+        "locked": false,
+        "status": "active",
+        "kernel": null,
+        "created_at": "2020-07-21T18:37:44Z",
+        "next_backup_window": null,
+        "image": {
+            "id": 63663980,
+            "name": "20.04 (LTS) x64",
+            "distribution": "Ubuntu",
+            "slug": "ubuntu-20-04-x64",
+            "public": true,
+            "regions": ["nyc3"],
+            "created_at": "2020-05-15T05:47:50Z",
+            "type": "snapshot",
+            "min_disk_size": 20,
+            "size_gigabytes": 2.36,
+        },
+        "size": {
+            "slug": size,
+            "memory": 1024,
+            "vcpus": 1,
+            "disk": 25,
+            "transfer": 1.0,
+            "price_monthly": 5.0,
+            "price_hourly": 0.007,
+            "regions": ["nyc3"],
+            "available": true,
+        },
+        "size_slug": size,
+        "networks": { "v4": [], "v6": [] },
+        "region": region(),
+        "tags": [],
+        "vpc_uuid": null,
+    })
+}
+
+/// A detached volume in `nyc3`.
+pub fn volume(name: &str, size: usize) -> Value {
+    json!({
+        "id": "506f78a4-e098-11e5-ad9f-000f53306ae1",
+        "region": region(),
+        "droplet_ids": [],
+        "name": name,
+        "description": "",
+        "size_gigabytes": size,
+        "created_at": "2020-07-21T18:37:44Z",
+    })
+}
+
+/// An action on Droplet 1, as returned when it is started or polled.
+pub fn action(id: usize, kind: &str, status: &str) -> Value {
+    json!({
+        "action": {
+            "id": id,
+            "status": status,
+            "type": kind,
+            "started_at": "2020-07-21T18:37:44Z",
+            "completed_at": null,
+            "resource_id": 1,
+            "resource_type": "droplet",
+            "region_slug": "nyc3",
+        }
+    })
+}
@@ -39,6 +39,45 @@ fn list_produces_correct_request() {
     assert_eq!(*req.body(), Value::Null);
 }
 
+#[test]
+fn list_by_name_produces_correct_request() {
+    before();
+
+    let (name, region) = ("bear", "tor1");
+    let correct_url = format!(
+        "https://api.digitalocean.com/v2/volumes?name={}&region={}",
+        name, region
+    );
+
+    let req: Request<List, Vec<Volume>> = Volume::list().name(name).region(region);
+    info!("{:#?}", req);
+
+    assert_eq!(req.url().as_str(), correct_url);
+    assert_eq!(*req.body(), Value::Null);
+}
+
+#[test]
+fn create_with_filesystem_produces_correct_request() {
+    before();
+
+    let (name, size) = ("bear", 123);
+
+    let req: Request<Create, Volume> = Volume::create(name, size)
+        .filesystem_type("ext4")
+        .filesystem_label("data");
+    info!("{:#?}", req);
+
+    assert_eq!(
+        *req.body(),
+        json!({
+            "name": name,
+            "size_gigabytes": size,
+            "filesystem_type": "ext4",
+            "filesystem_label": "data",
+        })
+    );
+}
+
 #[test]
 fn create_produces_correct_request() {
     before();
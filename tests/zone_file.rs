@@ -0,0 +1,187 @@
+extern crate digitalocean;
+#[macro_use]
+extern crate log;
+#[macro_use]
+extern crate serde_json;
+extern crate url;
+extern crate url_serde;
+
+mod utils;
+
+use serde_json::Value;
+
+use digitalocean::api::{Domain, DomainRecord};
+use digitalocean::error::Error;
+
+use crate::utils::before;
+
+const ZONE: &str = r#"
+$ORIGIN example.com.
+$TTL 1h
+@       IN  SOA ns1.digitalocean.com. hostmaster.example.com. (
+                2021010101 ; serial
+                3600 600 604800 1800 )
+        IN  NS  ns1.digitalocean.com.
+        IN  A   192.0.2.1
+        IN  MX  10 mail
+www     300 IN CNAME @
+mail        A   192.0.2.2
+            AAAA 2001:db8::2
+_sip._tcp   IN SRV 10 5 5060 sip.example.net.
+@           CAA 0 issue "letsencrypt.org"
+@           TXT "v=spf1 include:_spf.example.net " "-all" ; spf
+sub         NS  ns1.example.net.
+
+$ORIGIN dev.example.com.
+api         1d  A 192.0.2.3
+"#;
+
+#[test]
+fn from_zone_file_produces_correct_requests() {
+    before();
+
+    let correct_url = "https://api.digitalocean.com/v2/domains/example.com/records";
+
+    let reqs = Domain::get("example.com")
+        .records()
+        .from_zone_file(ZONE)
+        .unwrap();
+    info!("{:#?}", reqs);
+
+    for req in &reqs {
+        assert_eq!(req.url().as_str(), correct_url);
+    }
+    let bodies = reqs
+        .iter()
+        .map(|req| req.body().clone())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        bodies,
+        vec![
+            json!({ "type": "A", "name": "@", "data": "192.0.2.1", "ttl": 3600 }),
+            json!({
+                "type": "MX",
+                "name": "@",
+                "data": "mail.example.com.",
+                "priority": 10,
+                "ttl": 3600,
+            }),
+            json!({ "type": "CNAME", "name": "www", "data": "example.com.", "ttl": 300 }),
+            json!({ "type": "A", "name": "mail", "data": "192.0.2.2", "ttl": 3600 }),
+            json!({ "type": "AAAA", "name": "mail", "data": "2001:db8::2", "ttl": 3600 }),
+            json!({
+                "type": "SRV",
+                "name": "_sip._tcp",
+                "data": "sip.example.net.",
+                "priority": 10,
+                "weight": 5,
+                "port": 5060,
+                "ttl": 3600,
+            }),
+            json!({
+                "type": "CAA",
+                "name": "@",
+                "data": "letsencrypt.org",
+                "flags": 0,
+                "tag": "issue",
+                "ttl": 3600,
+            }),
+            json!({
+                "type": "TXT",
+                "name": "@",
+                "data": "v=spf1 include:_spf.example.net -all",
+                "ttl": 3600,
+            }),
+            json!({ "type": "NS", "name": "sub", "data": "ns1.example.net.", "ttl": 3600 }),
+            json!({ "type": "A", "name": "api.dev", "data": "192.0.2.3", "ttl": 86400 }),
+        ]
+    );
+}
+
+#[test]
+fn from_zone_file_rejects_invalid_zones() {
+    before();
+
+    let invalid = vec![
+        "www IN A not-an-address",
+        "www IN PTR example.com.",
+        "www.example.org. IN A 192.0.2.1",
+        "$INCLUDE other.zone",
+        "www IN TXT \"unterminated",
+        "www IN MX ( 10 mail",
+    ];
+
+    for zone in invalid {
+        let result = Domain::get("example.com").records().from_zone_file(zone);
+        info!("{:#?}", result);
+
+        match result {
+            Err(Error::InvalidZoneFile(_)) => (),
+            _ => panic!("Expected {:?} to be rejected.", zone),
+        }
+    }
+}
+
+#[test]
+fn to_zone_file_round_trips() {
+    before();
+
+    let domain: Domain = serde_json::from_value(json!({
+        "name": "example.com",
+        "ttl": 1800,
+        "zone_file": Value::Null,
+    }))
+    .unwrap();
+    let record = |kind: &str, name: &str, data: &str, priority: Value| -> DomainRecord {
+        serde_json::from_value(json!({
+            "id": 1,
+            "type": kind,
+            "name": name,
+            "data": data,
+            "priority": priority,
+            "port": null,
+            "ttl": 1800,
+            "weight": null,
+        }))
+        .unwrap()
+    };
+    let long_text = "a".repeat(300);
+    let records = vec![
+        record("SOA", "@", "1800", Value::Null),
+        record("A", "@", "192.0.2.1", Value::Null),
+        record("CNAME", "www", "@", Value::Null),
+        record("MX", "@", "mail.example.com", json!(10)),
+        record("TXT", "@", &long_text, Value::Null),
+    ];
+
+    let zone = domain.to_zone_file(&records);
+    info!("{}", zone);
+
+    assert!(!zone.contains("SOA"));
+    assert!(zone.starts_with("$ORIGIN example.com.\n$TTL 1800\n"));
+    assert!(zone.contains("@\t1800\tIN\tMX\t10 mail.example.com.\n"));
+
+    let reqs = Domain::get("example.com")
+        .records()
+        .from_zone_file(&zone)
+        .unwrap();
+    let bodies = reqs
+        .iter()
+        .map(|req| req.body().clone())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        bodies,
+        vec![
+            json!({ "type": "A", "name": "@", "data": "192.0.2.1", "ttl": 1800 }),
+            json!({ "type": "CNAME", "name": "www", "data": "example.com.", "ttl": 1800 }),
+            json!({
+                "type": "MX",
+                "name": "@",
+                "data": "mail.example.com.",
+                "priority": 10,
+                "ttl": 1800,
+            }),
+            json!({ "type": "TXT", "name": "@", "data": long_text, "ttl": 1800 }),
+        ]
+    );
+}